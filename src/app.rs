@@ -1,153 +1,505 @@
-use crate::config::Theme;
+use crate::config::{AccentConfig, AccessibilityConfig, AppConfig, DemoConfig, DictationConfig, KeyboardGeometry, KeyboardLayout, LastWord, LiveConfig, ProgressMode, Reveal, ScoreFormula, Theme};
+use crate::card;
+use crate::packs;
 use crate::history;
 use crate::models::{
-    AppState, Mode, QuoteData, WordData, Word, WordState
+    AppState, FailReason, FailRules, Mode, QuoteCredit, QuoteData, QuoteEntry, QuoteSelector, QuoteThresholds, ResultsTab, WordData
 };
+use crate::session::{Session, SessionResult};
+use crate::sources::CustomText;
+use crate::text::{CharStats, TypedText};
 use crate::utils::strings;
-use crate::generator::WordGenerator;
+use crate::clock::{Clock, SystemClock};
+use crate::generator::recent_quotes::RecentQuotes;
+use crate::opponents::{Opponent, PaceBot};
+use crate::ui::racers::Racer;
+use crate::generator::{sourcing, GeneratedWords, NumberStyles, RepeatRules, StreamTail, WordGenerator};
+use crate::generator::difficulty::difficulty;
 use anyhow::{Context, Result};
 use rust_embed::RustEmbed;
-use std::time::Instant;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::{Duration, Instant};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread;
+use std::ops::Range;
+
+/// lines of text on screen during a test
+pub const VISIBLE_LINES: usize = 3;
 
 #[derive(RustEmbed)]
 #[folder = "resources/"]
 struct Asset;
 
+/// a pack from the pack directory wins over the embedded one of the same name
+pub fn load_word_data(language: &str) -> Result<WordData> {
+    if let Some(words) = packs::load_word_data(language) {
+        return words;
+    }
+    let word_filename = format!("language/{}.json", language);
+    let word_file = Asset::get(&word_filename).context(format!(
+        "Could not find embedded language file: {}",
+        word_filename
+    ))?;
+    let w_str = std::str::from_utf8(word_file.data.as_ref())?;
+    Ok(serde_json::from_str(w_str)?)
+}
+
+/// names of the embedded word lists, for anything that wants to go through all of them
+pub fn languages() -> Vec<String> {
+    let mut names: Vec<String> = Asset::iter()
+        .filter_map(|f| f.strip_prefix("language/")?.strip_suffix(".json").map(str::to_string))
+        .collect();
+    names.sort();
+    names
+}
+
+/// every language there's a word list for, embedded or in the pack directory
+pub fn all_languages() -> Vec<String> {
+    let mut names = languages();
+    names.extend(packs::installed());
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// a language with no quotes of its own gets an empty set, so its words can still be typed
+pub fn load_quote_data(language: &str) -> Result<QuoteData> {
+    if let Some(quotes) = packs::load_quote_data(language) {
+        return quotes;
+    }
+    let quote_filename = format!("quotes/{}.json", language);
+    let Some(quote_file) = Asset::get(&quote_filename) else {
+        return Ok(no_quotes(language));
+    };
+    let q_str = std::str::from_utf8(quote_file.data.as_ref())?;
+    Ok(serde_json::from_str(q_str)?)
+}
+
+fn no_quotes(language: &str) -> QuoteData {
+    QuoteData { language: language.to_string(), groups: Vec::new(), quotes: Vec::new() }
+}
+
+/// the quotes for a quote-mode test in `language`, or `fallback`'s when it has none, with a
+/// word about it either way. other modes never look at quotes, so they don't load any.
+pub fn quotes_for(mode: &Mode, language: &str, fallback: Option<&str>) -> Result<(QuoteData, Option<String>)> {
+    if !matches!(mode, Mode::Quote(_)) {
+        return Ok((no_quotes(language), None));
+    }
+    let quotes = load_quote_data(language)?;
+    if !quotes.quotes.is_empty() {
+        return Ok((quotes, None));
+    }
+    if let Some(fallback) = fallback.filter(|&f| f != language) {
+        let borrowed = load_quote_data(fallback)?;
+        if !borrowed.quotes.is_empty() {
+            return Ok((borrowed, Some(format!("no {} quotes yet, these are {}", language, fallback))));
+        }
+    }
+    let notice = format!("no {} quotes yet: try time or words mode, or set fallback = \"english\" under [quotes]", language);
+    Ok((quotes, Some(notice)))
+}
+
+/// what the command line asked for. everything else comes from config or the embedded assets.
+#[derive(Debug, Clone)]
+pub struct TestOptions {
+    pub mode: Mode,
+    pub language: String,
+    pub use_numbers: bool,
+    pub use_punctuation: bool,
+    pub allow_repeats: bool,
+    /// quotes chained into one quote-mode test
+    pub quote_count: usize,
+    pub fail_rules: FailRules,
+    pub practice: bool,
+    /// race a bot that types at this many wpm
+    pub pace_wpm: Option<f64>,
+    /// type this instead, as a single quote. the mode and quote options are ignored.
+    pub custom_text: Option<CustomText>,
+    /// fill the word list's sentence templates instead of drawing words one at a time
+    pub sentences: bool,
+    /// a space pressed mid-word goes in as a mistake instead of skipping to the next word
+    pub strict_space: bool,
+    /// short easy words, no punctuation or numbers, a cheer for every word and friendlier results
+    pub kids: bool,
+    /// hide the words ahead, revealed as `[dictation]` in config says
+    pub dictation: bool,
+    /// add each finished test to the classroom results, under this student's name
+    pub classroom: Option<crate::classroom::Export>,
+}
+
+impl Default for TestOptions {
+    fn default() -> Self {
+        Self {
+            mode: Mode::Time(60),
+            language: "english".to_string(),
+            use_numbers: false,
+            use_punctuation: false,
+            allow_repeats: false,
+            quote_count: 1,
+            fail_rules: FailRules::default(),
+            practice: false,
+            pace_wpm: None,
+            custom_text: None,
+            sentences: false,
+            strict_space: false,
+            kids: false,
+            dictation: false,
+            classroom: None,
+        }
+    }
+}
+
 pub struct SessionConfig {
     pub mode: Mode,
     pub theme: Theme,
+    /// the theme as configured, before a mode's accent takes over its main color
+    pub(crate) base_theme: Theme,
+    pub(crate) accents: AccentConfig,
     pub use_numbers: bool,
     pub use_punctuation: bool,
     pub word_data: WordData,
     pub quote_data: QuoteData,
-    pub(crate) word_generator: WordGenerator,
+    pub quote_thresholds: QuoteThresholds,
+    pub quote_count: usize,
+    pub fail_rules: FailRules,
+    pub practice: bool,
+    pub progress_mode: ProgressMode,
+    pub terminal_title: bool,
+    pub pause_on_lock: bool,
+    /// typing time in one sitting before a break is suggested. None never suggests one.
+    pub break_after: Option<Duration>,
+    pub keyboard_layout: KeyboardLayout,
+    pub keyboard_geometry: KeyboardGeometry,
+    pub accessibility: AccessibilityConfig,
+    pub live: LiveConfig,
+    pub demo: DemoConfig,
+    pub score_formula: ScoreFormula,
+    /// how quickly a wrong key has to be backspaced to be forgiven. None turns settled accuracy off.
+    pub forgive_window: Option<Duration>,
+    /// a space pressed mid-word goes in as a mistake instead of skipping to the next word
+    pub strict_space: bool,
+    /// the tutor for children: a cheer for every word typed right, and results in stars
+    pub kids: bool,
+    /// which words are hidden. None shows them all.
+    pub dictation: Option<DictationConfig>,
+    pub classroom: Option<crate::classroom::Export>,
+    /// what a time test does with a word that's half typed when the clock runs out
+    pub last_word: LastWord,
+    /// how long past the limit a half-typed word can be finished in, with `LastWord::Grace`
+    pub grace_window: Duration,
+    /// confetti on a new personal best. off in the linear layout, which has no overlays, and
+    /// with reduced motion.
+    pub confetti: bool,
+    /// off for detached apps, which never write to the history file
+    pub save_history: bool,
+    /// None when repeats are allowed, so quote selection doesn't consult or update it.
+    pub recent_quotes: Option<RecentQuotes>,
+    pub(crate) word_generator: Arc<WordGenerator>,
 }
 
 pub struct TestState {
     pub state: AppState,
 
-    pub input: String,
-    pub cursor_idx: usize,
     pub start_time: Option<Instant>,
+    /// set while a running test is paused. the clock stands still until it's resumed.
+    pub paused_at: Option<Instant>,
 
     pub gross_char_count: usize,
-    pub total_errors_ever: usize,
-    pub processed_word_errors: HashSet<usize>,
+    /// incorrect keystrokes later taken back with backspace
+    pub corrected_errors: usize,
+    /// backspaces that deleted something, right or wrong
+    pub backspaces: usize,
+    /// spaces pressed mid-word with strict space on, each typed in as a mistake
+    pub stray_spaces: usize,
 
     pub generated_count: usize,
-    pub scrolled_word_count: usize,
-    pub furthest_word_idx: usize,
-
-    pub st_correct: usize,
-    pub st_incorrect: usize,
-    pub st_extra: usize,
-    pub st_missed: usize,
-
-    pub acc_score_correct: isize,
-    pub acc_score_incorrect: isize,
-
-    pub uncorrected_errors_scrolled: usize,
 
     pub live_correct_keystrokes: usize,
     pub live_incorrect_keystrokes: usize,
+    /// incorrect keystrokes that were backspaced inside the forgiveness window
+    pub forgiven_keystrokes: usize,
+    /// when the last key went in wrong, as long as nothing but backspace has come since
+    pub pending_mistake: Option<Instant>,
 
     pub final_wpm: f64,
     pub final_raw_wpm: f64,
     pub final_accuracy: f64,
     pub final_consistency: f64,
     pub final_time: f64,
-
-    pub current_quote_source: String,
-
-    pub word_stream: Vec<Word>,
-    pub word_stream_string: String,
-
-    pub visual_lines: Vec<String>,
-    pub display_string: String,
-    pub display_mask: Vec<bool>,
-    pub extra_char_count: usize,
-
-    pub missed_chars: HashMap<usize, usize>,
-    /// The renderer uses this instead of self.input so missed positions render correctly.
-    pub aligned_input: Vec<char>,
+    /// None unless a score formula is configured
+    pub final_score: Option<f64>,
+    /// how hard the text that was typed through is, around 1.0
+    pub final_difficulty: Option<f64>,
+    /// keystroke accuracy with quickly fixed mistakes forgiven. None unless a window is configured.
+    pub final_settled_accuracy: Option<f64>,
+    /// set when a fail rule ended the test early
+    pub fail_reason: Option<FailReason>,
+
+    /// one entry per quote in the stream, in typing order
+    pub quote_sources: Vec<QuoteCredit>,
+
+    /// the words from the top visible line on, with what's been typed into each.
+    /// everything shown and scored comes from here.
+    pub text: TypedText,
+    /// word ranges of the wrapped lines, from the top one to just past the caret's
+    pub visual_lines: Vec<Range<usize>>,
 
     pub quote_pool: Vec<String>,
     pub total_quote_words: usize,
     pub original_quote_length: usize,
-    pub next_word_index: usize,
 
     pub is_new_best: bool,
+    /// where the wpm falls among past results in the same mode, when there are enough of them
+    pub rank: Option<Rank>,
+    pub is_new_best_score: bool,
+    pub finished_at: Option<Instant>,
+    /// when the last word was typed right, for the kids' cheer
+    pub cheered_at: Option<Instant>,
+    /// the word being typed, counted from the start, and when the caret got to it
+    pub word_reached: (usize, Instant),
 
     /// reset on every new test so the blink phase always starts visible.
     pub caret_epoch: Instant,
 
-    // append-only record of every word ever in the stream, including scrolled-off ones.
-    // word_stream_string is trimmed on scroll so it can't be used for retry.
-    pub cumulative_words: Vec<String>,
+    /// what the generator needs to carry the stream on, without looking back over it
+    pub(crate) tail: StreamTail,
 
     pub wpm_history: Vec<(f64, f64)>,
     pub raw_wpm_history: Vec<(f64, f64)>,
     pub errors_history: Vec<(f64, f64)>,
+    /// how many of each second's errors were later backspaced, in step with errors_history
+    pub corrected_history: Vec<(f64, f64)>,
+    /// for each wrong keystroke still standing, oldest first, the snapshot it's counted in
+    pub(crate) open_errors: Vec<usize>,
+    /// corrections to errors made since the last snapshot
+    pub(crate) pending_corrected: usize,
     pub(crate) last_snapshot_second: u64,
     pub(crate) prev_incorrect_keystrokes: usize,
     pub(crate) prev_gross_char_count: usize,
 
     pub burst_wpm_history: Vec<f64>,
+
+    /// every keystroke that changed the text, at seconds into the test. None is a backspace.
+    pub keystrokes: Vec<(f64, Option<char>)>,
+    /// keystrokes by the key they should have been
+    pub key_tallies: BTreeMap<char, KeyTally>,
+    /// words that took a wrong keystroke, fixed or not, counted from the start
+    pub missed_words: BTreeSet<usize>,
+
+    pub results_tab: ResultsTab,
+    /// the snapshot picked out on the results chart
+    pub chart_cursor: Option<usize>,
+    /// lines scrolled down the words and keys tabs
+    pub results_scroll: u16,
+    /// when the replay tab was opened, so the replay starts from the beginning
+    pub replay_started: Option<Instant>,
+    /// where the results card was saved, or why it couldn't be
+    pub card_notice: Option<String>,
+    /// the session has gone on long enough that the results screen suggests a break
+    pub break_due: bool,
 }
 
-impl Default for TestState {
-    fn default() -> Self {
+impl TestState {
+    /// `epoch` is when the caret starts blinking, by the app's clock
+    pub fn new(epoch: Instant) -> Self {
         Self {
             state: AppState::Waiting,
-            input: String::new(),
-            cursor_idx: 0,
             start_time: None,
+            paused_at: None,
             gross_char_count: 0,
-            total_errors_ever: 0,
-            processed_word_errors: HashSet::new(),
+            corrected_errors: 0,
+            backspaces: 0,
+            stray_spaces: 0,
             generated_count: 0,
-            scrolled_word_count: 0,
-            furthest_word_idx: 0,
-            st_correct: 0,
-            st_incorrect: 0,
-            st_extra: 0,
-            st_missed: 0,
-            acc_score_correct: 0,
-            acc_score_incorrect: 0,
-            uncorrected_errors_scrolled: 0,
             live_correct_keystrokes: 0,
             live_incorrect_keystrokes: 0,
+            forgiven_keystrokes: 0,
+            pending_mistake: None,
             final_wpm: 0.0,
             final_raw_wpm: 0.0,
             final_accuracy: 0.0,
             final_consistency: 0.0,
             final_time: 0.0,
-            current_quote_source: String::new(),
-            word_stream: Vec::new(),
-            word_stream_string: String::new(),
+            final_score: None,
+            final_difficulty: None,
+            final_settled_accuracy: None,
+            fail_reason: None,
+            quote_sources: Vec::new(),
+            text: TypedText::default(),
             visual_lines: Vec::new(),
-            display_string: String::new(),
-            display_mask: Vec::new(),
-            extra_char_count: 0,
-            missed_chars: HashMap::new(),
-            aligned_input: Vec::new(),
             quote_pool: Vec::new(),
             total_quote_words: 0,
             original_quote_length: 0,
-            next_word_index: 0,
             is_new_best: false,
-            caret_epoch: Instant::now(),
-            cumulative_words: Vec::new(),
+            rank: None,
+            is_new_best_score: false,
+            finished_at: None,
+            cheered_at: None,
+            word_reached: (0, epoch),
+            caret_epoch: epoch,
+            tail: StreamTail::default(),
             wpm_history: Vec::new(),
             raw_wpm_history: Vec::new(),
             errors_history: Vec::new(),
+            corrected_history: Vec::new(),
+            open_errors: Vec::new(),
+            pending_corrected: 0,
             last_snapshot_second: u64::MAX,
             prev_incorrect_keystrokes: 0,
             prev_gross_char_count: 0,
             burst_wpm_history: Vec::new(),
+            keystrokes: Vec::new(),
+            key_tallies: BTreeMap::new(),
+            missed_words: BTreeSet::new(),
+            results_tab: ResultsTab::Summary,
+            chart_cursor: None,
+            results_scroll: 0,
+            replay_started: None,
+            card_notice: None,
+            break_due: false,
         }
     }
+
+    /// every source in the stream on one line. empty outside quote mode.
+    pub fn quote_source_label(&self) -> String {
+        self.quote_sources.iter().map(|c| c.source.as_str()).collect::<Vec<_>>().join("; ")
+    }
+
+    /// every word the stream still holds, scrolled off ones included, for a retry on the
+    /// same text. made when asked for, rather than kept alongside the text. a test that's run
+    /// past `KEPT_WORDS` has let go of its first words, so they aren't in it.
+    pub fn words(&self) -> Vec<String> {
+        self.text.all_words().map(|w| w.target().iter().collect()).collect()
+    }
+
+    /// the licenses of the quotes in the stream, each once, or none if no pack gave one
+    pub fn quote_license_label(&self) -> Option<String> {
+        credit_label(self.quote_sources.iter().filter_map(|c| c.license.as_deref()))
+    }
+
+    /// the attribution links of the quotes in the stream, each once
+    pub fn quote_attribution_label(&self) -> Option<String> {
+        credit_label(self.quote_sources.iter().filter_map(|c| c.attribution_url.as_deref()))
+    }
+}
+
+fn credit_label<'a>(values: impl Iterator<Item = &'a str>) -> Option<String> {
+    let mut seen: Vec<&str> = Vec::new();
+    for value in values {
+        if !seen.contains(&value) { seen.push(value); }
+    }
+    (!seen.is_empty()).then(|| seen.join("; "))
+}
+
+/// where this test sits in a multi-test run like `typa warmup`
+#[derive(Debug, Clone)]
+pub struct SequenceStep {
+    /// the run's name, or whose turn it is
+    pub name: String,
+    pub index: usize,
+    pub total: usize,
+    /// restarting goes back to the same words instead of new ones
+    pub fixed_words: bool,
+}
+
+/// a correction drill: rounds of just the words the last test went wrong on, until one's clean
+#[derive(Debug, Clone)]
+pub struct Drill {
+    pub round: usize,
+    /// the test to go back to once it's over
+    mode: Mode,
+    fail_rules: FailRules,
+}
+
+/// the next test's words, being made on a thread while the results are up
+struct Prewarm {
+    /// what they were made for. anything else gets words of its own.
+    mode: Mode,
+    words: Receiver<GeneratedWords>,
+}
+
+/// incorrect keystrokes over a whole test, and how many of them were backspaced
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ErrorCounts {
+    pub made: usize,
+    pub corrected: usize,
+}
+
+impl ErrorCounts {
+    pub fn uncorrected(&self) -> usize {
+        self.made - self.corrected
+    }
+}
+
+/// a result placed among the past ones in its mode
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rank {
+    pub top_percent: f64,
+    /// "60s", "25 word", "short quote"
+    pub mode: String,
+}
+
+impl Rank {
+    /// "top 12% of your 60s tests"
+    pub fn describe(&self) -> String {
+        format!("top {:.0}% of your {} tests", self.top_percent, self.mode)
+    }
+}
+
+/// keystrokes meant for one key, and how many of them went wrong
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct KeyTally {
+    pub presses: usize,
+    pub misses: usize,
+}
+
+impl KeyTally {
+    pub fn accuracy(&self) -> f64 {
+        if self.presses == 0 { return 100.0; }
+        (self.presses - self.misses) as f64 / self.presses as f64 * 100.0
+    }
+}
+
+/// what a finished test contributes to a multi-test summary
+#[derive(Debug, Clone)]
+pub struct StepSummary {
+    pub label: String,
+    pub wpm: f64,
+    pub raw_wpm: f64,
+    pub accuracy: f64,
+    pub consistency: f64,
+    pub time: f64,
+    pub failed: bool,
+}
+
+impl StepSummary {
+    /// a head to head: faster wins, accuracy settles a tie, and a failed test loses to one
+    /// that wasn't
+    pub fn beats(&self, other: &StepSummary) -> bool {
+        match (self.failed, other.failed) {
+            (false, true) => true,
+            (true, false) | (true, true) => false,
+            (false, false) => {
+                let (wpm, other_wpm) = (self.wpm.round(), other.wpm.round());
+                wpm > other_wpm || (wpm == other_wpm && self.accuracy > other.accuracy)
+            }
+        }
+    }
+}
+
+/// what `typa calibrate` settles on from its tests: the middle wpm of the ones finished for
+/// the pace bot, and four fifths of that as a floor for `--min-wpm`. none with fewer than two.
+pub fn calibrated_speeds(results: &[StepSummary]) -> Option<(u64, u64)> {
+    let mut wpms: Vec<f64> = results.iter().filter(|r| !r.failed).map(|r| r.wpm).collect();
+    if wpms.len() < 2 {
+        return None;
+    }
+    wpms.sort_by(|a, b| a.total_cmp(b));
+    let mid = wpms.len() / 2;
+    let median = if wpms.len() % 2 == 1 { wpms[mid] } else { (wpms[mid - 1] + wpms[mid]) / 2.0 };
+    let pace = median.round().max(1.0);
+    Some((pace as u64, (pace * 0.8).round().max(1.0) as u64))
 }
 
 pub struct App {
@@ -155,50 +507,158 @@ pub struct App {
     pub show_ui: bool,
     pub terminal_width: u16,
     pub last_test_words: Option<Vec<String>>,
+    pub sequence: Option<SequenceStep>,
 
     pub config: SessionConfig,
     pub test: TestState,
     pub discord: Option<crate::discord::DiscordPresence>,
+    pub statusline: Option<crate::statusline::StatusLine>,
+    pub clock: Box<dyn Clock>,
+    /// everyone racing alongside the player. they outlive restarts, like the rest of the session.
+    pub opponents: Vec<Box<dyn Opponent>>,
+    /// the idle demo is typing. nothing it does is saved, announced or counted.
+    pub demo: bool,
+    /// the player's last test, set aside while the demo types so a repeat doesn't replay the demo
+    words_before_demo: Option<Vec<String>>,
+    /// the tests finished since launch
+    pub session: Session,
+    /// set while a correction drill is running. its rounds aren't saved or counted toward bests.
+    pub drill: Option<Drill>,
+    /// the release check, while it's still out
+    pub update_check: Option<crate::update::UpdateCheck>,
+    /// a release newer than this one, for the footer
+    pub newer_version: Option<String>,
+    /// wrap the text to this many columns instead of a share of the terminal, in a pane
+    pub text_width: Option<u16>,
+    /// something about how the session was set up that the player should know, like quotes
+    /// borrowed from another language. shown while each test waits to start.
+    pub notice: Option<String>,
+    /// how the last classroom result's export went, for the results screen
+    pub classroom_notice: Option<String>,
+    pub uploads: crate::classroom::Uploads,
+    prewarm: Option<Prewarm>,
+    /// lifetime totals, kept up to date for the waiting screen when it shows them
+    pub lifetime: Option<history::counters::Counters>,
+    /// progress toward the configured quotas, topped up as tests are saved
+    pub quotas: Vec<history::quotas::QuotaProgress>,
 }
 
 impl App {
-    pub fn new(
-        mode: Mode,
-        language: String,
-        use_numbers: bool,
-        use_punctuation: bool,
-        theme: Theme,
-    ) -> Result<Self> {
-        let word_filename = format!("language/{}.json", language);
-        let word_file = Asset::get(&word_filename).context(format!(
-            "Could not find embedded language file: {}",
-            word_filename
-        ))?;
-        let w_str = std::str::from_utf8(word_file.data.as_ref())?;
-        let word_data: WordData = serde_json::from_str(w_str)?;
-
-        let quote_filename = format!("quotes/{}.json", language);
-        let quote_file = Asset::get(&quote_filename).context(format!(
-            "Could not find embedded quotes file: {}",
-            quote_filename
-        ))?;
-        let q_str = std::str::from_utf8(quote_file.data.as_ref())?;
-        let quote_data: QuoteData = serde_json::from_str(q_str)?;
-
-        let word_generator = WordGenerator::new(
+    pub fn new(options: TestOptions, app_config: AppConfig) -> Result<Self> {
+        let records = if app_config.statusline.enabled || !app_config.quotas.is_empty() {
+            history::load_history().unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        let statusline = app_config.statusline.enabled
+            .then(|| crate::statusline::StatusLine::new(&app_config.statusline, &records));
+        let lifetime = app_config.live.totals.then(history::counters::Counters::load_or_count);
+        let quotas = app_config.quotas.clone();
+        let mut app = Self::build(options, app_config, Box::new(SystemClock), RecentQuotes::load)?;
+        app.config.save_history = true;
+        app.lifetime = lifetime;
+        app.quotas = history::quotas::progress(&quotas, &records, app.local_now());
+        app.statusline = statusline;
+        app.statusline_idle();
+
+        let mut dp = crate::discord::DiscordPresence::new();
+        if dp.connected {
+            use crate::ui::utils::quote_idle_label;
+            let ql = match &app.config.mode {
+                Mode::Quote(q) => quote_idle_label(q, app.test.original_quote_length, &app.config.quote_thresholds),
+                _ => "",
+            };
+            dp.set_idle(&app.config.mode, app.config.use_punctuation, app.config.use_numbers, ql, &app.config.word_data.name);
+            app.discord = Some(dp);
+        } else {
+            app.discord = None;
+        }
+
+        Ok(app)
+    }
+
+    /// an app that never talks to discord or writes history, running on whatever clock it's given.
+    /// the quotes it's shown are remembered only as long as it runs.
+    pub fn detached(options: TestOptions, app_config: AppConfig, clock: Box<dyn Clock>) -> Result<Self> {
+        Self::build(options, app_config, clock, RecentQuotes::default)
+    }
+
+    fn build(options: TestOptions, app_config: AppConfig, clock: Box<dyn Clock>, recent_quotes: fn() -> RecentQuotes) -> Result<Self> {
+        let TestOptions { mut mode, language, mut use_numbers, mut use_punctuation, mut allow_repeats, quote_count, fail_rules, mut practice, pace_wpm, custom_text, mut sentences, mut strict_space, kids, dictation, classroom } = options;
+
+        let mut word_data = load_word_data(&language)?;
+        // kids get the easy words and nothing to trip on. their results stay out of the bests.
+        if kids {
+            word_data.words = sourcing::kids_words(&word_data.words);
+            (use_numbers, use_punctuation, sentences) = (false, false, false);
+            (practice, strict_space) = (true, true);
+        }
+        let (mut quote_data, notice) = match custom_text {
+            Some(_) => (no_quotes(&language), None),
+            None => quotes_for(&mode, &language, app_config.quotes.fallback.as_deref())?,
+        };
+        // custom text stands in as the only quote there is, so nothing else has to know about it
+        if let Some(custom) = custom_text {
+            let entry = QuoteEntry { length: custom.text.chars().count(), text: custom.text, source: custom.source, attribution_url: None, license: None, id: 1 };
+            quote_data.quotes = vec![entry];
+            mode = Mode::Quote(QuoteSelector::Id(1));
+            allow_repeats = true;
+        }
+        let quote_thresholds = sourcing::bucket_quotes(&mut quote_data, &app_config.quotes);
+
+        let mut word_generator = WordGenerator::new(
             word_data.clone(),
             use_numbers,
             use_punctuation,
-        );
+        ).with_repeats(RepeatRules {
+            window: app_config.words.repeat_window,
+            retries: app_config.words.repeat_retries,
+            forbid: app_config.words.no_repeats,
+        }).with_number_styles(NumberStyles {
+            roman: app_config.numbers.roman,
+            units: app_config.numbers.units,
+            dimensions: app_config.numbers.dimensions,
+            times: app_config.numbers.times,
+        });
+        if sentences || (app_config.words.sentences && !kids) {
+            word_generator = word_generator.with_sentences()?;
+        }
 
+        let theme = Theme { main: app_config.accents.main_for(&mode, &app_config.theme), ..app_config.theme.clone() };
         let config = SessionConfig {
             mode,
             theme,
+            base_theme: app_config.theme,
+            accents: app_config.accents,
             use_numbers,
             use_punctuation,
             word_data,
             quote_data,
-            word_generator,
+            quote_thresholds,
+            quote_count,
+            fail_rules,
+            practice,
+            progress_mode: app_config.terminal.progress,
+            terminal_title: app_config.terminal.title,
+            pause_on_lock: app_config.terminal.pause_on_lock,
+            keyboard_layout: app_config.keyboard.layout,
+            keyboard_geometry: app_config.keyboard.geometry,
+            break_after: app_config.breaks.after_minutes.filter(|&m| m > 0).map(|m| Duration::from_secs(m * 60)),
+            confetti: app_config.effects.confetti && app_config.accessibility.animates() && !app_config.accessibility.is_linear(),
+            accessibility: app_config.accessibility,
+            live: app_config.live,
+            demo: app_config.demo,
+            score_formula: app_config.score.formula,
+            forgive_window: app_config.accuracy.forgive_ms.map(Duration::from_millis),
+            strict_space: strict_space || app_config.accuracy.strict_space,
+            kids,
+            dictation: dictation.then_some(app_config.dictation),
+            classroom,
+            last_word: app_config.time.last_word,
+            grace_window: Duration::from_millis(app_config.time.grace_ms),
+            save_history: false,
+            recent_quotes: (!allow_repeats).then(recent_quotes),
+            word_generator: Arc::new(word_generator),
         };
 
         let mut app = Self {
@@ -206,32 +666,335 @@ impl App {
             show_ui: true,
             terminal_width: 80,
             last_test_words: None,
+            sequence: None,
             config,
-            test: TestState::default(),
+            test: TestState::new(clock.now()),
             discord: None,
+            statusline: None,
+            clock,
+            opponents: Vec::new(),
+            demo: false,
+            words_before_demo: None,
+            session: Session::default(),
+            drill: None,
+            update_check: None,
+            newer_version: None,
+            text_width: None,
+            notice,
+            classroom_notice: None,
+            uploads: Default::default(),
+            prewarm: None,
+            lifetime: None,
+            quotas: Vec::new(),
         };
+        if let Some(wpm) = pace_wpm {
+            app.opponents.push(Box::new(PaceBot { wpm }));
+        }
 
         app.generate_initial_words();
+        Ok(app)
+    }
 
-        let mut dp = crate::discord::DiscordPresence::new();
-        if dp.connected {
-            use crate::ui::utils::quote_idle_label;
-            let ql = match &app.config.mode {
-                Mode::Quote(q) => quote_idle_label(q, app.test.original_quote_length),
-                _ => "",
-            };
-            dp.set_idle(&app.config.mode, app.config.use_punctuation, app.config.use_numbers, ql, &app.config.word_data.name);
-            app.discord = Some(dp);
-        } else {
-            app.discord = None;
+    fn save_record(&mut self, completed: bool) {
+        if self.config.save_history && !self.demo && self.drill.is_none() {
+            if let Ok(Some((record, totals))) = history::record_test(self, completed) {
+                if self.lifetime.is_some() {
+                    self.lifetime = Some(totals);
+                }
+                for quota in &mut self.quotas {
+                    quota.add(&record);
+                }
+            }
         }
+    }
 
-        Ok(app)
+    /// starts over any quota whose window has ended since it was counted, as one does
+    /// overnight with typa left open. true if any did.
+    pub fn roll_quotas(&mut self) -> bool {
+        if self.quotas.is_empty() { return false; }
+        let now = self.local_now();
+        let mut rolled = false;
+        for quota in &mut self.quotas {
+            rolled |= quota.roll(now);
+        }
+        rolled
+    }
+
+    /// the words the test took a wrong keystroke in, each once, in the order they came
+    pub fn missed_words(&self) -> Vec<String> {
+        let mut words: Vec<String> = Vec::new();
+        let (dropped, _) = self.test.text.dropped();
+        for (i, word) in self.test.text.all_words().enumerate() {
+            let i = dropped + i;
+            let target: String = word.target().iter().collect();
+            if self.test.missed_words.contains(&i) && !words.contains(&target) {
+                words.push(target);
+            }
+        }
+        words
+    }
+
+    /// a finished test that went wrong somewhere can be drilled, unless it's one of a run
+    pub fn can_drill(&self) -> bool {
+        self.test.state == AppState::Finished && self.sequence.is_none() && !self.config.kids
+            && !self.test.missed_words.is_empty()
+    }
+
+    /// from a finished test, a round of the words it went wrong on. after a clean round, the
+    /// drill is over and a test like the one it started from comes up.
+    pub fn drill(&mut self) {
+        if self.test.state != AppState::Finished { return; }
+        let words = self.missed_words();
+        if words.is_empty() {
+            if let Some(drill) = self.drill.take() {
+                self.config.mode = drill.mode;
+                self.config.fail_rules = drill.fail_rules;
+                self.apply_accent();
+                self.restart_test();
+            }
+            return;
+        }
+
+        let drill = self.drill.get_or_insert_with(|| Drill {
+            round: 0,
+            mode: self.config.mode.clone(),
+            fail_rules: self.config.fail_rules,
+        });
+        drill.round += 1;
+        self.config.mode = Mode::Words(words.len());
+        self.config.fail_rules = FailRules::default();
+        self.apply_accent();
+        self.last_test_words = Some(words);
+        self.retry_last_test();
+    }
+
+    /// the mode's accent as the main color, after the mode has changed
+    fn apply_accent(&mut self) {
+        self.config.theme.main = self.config.accents.main_for(&self.config.mode, &self.config.base_theme);
+    }
+
+    /// puts the break reminder away, and starts counting toward the next one
+    pub fn dismiss_break(&mut self) {
+        self.test.break_due = false;
+        self.session.take_break();
+    }
+
+    /// notes how a classroom upload went once it's done. true if one was.
+    pub fn poll_uploads(&mut self) -> bool {
+        let Some(outcome) = self.uploads.poll() else { return false };
+        self.note_upload(outcome);
+        true
+    }
+
+    /// gives uploads still going a few seconds to finish before typa quits
+    pub fn finish_uploads(&mut self) {
+        if let Some(outcome) = self.uploads.wait(Duration::from_secs(5)) {
+            self.note_upload(outcome);
+        }
+    }
+
+    fn note_upload(&mut self, outcome: Result<String>) {
+        let sent = crate::classroom::upload_notice(&outcome);
+        self.classroom_notice = Some(match self.classroom_notice.take() {
+            Some(append) => format!("{} · {}", append, sent),
+            None => sent,
+        });
+    }
+
+    /// saves an svg card of the finished test, and notes where it went for the results screen
+    pub fn save_card(&mut self) {
+        if self.test.state != AppState::Finished || self.test.fail_reason.is_some() { return; }
+        let record = history::build_record(self, true);
+        self.test.card_notice = Some(match card::save(&record, &self.config.theme) {
+            Ok(path) => format!("card saved to {}", path.display()),
+            Err(e) => format!("couldn't save the card: {}", e),
+        });
+    }
+
+    pub fn now(&self) -> Instant {
+        self.clock.now()
+    }
+
+    pub fn local_now(&self) -> chrono::DateTime<chrono::Local> {
+        self.clock.local_now()
+    }
+
+    /// time since the first keystroke, by the app's clock, not counting time spent paused
+    pub fn elapsed(&self) -> Option<Duration> {
+        let now = self.test.paused_at.unwrap_or_else(|| self.now());
+        self.test.start_time.map(|t| now.saturating_duration_since(t))
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.test.paused_at.is_some()
+    }
+
+    /// stops the clock on a running test
+    pub fn pause(&mut self) {
+        if self.test.state == AppState::Running && self.test.paused_at.is_none() {
+            self.test.paused_at = Some(self.now());
+        }
+    }
+
+    /// starts the clock again, moving the test's timestamps on by however long it was paused
+    pub fn resume(&mut self) {
+        let Some(paused_at) = self.test.paused_at.take() else { return };
+        let paused_for = self.now().saturating_duration_since(paused_at);
+        self.test.start_time = self.test.start_time.map(|t| t + paused_for);
+        self.test.pending_mistake = self.test.pending_mistake.map(|t| t + paused_for);
+    }
+
+    /// net wpm as of the last whole second. steadier than recomputing every frame.
+    /// None until the first second is in.
+    pub fn live_wpm(&self) -> Option<f64> {
+        if self.test.state != AppState::Running { return None; }
+        self.test.wpm_history.last().map(|&(_, w)| w)
+    }
+
+    /// how far through the personal best celebration we are, 0.0 to 1.0. None when there's
+    /// nothing to celebrate, it's turned off, or it's over.
+    pub fn celebration(&self) -> Option<f64> {
+        const LENGTH: Duration = Duration::from_millis(1200);
+        if !self.test.is_new_best || !self.config.confetti { return None; }
+        let since = self.now().saturating_duration_since(self.test.finished_at?);
+        (since < LENGTH).then(|| since.as_secs_f64() / LENGTH.as_secs_f64())
+    }
+
+    /// a word of praise for a moment after each word typed right, in kids mode
+    pub fn cheer(&self) -> Option<&'static str> {
+        const CHEERS: [&str; 6] = ["great!", "nice!", "super!", "yay!", "well done!", "awesome!"];
+        const LENGTH: Duration = Duration::from_millis(900);
+        if !self.config.kids || self.test.state != AppState::Running { return None; }
+        let since = self.now().saturating_duration_since(self.test.cheered_at?);
+        (since < LENGTH).then(|| CHEERS[self.test.text.closed_words() % CHEERS.len()])
+    }
+
+    /// in dictation, the first word from the top line with its untyped letters hidden.
+    /// with a flash, the word being typed shows until its time is up, or until the test starts.
+    pub fn hidden_from(&self) -> Option<usize> {
+        let dictation = self.config.dictation.as_ref()?;
+        let current = self.test.text.current_index();
+        let shown = match dictation.reveal {
+            Reveal::Current => true,
+            Reveal::Flash => {
+                let since = self.now().saturating_duration_since(self.test.word_reached.1);
+                self.test.state == AppState::Waiting || since < Duration::from_millis(dictation.flash_ms)
+            }
+        };
+        Some(current + shown as usize)
+    }
+
+    /// when the flashed dictation word hides, while it's still up. nothing else redraws then.
+    pub fn flash_deadline(&self) -> Option<Instant> {
+        let dictation = self.config.dictation.as_ref()?;
+        if dictation.reveal != Reveal::Flash || self.test.state != AppState::Running { return None; }
+        let deadline = self.test.word_reached.1 + Duration::from_millis(dictation.flash_ms);
+        (deadline > self.now()).then_some(deadline)
+    }
+
+    /// up to three stars for a kids test, all for accuracy
+    pub fn stars(&self) -> usize {
+        match self.test.final_accuracy {
+            a if a >= 98.0 => 3,
+            a if a >= 90.0 => 2,
+            a if a >= 75.0 => 1,
+            _ => 0,
+        }
+    }
+
+    /// the player plus every opponent, for the progress widget. empty when racing nobody.
+    pub fn racers(&self) -> Vec<Racer> {
+        if self.opponents.is_empty() {
+            return Vec::new();
+        }
+        let elapsed = self.elapsed().unwrap_or_default();
+        let mut racers = vec![Racer {
+            name: "you".to_string(),
+            words_done: self.words_done(),
+            wpm: self.live_wpm().unwrap_or(0.0),
+            is_you: true,
+        }];
+        for opponent in &self.opponents {
+            let pos = opponent.position(&self.test.text, elapsed);
+            racers.push(Racer { name: opponent.name(), words_done: pos.words_done, wpm: pos.wpm, is_you: false });
+        }
+        racers
+    }
+
+    /// words to the finish line, or None in time mode where there isn't one
+    pub fn race_length(&self) -> Option<f64> {
+        match self.config.mode {
+            Mode::Time(_) => None,
+            Mode::Words(w) => Some(w as f64),
+            Mode::Quote(_) => Some(self.test.total_quote_words as f64),
+        }
+    }
+
+    /// how far through the test we are, 0.0 to 1.0. None unless a test is running.
+    pub fn progress(&self) -> Option<f64> {
+        if self.test.state != AppState::Running { return None; }
+        match self.config.mode {
+            Mode::Time(limit) => {
+                let elapsed = self.elapsed()?.as_secs_f64();
+                Some((elapsed / limit.max(1) as f64).min(1.0))
+            }
+            _ => self.race_length().map(|total| self.words_done() / total.max(1.0)),
+        }
+    }
+
+    fn words_done(&self) -> f64 {
+        let text = &self.test.text;
+        let partial = text.current()
+            .map(|w| w.typed().len() as f64 / w.target().len().max(1) as f64)
+            .unwrap_or(0.0)
+            .min(1.0);
+        text.closed_words() as f64 + partial
+    }
+
+    pub fn caret_elapsed(&self) -> Duration {
+        self.now().saturating_duration_since(self.test.caret_epoch)
+    }
+
+    /// hands the keyboard to the idle demo
+    pub fn start_demo(&mut self) {
+        if !self.demo {
+            self.words_before_demo = self.last_test_words.clone();
+        }
+        self.demo = true;
+    }
+
+    /// throws away whatever the demo was typing and gets a fresh test ready.
+    /// a repeat after this is the player's last test again, not the demo's.
+    pub fn stop_demo(&mut self) {
+        self.restart_test();
+        self.last_test_words = self.words_before_demo.take();
+        self.demo = false;
+        self.statusline_idle();
+    }
+
+    fn statusline_idle(&mut self) {
+        if self.demo { return; }
+        let label = self.test_type_label();
+        if let Some(s) = self.statusline.as_mut() {
+            s.set_idle(&label);
+        }
+    }
+
+    fn statusline_running(&mut self) {
+        if self.demo { return; }
+        let label = self.test_type_label();
+        let wpm = self.live_wpm().unwrap_or(0.0);
+        if let Some(s) = self.statusline.as_mut() {
+            s.set_running(&label, wpm);
+        }
     }
 
     pub fn quit(&mut self) {
         if self.test.state == AppState::Running {
-            let _ = history::record_test(self, false);
+            self.save_record(false);
+        }
+        if let Some(s) = self.statusline.as_mut() {
+            s.close();
         }
         self.should_quit = true;
     }
@@ -241,6 +1004,12 @@ impl App {
         self.recalculate_lines();
     }
 
+    /// wraps the text to exactly `width` columns, for when it isn't drawn across the terminal
+    pub fn set_text_width(&mut self, width: u16) {
+        self.text_width = Some(width);
+        self.recalculate_lines();
+    }
+
     pub fn on_mouse(&mut self) {
         if self.test.state != AppState::Finished {
             self.show_ui = true;
@@ -249,18 +1018,20 @@ impl App {
 
     pub fn restart_test(&mut self) {
         if self.test.state == AppState::Running {
-            let _ = history::record_test(self, false);
+            self.save_record(false);
         }
-        if !self.test.cumulative_words.is_empty() {
-            self.last_test_words = Some(self.test.cumulative_words.clone());
+        // a finished test set them aside in end_test, before its text was cut at the caret
+        if self.test.state != AppState::Finished && !self.test.text.is_empty() {
+            self.last_test_words = Some(self.test.words());
         }
-        self.test = TestState::default();
+        self.test = TestState::new(self.clock.now());
         self.show_ui = true;
         self.generate_initial_words();
+        self.statusline_idle();
         if let Some(ref mut d) = self.discord {
             use crate::ui::utils::quote_idle_label;
             let ql = match &self.config.mode {
-                Mode::Quote(q) => quote_idle_label(q, self.test.original_quote_length),
+                Mode::Quote(q) => quote_idle_label(q, self.test.original_quote_length, &self.config.quote_thresholds),
                 _ => "",
             };
             d.set_idle(&self.config.mode, self.config.use_punctuation, self.config.use_numbers, ql, &self.config.word_data.name);
@@ -273,66 +1044,75 @@ impl App {
             _ => { self.restart_test(); return; }
         };
         if self.test.state == AppState::Running {
-            let _ = history::record_test(self, false);
+            self.save_record(false);
         }
-        self.test = TestState::default();
+        self.test = TestState::new(self.clock.now());
         self.show_ui = true;
         self.seed_from_word_list(words);
+        self.statusline_idle();
     }
 
-    fn seed_from_word_list(&mut self, words: Vec<String>) {
-        use crate::models::WordState;
+    pub(crate) fn seed_from_word_list(&mut self, words: Vec<String>) {
         let total = words.len();
-        let word_stream: Vec<Word> = words.iter().enumerate().map(|(i, text)| {
-            let mut w = Word::new(text.clone(), i);
-            if i == 0 { w.state = WordState::Active; }
-            w
-        }).collect();
-
-        self.test.word_stream      = word_stream;
+        self.test.text             = TypedText::new(words.iter().map(String::as_str));
         self.test.generated_count  = total;
-        self.test.next_word_index  = total;
-        self.test.cumulative_words = words;
+        self.test.tail             = self.config.word_generator.tail(&words);
 
         if matches!(self.config.mode, Mode::Quote(_)) {
             self.test.total_quote_words = total;
-        }
-
-        self.update_stream_string();
-        self.sync_display_text();
-
-        if matches!(self.config.mode, Mode::Quote(_)) {
-            self.test.original_quote_length = self.test.word_stream_string.chars().count();
+            self.test.original_quote_length = self.stream_char_count();
         }
 
         self.recalculate_lines();
     }
 
     pub fn check_time(&mut self) {
-        if self.test.state != AppState::Running { return; }
+        if self.test.state != AppState::Running || self.is_paused() { return; }
         self.record_snapshot_if_needed();
-        if let Some(start) = self.test.start_time {
-            let elapsed = start.elapsed().as_secs_f64();
-            if let Mode::Time(limit) = self.config.mode {
-                if elapsed >= limit as f64 { self.end_test(); }
+        if let Some(min) = self.config.fail_rules.min_wpm {
+            // the first few seconds are too noisy to judge anyone on
+            const MIN_WPM_GRACE_SECS: f64 = 5.0;
+            if let Some(&(secs, wpm)) = self.test.wpm_history.last() {
+                if secs >= MIN_WPM_GRACE_SECS && wpm < min {
+                    self.fail_test(FailReason::BelowMinWpm { wpm, min });
+                    return;
+                }
             }
         }
+        if self.time_is_up() { self.end_test(); }
+    }
+
+    /// past the time limit, and not still giving a half-typed word its grace to be finished in
+    fn time_is_up(&self) -> bool {
+        let (&Mode::Time(limit), Some(elapsed)) = (&self.config.mode, self.elapsed()) else { return false };
+        let Some(over) = elapsed.checked_sub(Duration::from_secs(limit)) else { return false };
+        let finishing = self.config.last_word == LastWord::Grace
+            && over < self.config.grace_window
+            && self.test.text.current().is_some_and(|w| !w.typed().is_empty() && !w.is_correct());
+        !finishing
+    }
+
+    fn fail_test(&mut self, reason: FailReason) {
+        self.test.fail_reason = Some(reason);
+        self.end_test();
     }
 
     pub fn end_test(&mut self) {
         self.test.state = AppState::Finished;
-        let duration_secs = self.test.start_time.map(|t| t.elapsed().as_secs_f64()).unwrap_or(1.0);
+        self.test.finished_at = Some(self.clock.now());
+        let duration_secs = self.elapsed().map(|d| d.as_secs_f64()).unwrap_or(1.0);
 
+        if !self.test.text.is_empty() {
+            self.last_test_words = Some(self.test.words());
+        }
         if let Mode::Time(_) = self.config.mode {
-            let typed_len = self.test.aligned_input.len();
-            if typed_len < self.test.display_string.chars().count() {
-                let truncated: String = self.test.display_string.chars().take(typed_len).collect();
-                self.test.display_string = truncated;
-                self.test.display_mask.truncate(typed_len);
+            if self.config.last_word == LastWord::Count {
+                self.test.text.complete_current();
             }
+            self.test.text.truncate_at_cursor();
         }
 
-        let total_correct_chars = self.test.st_correct + self.calculate_live_correct_chars();
+        let total_correct_chars = self.test.text.live_correct();
 
         self.test.final_raw_wpm = (self.test.gross_char_count as f64 / 5.0) * (60.0 / duration_secs);
         self.test.final_wpm    = (total_correct_chars as f64 / 5.0) * (60.0 / duration_secs);
@@ -343,8 +1123,25 @@ impl App {
         } else {
             0.0
         };
+        if let Some(min) = self.config.fail_rules.min_accuracy.filter(|_| self.test.fail_reason.is_none()) {
+            if self.test.final_accuracy < min {
+                self.test.fail_reason = Some(FailReason::BelowMinAccuracy { accuracy: self.test.final_accuracy, min });
+            }
+        }
+        let failed = self.test.fail_reason.is_some();
 
         self.test.final_time = duration_secs;
+        self.test.final_settled_accuracy = self.config.forgive_window.map(|_| {
+            let counted = total_keystrokes.saturating_sub(self.test.forgiven_keystrokes);
+            if counted > 0 {
+                (self.test.live_correct_keystrokes as f64 / counted as f64) * 100.0
+            } else {
+                0.0
+            }
+        });
+        self.test.final_score = self.config.score_formula.score(self.test.final_wpm, self.test.final_accuracy);
+        let targets: Vec<String> = self.test.text.all_words().map(|w| w.target().iter().collect()).collect();
+        self.test.final_difficulty = difficulty(targets.iter().map(String::as_str));
         self.show_ui = true;
 
         let last_full_second = if self.test.last_snapshot_second == u64::MAX {
@@ -360,17 +1157,19 @@ impl App {
 
         self.test.final_consistency = self.calculate_consistency();
 
-        self.check_personal_best();
+        if !failed && !self.config.practice && !self.demo && self.drill.is_none() {
+            self.check_personal_best();
+        }
 
-        if let Some(ref mut d) = self.discord {
-            let typed_words = self.test.scrolled_word_count
-                + self.test.input.split_whitespace().count();
+        let demo = self.demo;
+        if let Some(d) = self.discord.as_mut().filter(|_| !failed && !demo) {
+            let typed_words = self.test.text.started_words();
             let total_words = match self.config.mode {
                 Mode::Words(w) => w,
-                _ => self.test.total_quote_words.max(self.test.word_stream.len()),
+                _ => self.test.total_quote_words.max(self.test.text.len()),
             };
             use crate::ui::utils::get_quote_length_category;
-            let ql = get_quote_length_category(self.test.original_quote_length);
+            let ql = get_quote_length_category(self.test.original_quote_length, &self.config.quote_thresholds);
             d.set_result(
                 self.test.final_wpm,
                 self.test.final_accuracy,
@@ -378,7 +1177,7 @@ impl App {
                 self.test.is_new_best,
                 typed_words,
                 total_words,
-                &self.test.current_quote_source.clone(),
+                &self.test.quote_source_label(),
                 self.test.final_consistency,
                 self.config.use_punctuation,
                 self.config.use_numbers,
@@ -387,16 +1186,73 @@ impl App {
             );
         }
 
-        if !self.test.cumulative_words.is_empty() {
-            self.last_test_words = Some(self.test.cumulative_words.clone());
+        if !failed && !demo {
+            self.session.add(SessionResult {
+                wpm: self.test.final_wpm,
+                accuracy: self.test.final_accuracy,
+                secs: duration_secs,
+            }, self.clock.now());
+            self.test.break_due = self.config.break_after
+                .is_some_and(|after| self.session.since_break >= after.as_secs_f64());
+        }
+
+        self.save_record(!failed);
+        if let Some(export) = self.config.classroom.clone().filter(|_| !demo && self.drill.is_none()) {
+            let submission = crate::classroom::Submission::from_app(&export.student, self, self.local_now());
+            self.classroom_notice = crate::classroom::append(&export.path, &submission.row()).err()
+                .map(|e| format!("couldn't add the result to {}: {}", export.path.display(), e));
+            if let Some(server) = export.server {
+                self.uploads.send(server, submission);
+            }
+        }
+
+        let label = self.test_type_label();
+        if let Some(s) = self.statusline.as_mut().filter(|_| !demo) {
+            if failed {
+                s.set_failed(&label);
+            } else {
+                s.set_result(&label, self.test.final_wpm, self.test.final_accuracy);
+            }
         }
-        let _ = history::record_test(self, true);
+        self.start_prewarm();
+    }
+
+    /// starts on the next test's words, so tab doesn't wait on a long test or a slow source.
+    /// quotes are left alone: they're short, and picking one marks it as seen.
+    fn start_prewarm(&mut self) {
+        if matches!(self.config.mode, Mode::Quote(_)) || self.drill.is_some() { return; }
+        let (tx, words) = mpsc::channel();
+        let generator = Arc::clone(&self.config.word_generator);
+        let mode = self.config.mode.clone();
+        let quote_count = self.config.quote_count;
+        thread::spawn(move || {
+            let no_quotes = QuoteData { language: String::new(), groups: Vec::new(), quotes: Vec::new() };
+            let _ = tx.send(generator.generate_initial_words(&mode, quote_count, &no_quotes, None));
+        });
+        self.prewarm = Some(Prewarm { mode: self.config.mode.clone(), words });
+    }
+
+    /// the words made while the results were up, if they're for this test. waits on them if
+    /// they're not quite done, which is still sooner than starting over.
+    fn take_prewarmed(&mut self) -> Option<GeneratedWords> {
+        let prewarm = self.prewarm.take()?;
+        (prewarm.mode == self.config.mode).then(|| prewarm.words.recv().ok()).flatten()
+    }
+
+    /// words for the next test are ready or on their way
+    pub fn is_prewarmed(&self) -> bool {
+        self.prewarm.as_ref().is_some_and(|p| p.mode == self.config.mode)
+    }
+
+    /// `count` words as this app would make them, for a test whose words are set up front
+    pub fn generate_words(&self, count: usize) -> Vec<String> {
+        self.config.word_generator.generate_stream(count, &mut rand::rng())
     }
 
     fn push_snapshot(&mut self, elapsed_secs: f64) {
         if elapsed_secs <= 0.0 { return; }
 
-        let total_correct_chars = self.test.st_correct + self.calculate_live_correct_chars();
+        let total_correct_chars = self.test.text.live_correct();
         let raw_wpm = (self.test.gross_char_count as f64 / 5.0) * (60.0 / elapsed_secs);
         let net_wpm = (total_correct_chars as f64 / 5.0) * (60.0 / elapsed_secs);
 
@@ -413,12 +1269,14 @@ impl App {
         self.test.wpm_history.push((elapsed_secs, net_wpm));
         self.test.raw_wpm_history.push((elapsed_secs, raw_wpm));
         self.test.errors_history.push((elapsed_secs, errors_this_second));
+        self.test.corrected_history.push((elapsed_secs, self.test.pending_corrected as f64));
+        self.test.pending_corrected = 0;
     }
 
     pub fn record_snapshot_if_needed(&mut self) {
         if self.test.state != AppState::Running { return; }
-        if let Some(start) = self.test.start_time {
-            let elapsed_secs = start.elapsed().as_secs_f64();
+        if let Some(elapsed) = self.elapsed() {
+            let elapsed_secs = elapsed.as_secs_f64();
             let current_second = elapsed_secs.floor() as u64;
 
             if current_second >= 1 &&
@@ -426,19 +1284,26 @@ impl App {
             {
                 self.test.last_snapshot_second = current_second;
                 self.push_snapshot(current_second as f64);
+                self.statusline_running();
             }
         }
     }
 
     pub fn on_key(&mut self, c: char) {
         if self.test.state == AppState::Finished { return; }
+        // a space can't close the last word: the test ends when it's typed right instead. such a
+        // space goes nowhere, so it isn't scored or logged, and doesn't resume a paused test.
+        if c == ' ' && self.test.text.is_on_last_word() && !self.is_stray_space() { return; }
+        // typing picks a paused test back up
+        self.resume();
         if self.test.state == AppState::Waiting {
-            self.test.start_time = Some(Instant::now());
+            self.test.start_time = Some(self.clock.now());
             self.test.state = AppState::Running;
+            self.statusline_running();
             if let Some(ref mut d) = self.discord {
                 use crate::ui::utils::quote_idle_label;
                 let ql = match &self.config.mode {
-                    Mode::Quote(q) => quote_idle_label(q, self.test.original_quote_length),
+                    Mode::Quote(q) => quote_idle_label(q, self.test.original_quote_length, &self.config.quote_thresholds),
                     _ => "",
                 };
                 d.set_typing(&self.config.mode, self.config.use_punctuation, self.config.use_numbers, ql, &self.config.word_data.name);
@@ -447,244 +1312,262 @@ impl App {
 
         self.record_snapshot_if_needed();
 
-        let current_input_segments: Vec<&str> = self.test.input.split(' ').collect();
-        let word_idx = current_input_segments.len().saturating_sub(1);
+        let Some(word) = self.test.text.current() else { return };
+        let typed_len = word.typed().len();
+        let target_len = word.target().len();
 
-            if word_idx < self.test.word_stream.len() {
-                let target_word_struct = &self.test.word_stream[word_idx];
-                let target_word = &target_word_struct.text;
-                let user_current_word = current_input_segments.last().unwrap_or(&"");
+        if c == ' ' && typed_len == 0 { return; }
 
-                if c == ' ' && user_current_word.is_empty() { return; }
+        let stray_space = c == ' ' && self.is_stray_space();
+        let submits = c == ' ' && !stray_space;
 
-            let target_char_count = target_word.chars().count();
-            let user_char_count = user_current_word.chars().count();
+        if !submits {
+            // room for a few mistakes, but not a whole sentence of them
+            if typed_len >= target_len + 19 { return; }
 
-            // use char count for the limit, byte len is wrong for multi-byte chars like em dash
-            let limit = target_char_count + 19;
-            if user_char_count >= limit {
-                if c != ' ' { return; }
-            }
+            let is_extra = typed_len >= target_len;
+            if self.will_cause_visual_wrap(is_extra) { return; }
 
-            if c != ' ' {
-                let is_extra = user_char_count >= target_char_count;
-                if self.will_cause_visual_wrap(c, is_extra) { return; }
-
-                let is_finite_mode = matches!(self.config.mode, Mode::Words(_) | Mode::Quote(_));
-                if is_finite_mode {
-                    let last_word_idx = self.test.word_stream_string
-                        .split(' ')
-                        .count()
-                        .saturating_sub(1);
-                    if word_idx >= last_word_idx && user_char_count >= target_char_count {
-                        return;
-                    }
-                }
+            let is_finite_mode = matches!(self.config.mode, Mode::Words(_) | Mode::Quote(_));
+            if is_finite_mode && is_extra && self.test.text.is_on_last_word() {
+                return;
             }
         }
 
         self.show_ui = false;
         self.test.gross_char_count += 1;
 
-        // compare relative to the current word. global indices break when extra chars shift positions
-        let is_keystroke_correct = if word_idx < self.test.word_stream.len() {
-            let target_word = &self.test.word_stream[word_idx].text;
-            let user_current_word = current_input_segments.last().unwrap_or(&"");
-
-            if c == ' ' {
-                // word-level visual equality so hyphens typed against em-dash or en-dash counts as correct
-                Self::words_visually_equal(user_current_word, target_word)
-            } else {
-                let user_char_count = user_current_word.chars().count();
-                let target_char_count = target_word.chars().count();
-                if user_char_count < target_char_count {
-                    // use char index, not byte index. target_word may contain multi-byte chars
-                    let target_char = target_word.chars().nth(user_char_count).unwrap_or('\0');
-                    strings::are_characters_visually_equal(c, target_char)
-                } else {
-                    false
-                }
-            }
+        let is_keystroke_correct = if submits {
+            // word-level visual equality so hyphens typed against em-dash or en-dash counts as correct
+            word.is_correct()
         } else {
-            false
+            word.target().get(typed_len).is_some_and(|&t| strings::are_characters_visually_equal(c, t))
         };
+        // extra letters weren't meant for any key
+        let meant = if submits { Some(' ') } else { word.target().get(typed_len).copied() };
+        if let Some(key) = meant {
+            let tally = self.test.key_tallies.entry(key).or_default();
+            tally.presses += 1;
+            tally.misses += !is_keystroke_correct as usize;
+        }
 
         if is_keystroke_correct {
             self.test.live_correct_keystrokes += 1;
+            self.test.pending_mistake = None;
         } else {
             self.test.live_incorrect_keystrokes += 1;
+            self.test.missed_words.insert(self.test.text.closed_words());
+            self.test.pending_mistake = Some(self.clock.now());
+            self.test.open_errors.push(self.test.errors_history.len());
         }
 
-        if !is_keystroke_correct {
-            self.test.total_errors_ever += 1;
-        }
-
-        if word_idx < self.test.word_stream.len() && c == ' ' {
-            let user_current_word = current_input_segments.last().unwrap_or(&"").to_string();
-            self.handle_space_press(word_idx, &user_current_word);
-        }
-
-        self.test.input.push(c);
-
-        if c == ' ' {
+        if submits {
+            self.test.text.type_space();
+            if self.config.kids && is_keystroke_correct {
+                self.test.cheered_at = Some(self.clock.now());
+            }
+            self.log_keystroke(Some(' '));
             self.on_word_finished();
+        } else {
+            self.test.stray_spaces += stray_space as usize;
+            self.test.text.type_char(c);
+            self.log_keystroke(Some(c));
         }
-        self.sync_display_text();
+        self.recalculate_lines();
         self.check_scroll_trigger();
         self.check_test_completion();
+
+        if self.test.state == AppState::Running && !is_keystroke_correct {
+            let rules = self.config.fail_rules;
+            if rules.sudden_death {
+                self.fail_test(FailReason::SuddenDeath);
+            } else if rules.expert && submits {
+                self.fail_test(FailReason::Expert);
+            }
+        }
     }
 
     pub fn on_backspace(&mut self) {
         if self.test.state == AppState::Finished { return; }
+        self.resume();
 
-        if self.test.input.ends_with(' ') {
-            let segments: Vec<&str> = self.test.input.split(' ').collect();
-            if segments.len() >= 2 {
-                let last_completed_idx = segments.len() - 2;
-                let typed_word = segments[last_completed_idx];
+        // a word that was typed exactly stays done
+        if let Some((' ', word)) = self.test.text.last_typed() {
+            if word.typed() == word.target() {
+                return;
+            }
+        }
 
-                if let Some(target_word) = self.test.word_stream.get(last_completed_idx) {
-                    if typed_word == target_word.text {
-                        return;
-                    }
-                }
+        if self.last_key_was_wrong() {
+            self.test.corrected_errors += 1;
+            self.credit_correction();
+        }
 
-                let current_idx = last_completed_idx + 1;
-                if current_idx < self.test.word_stream.len() {
-                    self.test.word_stream[current_idx].state = WordState::Pending;
-                }
-                if last_completed_idx < self.test.word_stream.len() {
-                    self.test.word_stream[last_completed_idx].state = WordState::Active;
+        if self.test.text.backspace().is_some() {
+            self.log_keystroke(None);
+            self.test.backspaces += 1;
+            if let (Some(at), Some(window)) = (self.test.pending_mistake.take(), self.config.forgive_window) {
+                if self.clock.now().saturating_duration_since(at) <= window {
+                    self.test.forgiven_keystrokes += 1;
                 }
             }
+            self.recalculate_lines();
         }
+    }
 
-        if let Some(popped_char) = self.test.input.pop() {
-            if popped_char == ' ' {
-                // clear missed record so the word is treated as fresh when re-typed
-                let word_idx = self.test.input.split(' ').count().saturating_sub(1);
-                self.test.missed_chars.remove(&word_idx);
-            }
-            self.sync_display_text();
-        }
+    /// with strict space, a space short of the word's end is just another wrong key
+    fn is_stray_space(&self) -> bool {
+        self.config.strict_space && self.test.text.current().is_some_and(|w| w.typed().len() < w.target().len())
     }
 
-    fn words_visually_equal(typed: &str, target: &str) -> bool {
-        let mut t = typed.chars();
-        let mut g = target.chars();
-        loop {
-            let pair = (t.next(), g.next());
-            if let (Some(a), Some(b)) = pair {
-                if !strings::are_characters_visually_equal(a, b) { return false; }
-            } else {
-                return pair == (None, None);
+    fn log_keystroke(&mut self, key: Option<char>) {
+        let at = self.elapsed().unwrap_or_default().as_secs_f64();
+        self.test.keystrokes.push((at, key));
+    }
+
+    pub fn select_results_tab(&mut self, tab: ResultsTab) {
+        if self.test.state != AppState::Finished { return; }
+        self.test.results_tab = tab;
+        self.test.results_scroll = 0;
+        self.test.replay_started = (tab == ResultsTab::Replay).then(|| self.clock.now());
+    }
+
+    pub fn step_results_tab(&mut self, step: isize) {
+        self.select_results_tab(self.test.results_tab.step(step));
+    }
+
+    pub fn scroll_results(&mut self, step: i16) {
+        if self.test.state != AppState::Finished { return; }
+        self.test.results_scroll = self.test.results_scroll.saturating_add_signed(step);
+    }
+
+    /// seconds into the test the replay has got to. it holds on the end for a moment, then
+    /// starts over. None unless the replay tab is open.
+    pub fn replay_position(&self) -> Option<f64> {
+        const HOLD_SECS: f64 = 2.0;
+        let since = self.now().saturating_duration_since(self.test.replay_started?).as_secs_f64();
+        let length = self.test.keystrokes.last().map_or(0.0, |&(at, _)| at);
+        Some((since % (length + HOLD_SECS)).min(length))
+    }
+
+    /// the test's text as it stood `secs` in, rebuilt from the keystroke log
+    pub fn replay_text(&self, secs: f64) -> TypedText {
+        let words = self.test.words();
+        let mut text = TypedText::new(words.iter().map(String::as_str));
+        for &(_, key) in self.test.keystrokes.iter().take_while(|&&(at, _)| at <= secs) {
+            let stray_space = self.config.strict_space
+                && text.current().is_some_and(|w| w.typed().len() < w.target().len());
+            match key {
+                Some(' ') if !stray_space => { text.type_space(); }
+                Some(c) => text.type_char(c),
+                None => { text.backspace(); }
             }
         }
+        text
     }
 
-    fn handle_space_press(&mut self, word_idx: usize, user_current_word: &str) {
-        let target_word = self.test.word_stream[word_idx].text.clone();
-
-        // visual equality so "-" typed against "—" is not counted as an error
-        let is_word_error = !Self::words_visually_equal(user_current_word, &target_word);
-        // char counts; byte lengths are wrong for multi-byte chars like em dash (3 bytes, 1 char)
-        let user_chars   = user_current_word.chars().count();
-        let target_chars = target_word.chars().count();
-        let extra_len_penalty = user_chars.saturating_sub(target_chars);
+    /// moves the results chart's cursor `step` seconds along. the first press lands on
+    /// whichever end it points away from.
+    pub fn move_chart_cursor(&mut self, step: isize) {
+        if self.test.state != AppState::Finished { return; }
+        let Some(last) = self.test.wpm_history.len().checked_sub(1) else { return };
+        self.test.chart_cursor = Some(match self.test.chart_cursor {
+            Some(at) => at.saturating_add_signed(step).min(last),
+            None if step < 0 => last,
+            None => 0,
+        });
+    }
 
-        if !self.test.processed_word_errors.contains(&word_idx) && (is_word_error || extra_len_penalty > 0) {
-            let word_penalty = if is_word_error { 1 } else { 0 };
-            self.test.total_errors_ever += word_penalty + extra_len_penalty;
-            self.test.processed_word_errors.insert(word_idx);
+    /// marks the error being backspaced as corrected, in the second it was made
+    fn credit_correction(&mut self) {
+        let Some(snapshot) = self.test.open_errors.pop() else { return };
+        match self.test.corrected_history.get_mut(snapshot) {
+            Some((_, corrected)) => *corrected += 1.0,
+            None => self.test.pending_corrected += 1,
         }
+    }
 
-        if user_chars < target_chars {
-            let missing_count = target_chars - user_chars;
-            self.test.missed_chars.insert(word_idx, missing_count);
+    /// whether the last char typed went in as an incorrect keystroke, judged the same
+    /// way on_key judges it: a space against its whole word, anything else against its slot
+    fn last_key_was_wrong(&self) -> bool {
+        match self.test.text.last_typed() {
+            None => false,
+            Some((' ', word)) => !word.is_correct(),
+            Some((c, word)) => word.target()
+                .get(word.typed().len() - 1)
+                .is_none_or(|&t| !strings::are_characters_visually_equal(c, t)),
         }
     }
 
     fn check_test_completion(&mut self) {
-        match self.config.mode {
-            Mode::Words(_) | Mode::Quote(_) => {
-                // subtract extras only. aligned_input includes \0 slots for missed chars
-                let effective_len = self.test.aligned_input.len()
-                    .saturating_sub(self.test.extra_char_count);
-                if effective_len < self.test.word_stream_string.chars().count() { return; }
-
-                let target_words: Vec<&str> = self.test.word_stream_string.split(' ').collect();
-                let input_words:  Vec<&str> = self.test.input.split(' ').collect();
-
-                if let Some(last_target_word) = target_words.last() {
-                    let last_word_index = target_words.len() - 1;
-                    let last_input_word = input_words.get(last_word_index).unwrap_or(&"");
-                    if last_input_word == last_target_word {
-                        self.end_test();
-                    }
-                }
-            }
-            _ => {}
+        let done = match self.config.mode {
+            Mode::Words(_) | Mode::Quote(_) => self.test.text.is_complete(),
+            // only once the clock has run out, when a word finished in grace ends it
+            Mode::Time(_) => self.time_is_up(),
+        };
+        if done {
+            self.end_test();
         }
     }
 
-    fn calculate_live_correct_chars(&self) -> usize {
-        let ends_with_space = self.test.aligned_input.last() == Some(&' ');
+    /// correct, incorrect, extra and missed chars over the whole test so far
+    pub fn resolved_char_stats(&self) -> (usize, usize, usize, usize) {
+        let CharStats { correct, incorrect, extra, missed } = self.test.text.stats();
+        (correct, incorrect, extra, missed)
+    }
 
-        let completed_len = if ends_with_space || self.test.aligned_input.is_empty() {
-            self.test.aligned_input.len()
-        } else {
-            self.test.aligned_input.iter().rposition(|&c| c == ' ')
-                .map(|p| p + 1)
-                .unwrap_or(0)
+    /// e.g. "quote short english punctuation". used by the results header and summaries.
+    pub fn test_type_label(&self) -> String {
+        use crate::models::QuoteSelector;
+        use crate::ui::utils::get_quote_length_category;
+        let mode_str = match &self.config.mode {
+            Mode::Time(t) => format!("time {}", t),
+            Mode::Words(w) => format!("word {}", w),
+            Mode::Quote(q) => match q {
+                QuoteSelector::Id(_) => format!("quote {}", get_quote_length_category(self.test.original_quote_length, &self.config.quote_thresholds)),
+                QuoteSelector::Category(len) => {
+                    let s = format!("{:?}", len).to_lowercase();
+                    format!("quote {}", if s == "all" { get_quote_length_category(self.test.original_quote_length, &self.config.quote_thresholds) } else { &s })
+                }
+            },
         };
 
-        let completed_aligned = &self.test.aligned_input[..completed_len];
-        let completed_display: String = self.test.display_string.chars().take(completed_len).collect();
-        let completed_mask: Vec<bool> = self.test.display_mask.iter().take(completed_len).copied().collect();
-
-        let (_, _, completed_correct_chars, _, _, _) =
-            self.calculate_custom_stats_for_slice(completed_aligned, &completed_display, &completed_mask);
+        let mut type_parts = vec![mode_str, self.config.word_data.name.clone()];
+        if self.config.use_punctuation { type_parts.push("punctuation".to_string()); }
+        if self.config.use_numbers     { type_parts.push("number".to_string()); }
+        if self.config.practice        { type_parts.push("practice".to_string()); }
+        if self.config.dictation.is_some() { type_parts.push("dictation".to_string()); }
+        if let Some(drill) = &self.drill { type_parts.push(format!("drill round {}", drill.round)); }
+        type_parts.join(" ")
+    }
 
-        // use self.input for the in-progress word. it has no \0 so indexing is unambiguous
-        let current_word_input = if ends_with_space || self.test.aligned_input.is_empty() {
-            ""
-        } else if let Some(last_space) = self.test.input.rfind(' ') {
-            &self.test.input[last_space + 1..]
-        } else {
-            self.test.input.as_str()
-        };
+    pub fn error_counts(&self) -> ErrorCounts {
+        let made = self.test.live_incorrect_keystrokes;
+        ErrorCounts { made, corrected: self.test.corrected_errors.min(made) }
+    }
 
-        let current_word_correct_chars = if !current_word_input.is_empty() {
-            let current_word_idx = self.test.input.split(' ').count().saturating_sub(1);
-            if let Some(word) = self.test.word_stream.get(current_word_idx) {
-                let target_word = &word.text;
-                let has_error = current_word_input.chars().enumerate().any(|(i, c)| {
-                    target_word.chars().nth(i).map_or(true, |tc| !strings::are_characters_visually_equal(c, tc))
-                });
-                if has_error { 0 } else { current_word_input.chars().count() }
-            } else {
-                0
-            }
-        } else {
-            0
-        };
+    /// the final wpm adjusted for how hard the text was
+    pub fn adjusted_wpm(&self) -> Option<f64> {
+        Some(history::stats::adjusted_wpm(self.test.final_wpm, self.test.final_difficulty?))
+    }
 
-        completed_correct_chars + current_word_correct_chars
+    /// backspaces per 100 keystrokes
+    pub fn backspace_ratio(&self) -> f64 {
+        let keystrokes = self.test.live_correct_keystrokes + self.test.live_incorrect_keystrokes;
+        if keystrokes == 0 { return 0.0; }
+        self.test.backspaces as f64 / keystrokes as f64 * 100.0
     }
 
-    pub fn resolved_char_stats(&self) -> (usize, usize, usize, usize) {
-        let (_, _, vis_cor, vis_inc, vis_ext, vis_mis) =
-            self.calculate_custom_stats_for_slice(
-                &self.test.aligned_input,
-                &self.test.display_string,
-                &self.test.display_mask,
-            );
-        (
-            self.test.st_correct   + vis_cor,
-            self.test.st_incorrect + vis_inc,
-            self.test.st_extra     + vis_ext,
-            self.test.st_missed    + vis_mis,
-        )
+    pub fn step_summary(&self) -> StepSummary {
+        StepSummary {
+            label: self.test_type_label(),
+            wpm: self.test.final_wpm,
+            raw_wpm: self.test.final_raw_wpm,
+            accuracy: self.test.final_accuracy,
+            consistency: self.test.final_consistency,
+            time: self.test.final_time,
+            failed: self.test.fail_reason.is_some(),
+        }
     }
 
     fn check_personal_best(&mut self) {
@@ -695,11 +1578,11 @@ impl App {
                 use crate::models::QuoteSelector;
                 use crate::ui::utils::get_quote_length_category;
                 let label = match q {
-                    QuoteSelector::Id(_) => get_quote_length_category(self.test.original_quote_length).to_string(),
+                    QuoteSelector::Id(_) => get_quote_length_category(self.test.original_quote_length, &self.config.quote_thresholds).to_string(),
                     QuoteSelector::Category(len) => {
                         let s = format!("{:?}", len).to_lowercase();
                         if s == "all" {
-                            get_quote_length_category(self.test.original_quote_length).to_string()
+                            get_quote_length_category(self.test.original_quote_length, &self.config.quote_thresholds).to_string()
                         } else {
                             s
                         }
@@ -710,11 +1593,31 @@ impl App {
         };
 
         if let Ok(records) = history::load_history() {
-            let prev_best = records.iter()
-                .filter(|r| r.completed && r.mode == mode_str && r.mode_value == mode_value)
+            let same_mode: Vec<_> = records.iter()
+                .filter(|r| r.completed && !r.practice && r.mode == mode_str && r.mode_value == mode_value)
+                .collect();
+            let prev_best = same_mode.iter()
                 .filter_map(|r| r.wpm)
                 .fold(0.0_f64, f64::max);
             self.test.is_new_best = self.test.final_wpm > prev_best;
+
+            let past: Vec<f64> = same_mode.iter().filter_map(|r| r.wpm).collect();
+            self.test.rank = history::stats::top_percent(&past, self.test.final_wpm)
+                .map(|top_percent| Rank { top_percent, mode: match mode_str.as_str() {
+                    "time" => format!("{}s", mode_value),
+                    "words" => format!("{} word", mode_value),
+                    _ => format!("{} quote", mode_value),
+                } });
+
+            // scores only compare against scores from the same formula
+            if let Some(score) = self.test.final_score {
+                let formula = self.config.score_formula.name();
+                let prev_best_score = same_mode.iter()
+                    .filter(|r| r.score_formula.as_deref() == Some(formula))
+                    .filter_map(|r| r.score)
+                    .fold(0.0_f64, f64::max);
+                self.test.is_new_best_score = score > prev_best_score;
+            }
         }
     }
 
@@ -734,371 +1637,101 @@ impl App {
     }
 
     fn on_word_finished(&mut self) {
-        let segments: Vec<&str> = self.test.input.split(' ').collect();
-        let finished_idx = segments.len().saturating_sub(2);
-        if finished_idx < self.test.word_stream.len() {
-            self.test.word_stream[finished_idx].state = WordState::Typed;
-        }
-        let next_idx = finished_idx + 1;
-        if next_idx < self.test.word_stream.len() {
-            self.test.word_stream[next_idx].state = WordState::Active;
-        }
-        if finished_idx >= self.test.furthest_word_idx {
-            self.test.furthest_word_idx = finished_idx + 1;
-            let pending_count = self.test.word_stream.iter()
-                .skip(next_idx)
-                .filter(|w| w.state == WordState::Pending)
-                .count();
-            if pending_count < 100 {
-                self.add_one_word();
-            }
+        if self.test.text.pending() < 100 {
+            self.add_one_word();
         }
     }
 
     fn generate_initial_words(&mut self) {
-        let result = self.config.word_generator.generate_initial_words(
-            &self.config.mode,
-            &self.config.quote_data,
-        );
-        self.test.word_stream          = result.word_stream;
+        let result = match self.take_prewarmed() {
+            Some(result) => result,
+            None => self.config.word_generator.generate_initial_words(
+                &self.config.mode,
+                self.config.quote_count,
+                &self.config.quote_data,
+                self.config.recent_quotes.as_mut(),
+            ),
+        };
+        self.test.text                 = TypedText::new(result.word_stream.iter().map(String::as_str));
         self.test.quote_pool           = result.quote_pool;
         self.test.total_quote_words    = result.total_quote_words;
-        self.test.current_quote_source = result.current_quote_source;
+        self.test.quote_sources        = result.quote_sources;
         self.test.generated_count      = result.generated_count;
-        self.test.next_word_index      = result.next_index;
-        self.test.cumulative_words     = self.test.word_stream.iter().map(|w| w.text.clone()).collect();
-        self.update_stream_string();
-        self.sync_display_text();
+        self.test.tail                 = self.config.word_generator.tail(&result.word_stream);
 
         if matches!(self.config.mode, Mode::Quote(_)) {
-            self.test.original_quote_length = self.test.word_stream_string.chars().count();
+            self.test.original_quote_length = self.stream_char_count();
         }
+        self.recalculate_lines();
     }
 
     fn add_one_word(&mut self) {
-        if let Some((new_words, new_next_index)) = self.config.word_generator.add_one_word(
+        if let Some(new_words) = self.config.word_generator.add_one_word(
             &self.config.mode,
-            &self.test.word_stream,
+            &mut self.test.tail,
             &mut self.test.quote_pool,
             self.test.generated_count,
-            self.test.next_word_index,
         ) {
-            self.test.word_stream.extend(new_words.iter().cloned());
-            self.test.cumulative_words.extend(new_words.iter().map(|w| w.text.clone()));
-            self.test.next_word_index = new_next_index;
+            for word in &new_words {
+                self.test.text.push_word(word);
+            }
             if matches!(self.config.mode, Mode::Words(_)) {
                 self.test.generated_count += new_words.len();
             }
-            self.update_stream_string();
         }
     }
 
-    fn update_stream_string(&mut self) {
-        self.test.word_stream_string = self.test.word_stream
-            .iter()
-            .map(|w| w.text.as_str())
-            .collect::<Vec<&str>>()
-            .join(" ");
-    }
-
-    fn sync_display_text(&mut self) {
-        let clean_chars: Vec<char> = self.test.word_stream_string.chars().collect();
-        let input_chars: Vec<char> = self.test.input.chars().collect();
-
-        let mut new_display = String::with_capacity(self.test.word_stream_string.len() + 20);
-        let mut new_mask: Vec<bool>   = Vec::with_capacity(self.test.word_stream_string.len() + 20);
-        let mut new_aligned: Vec<char> = Vec::with_capacity(self.test.word_stream_string.len() + 20);
-
-        let mut clean_idx = 0;
-        let mut input_idx = 0;
-        let mut word_idx  = 0usize;
-
-        while clean_idx < clean_chars.len() {
-            let clean_char = clean_chars[clean_idx];
-            if clean_char == ' ' {
-                while input_idx < input_chars.len() && input_chars[input_idx] != ' ' {
-                    new_display.push(input_chars[input_idx]);
-                    new_mask.push(true);
-                    new_aligned.push(input_chars[input_idx]);
-                    input_idx += 1;
-                }
-                if input_idx < input_chars.len() && input_chars[input_idx] == ' ' {
-                    // inject \0 slots so aligned_input has the right length for missed positions
-                    if let Some(&missed) = self.test.missed_chars.get(&word_idx) {
-                        for _ in 0..missed {
-                            new_aligned.push('\0');
-                        }
-                    }
-                    new_display.push(' ');
-                    new_mask.push(false);
-                    new_aligned.push(' ');
-                    input_idx += 1;
-                    word_idx  += 1;
-                } else {
-                    new_display.push(' ');
-                    new_mask.push(false);
-                }
-                clean_idx += 1;
-            } else {
-                new_display.push(clean_char);
-                new_mask.push(false);
-                clean_idx += 1;
-                if input_idx < input_chars.len() && input_chars[input_idx] != ' ' {
-                    new_aligned.push(input_chars[input_idx]);
-                    input_idx += 1;
-                }
-            }
-        }
-
-        self.test.display_string  = new_display;
-        self.test.display_mask    = new_mask;
-        self.test.aligned_input   = new_aligned;
-        self.test.extra_char_count = self.test.display_mask.iter().filter(|&&x| x).count();
-        self.test.cursor_idx = self.test.aligned_input.len();
-        self.recalculate_lines();
+    /// chars in the stream as it stands, spaces included
+    fn stream_char_count(&self) -> usize {
+        let words = self.test.text.words();
+        words.iter().map(|w| w.target().len()).sum::<usize>() + words.len().saturating_sub(1)
     }
 
-    fn will_cause_visual_wrap(&self, extra_char: char, is_extra: bool) -> bool {
-        let layout_width = (self.terminal_width as usize * 80) / 100;
-        let candidate_width = if is_extra { layout_width } else { layout_width.saturating_sub(2) };
-
-        let current_line_idx = Self::line_idx_for_cursor(
-            &self.test.visual_lines,
-            self.test.aligned_input.len(),
-        );
-
-        let mut candidate_display = self.test.display_string.clone();
-        candidate_display.push(extra_char);
-        let candidate_lines = Self::wrap_into_lines(&candidate_display, candidate_width);
-
-        let candidate_line_idx = Self::line_idx_for_cursor(
-            &candidate_lines,
-            self.test.aligned_input.len() + 1,
-        );
-
-        if is_extra {
-            candidate_line_idx > current_line_idx
-        } else {
-            candidate_line_idx >= 3
-        }
+    fn wrap_width(&self) -> usize {
+        let width = match self.text_width {
+            // a cell for the caret past the end of a line
+            Some(width) => (width as usize).saturating_sub(1),
+            None => ((self.terminal_width as usize * 80) / 100).saturating_sub(2),
+        };
+        // large text takes two cells a letter
+        if self.config.accessibility.large_text { width / 2 } else { width }
     }
 
-    // used by both recalculate_lines and will_cause_visual_wrap so they always agree on boundaries
-    fn wrap_into_lines(text: &str, width: usize) -> Vec<String> {
-        let mut lines: Vec<String> = Vec::new();
-        let mut current_line = String::new();
-        let mut current_width = 0usize;
-
-        for word in text.split(' ') {
-            let word_len    = word.chars().count();
-            let space_before = if current_width == 0 { 0 } else { 1 };
-
-            if current_width + space_before + word_len <= width {
-                if current_width > 0 {
-                    current_line.push(' ');
-                    current_width += 1;
-                }
-                current_line.push_str(word);
-                current_width += word_len;
-            } else {
-                if !current_line.is_empty() {
-                    lines.push(current_line.clone());
-                }
-                current_line.clear();
-                current_line.push_str(word);
-                current_width = word_len;
-            }
-        }
-        if !current_line.is_empty() {
-            lines.push(current_line);
-        }
-        lines
+    fn caret_line(&self) -> usize {
+        let current = self.test.text.current_index();
+        self.test.visual_lines.iter().position(|l| l.contains(&current)).unwrap_or(0)
     }
 
-    fn line_idx_for_cursor(lines: &[String], cursor_pos: usize) -> usize {
-        let mut running = 0usize;
-        for (i, line) in lines.iter().enumerate() {
-            let line_len = line.chars().count() + 1;
-            if cursor_pos < running + line_len { return i; }
-            running += line_len;
+    /// keeps the caret inside the three visible lines, and extra letters from pushing
+    /// their word onto the next line
+    fn will_cause_visual_wrap(&self, is_extra: bool) -> bool {
+        let line_idx = self.caret_line();
+        if !is_extra {
+            return line_idx >= VISIBLE_LINES;
         }
-        if lines.is_empty() { 0 } else { lines.len() - 1 }
+        let Some(line) = self.test.visual_lines.get(line_idx) else { return false };
+        let current = self.test.text.current_index();
+        self.test.text.line_width(&(line.start..current + 1)) + 1 > self.wrap_width()
     }
 
     fn recalculate_lines(&mut self) {
-        let layout_width = (self.terminal_width as usize * 80) / 100;
-        let safe_width   = layout_width.saturating_sub(2);
-        self.test.visual_lines = Self::wrap_into_lines(&self.test.display_string, safe_width);
+        self.test.visual_lines = self.test.text.wrap(self.wrap_width(), VISIBLE_LINES);
+        let current = self.test.text.closed_words();
+        if self.test.word_reached.0 != current {
+            self.test.word_reached = (current, self.clock.now());
+        }
     }
 
     fn check_scroll_trigger(&mut self) {
-        let mut running_char_count = 0;
-        let mut current_line_index = 0;
-        for (i, line) in self.test.visual_lines.iter().enumerate() {
-            let line_len = line.chars().count() + 1;
-            if self.test.aligned_input.len() < running_char_count + line_len {
-                current_line_index = i;
-                break;
-            }
-            running_char_count += line_len;
-        }
-        if current_line_index >= 2 {
+        if self.caret_line() >= 2 {
             self.delete_first_visual_line();
         }
     }
 
     fn delete_first_visual_line(&mut self) {
-        if self.test.visual_lines.is_empty() { return; }
-        let first_line = &self.test.visual_lines[0];
-        let visual_char_count = first_line.chars().count();
-        let mut chars_to_remove_visual = visual_char_count;
-
-        if self.test.display_string.chars().count() > visual_char_count {
-            if let Some(c) = self.test.display_string.chars().nth(visual_char_count) {
-                if c == ' ' { chars_to_remove_visual += 1; }
-            }
-        }
-
-        // aligned_input has \0 for missed positions, so stats are accurate even for short words
-        let capped = chars_to_remove_visual.min(self.test.aligned_input.len());
-        let aligned_chunk  = &self.test.aligned_input[..capped];
-        let display_chunk: String    = self.test.display_string.chars().take(chars_to_remove_visual).collect();
-        let mask_chunk: Vec<bool>    = self.test.display_mask.iter().take(chars_to_remove_visual).cloned().collect();
-
-        let (acc_cor, acc_inc, raw_cor, raw_inc, raw_ext, raw_mis) =
-            self.calculate_custom_stats_for_slice(aligned_chunk, &display_chunk, &mask_chunk);
-
-        self.test.st_correct   += raw_cor;
-        self.test.st_incorrect += raw_inc;
-        self.test.st_extra     += raw_ext;
-        self.test.st_missed    += raw_mis;
-
-        self.test.acc_score_correct   = (self.test.acc_score_correct   + acc_cor).max(0);
-        self.test.acc_score_incorrect = (self.test.acc_score_incorrect + acc_inc).max(0);
-
-        self.test.uncorrected_errors_scrolled += raw_inc + raw_mis + raw_ext;
-
-        let tokens_scrolled = aligned_chunk.iter().filter(|&&c| c == ' ').count();
-        if tokens_scrolled > 0 {
-            self.test.scrolled_word_count += tokens_scrolled;
-            let drain_amount = tokens_scrolled.min(self.test.word_stream.len());
-            self.test.word_stream.drain(0..drain_amount);
-            self.test.furthest_word_idx = self.test.furthest_word_idx.saturating_sub(tokens_scrolled);
-
-            // word indices shift down after scrolling, so remap both maps to stay in sync
-            self.test.missed_chars = self.test.missed_chars
-                .iter()
-                .filter(|(&k, _)| k >= tokens_scrolled)
-                .map(|(&k, &v)| (k - tokens_scrolled, v))
-                .collect();
-
-            self.test.processed_word_errors = self.test.processed_word_errors
-                .iter()
-                .filter(|&&k| k >= tokens_scrolled)
-                .map(|&k| k - tokens_scrolled)
-                .collect();
-        }
-
-        let mut real_chars_removed = 0;
-        for i in 0..chars_to_remove_visual {
-            if i < self.test.display_mask.len() {
-                if !self.test.display_mask[i] { real_chars_removed += 1; }
-            }
-        }
-        if real_chars_removed > 0 {
-            // real_chars_removed is a char count. must convert to byte offset before slicing
-            let ws_byte_len: usize = self.test.word_stream_string.chars()
-                .take(real_chars_removed)
-                .map(|c| c.len_utf8())
-                .sum();
-            if self.test.word_stream_string.len() >= ws_byte_len {
-                self.test.word_stream_string = self.test.word_stream_string[ws_byte_len..].to_string();
-            }
-        }
-
-        // self.input has no \0 so we count real chars from aligned_chunk to know how many to drain
-        let clean_chars_to_remove = aligned_chunk.iter().filter(|&&c| c != '\0').count();
-        let byte_len: usize = self.test.input.chars()
-            .take(clean_chars_to_remove)
-            .map(|c| c.len_utf8())
-            .sum();
-        self.test.input.drain(..byte_len);
-
-        self.sync_display_text();
-    }
-
-    pub fn calculate_custom_stats_for_slice(&self, input_chars: &[char], display_str: &str, mask: &[bool])
-        -> (isize, isize, usize, usize, usize, usize)
-    {
-        let mut acc_correct_score: isize = 0;
-        for &m in mask { if !m { acc_correct_score += 1; } }
-        let mut acc_incorrect_score: isize = 0;
-
-        let mut raw_cor = 0;
-        let mut raw_inc = 0;
-        let mut raw_ext = 0;
-        let mut raw_mis = 0;
-
-        let display_chars: Vec<char> = display_str.chars().collect();
-
-        let mut i = 0;
-        while i < display_chars.len() {
-            let mut word_end = i;
-            while word_end < display_chars.len() {
-                let is_extra = if word_end < mask.len() { mask[word_end] } else { false };
-                if !is_extra && display_chars[word_end] == ' ' { break; }
-                word_end += 1;
-            }
-
-            let mut word_has_error = false;
-
-            for k in i..word_end {
-                let is_extra   = if k < mask.len() { mask[k] } else { false };
-                let target_char = display_chars[k];
-                let input_char  = input_chars.get(k).copied().unwrap_or('\0');
-
-                if is_extra {
-                    word_has_error = true;
-                } else if input_char == '\0' {
-                    word_has_error = true;
-                } else if !strings::are_characters_visually_equal(input_char, target_char) {
-                    word_has_error = true;
-                }
-            }
-
-            for k in i..word_end {
-                let is_extra   = if k < mask.len() { mask[k] } else { false };
-                let target_char = display_chars[k];
-                let input_char  = input_chars.get(k).copied().unwrap_or('\0');
-
-                if is_extra {
-                    acc_incorrect_score += 1;
-                    raw_ext += 1;
-                } else if input_char == '\0' {
-                    acc_correct_score -= 1;
-                    raw_mis += 1;
-                } else if !strings::are_characters_visually_equal(input_char, target_char) {
-                    acc_correct_score -= 1;
-                    acc_incorrect_score += 1;
-                    raw_inc += 1;
-                } else if !word_has_error {
-                    raw_cor += 1;
-                }
-            }
-
-            if word_end < display_chars.len() {
-                if word_has_error {
-                    acc_correct_score -= 1;
-                    acc_incorrect_score += 1;
-                } else {
-                    raw_cor += 1;
-                }
-                i = word_end + 1;
-            } else {
-                i = word_end;
-            }
-        }
-
-        (acc_correct_score, acc_incorrect_score, raw_cor, raw_inc, raw_ext, raw_mis)
+        let Some(first) = self.test.visual_lines.first() else { return };
+        // every word above the caret's line has been closed with a space
+        self.test.text.scroll(first.len());
+        self.recalculate_lines();
     }
 }
@@ -0,0 +1,185 @@
+//! `typa backup` and `typa restore`: the config file and everything in the data directory
+//! (history, recent quotes, saved cards) in one tar file, for moving to another machine.
+//! the tar handling is the small ustar subset this needs: plain files, nothing else.
+
+use crate::utils::paths;
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+const BLOCK: usize = 512;
+const CONFIG_FILE: &str = "config.toml";
+
+/// a file in the archive: where it goes, relative to the archive root, and what's in it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+/// writes every file there is to back up to `dest` and says how many went in
+pub fn backup(dest: &Path) -> Result<usize> {
+    let mut entries = Vec::new();
+    let config = paths::config_file(CONFIG_FILE);
+    if let Some(config) = config.as_ref().filter(|p| p.is_file()) {
+        entries.push(Entry { name: format!("config/{}", CONFIG_FILE), data: fs::read(config)? });
+    }
+    if let Some(dir) = paths::data_dir().filter(|d| d.is_dir()) {
+        let skip: Vec<PathBuf> = [Some(dest.to_path_buf()), config].into_iter().flatten()
+            .filter_map(|p| p.canonicalize().ok())
+            .collect();
+        collect(&dir, &dir, &skip, &mut entries)?;
+    }
+    if entries.is_empty() {
+        bail!("nothing to back up yet: no config file and no history");
+    }
+
+    let tar = write_tar(&entries)?;
+    fs::write(dest, tar).with_context(|| format!("couldn't write {}", dest.display()))?;
+    Ok(entries.len())
+}
+
+/// puts the files from a backup back where they go. refuses to replace any that are already
+/// there unless `force` is set, so a restore can't quietly wipe out a history.
+pub fn restore(src: &Path, force: bool) -> Result<usize> {
+    let bytes = fs::read(src).with_context(|| format!("couldn't read {}", src.display()))?;
+    let entries = read_tar(&bytes)?;
+
+    let mut targets = Vec::with_capacity(entries.len());
+    for entry in &entries {
+        targets.push(target(&entry.name)?);
+    }
+    let existing: Vec<&PathBuf> = targets.iter().filter(|t| t.exists()).collect();
+    if !force {
+        if let Some(first) = existing.first() {
+            bail!("restoring would replace {} existing file(s), starting with {}. pass --force to replace them", existing.len(), first.display());
+        }
+    }
+
+    for (entry, target) in entries.iter().zip(&targets) {
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(target, &entry.data).with_context(|| format!("couldn't write {}", target.display()))?;
+    }
+    Ok(entries.len())
+}
+
+/// where an archive entry belongs on this machine. anything outside `config/` and `data/`,
+/// or trying to climb out of them, is rejected.
+fn target(name: &str) -> Result<PathBuf> {
+    let (root, rest) = name.split_once('/').context("a file at the top of the backup")?;
+    let rest = Path::new(rest);
+    if rest.as_os_str().is_empty() || !rest.components().all(|c| matches!(c, Component::Normal(_))) {
+        bail!("the backup has a file with an unsafe path: {}", name);
+    }
+    let base = match root {
+        "config" => paths::config_file(""),
+        "data" => paths::data_dir(),
+        _ => bail!("the backup has a file typa doesn't know where to put: {}", name),
+    };
+    Ok(base.context("no config or data directory to restore into")?.join(rest))
+}
+
+fn collect(root: &Path, dir: &Path, skip: &[PathBuf], entries: &mut Vec<Entry>) -> Result<()> {
+    let mut children: Vec<PathBuf> = fs::read_dir(dir)?.filter_map(|e| e.ok().map(|e| e.path())).collect();
+    children.sort();
+    for path in children {
+        if path.canonicalize().is_ok_and(|p| skip.contains(&p)) { continue; }
+        if path.is_dir() {
+            collect(root, &path, skip, entries)?;
+        } else if path.is_file() && path.extension().is_none_or(|e| e != "tmp") {
+            let relative = path.strip_prefix(root)?.to_string_lossy().replace('\\', "/");
+            entries.push(Entry { name: format!("data/{}", relative), data: fs::read(&path)? });
+        }
+    }
+    Ok(())
+}
+
+/// a ustar archive of `entries`, ending in the two empty blocks tar expects
+pub fn write_tar(entries: &[Entry]) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    let mtime = chrono::Utc::now().timestamp().max(0) as u64;
+    for entry in entries {
+        let mut header = [0u8; BLOCK];
+        let (prefix, name) = split_name(&entry.name)?;
+        header[..name.len()].copy_from_slice(name.as_bytes());
+        octal(&mut header[100..108], 0o644);
+        octal(&mut header[108..116], 0);
+        octal(&mut header[116..124], 0);
+        octal(&mut header[124..136], entry.data.len() as u64);
+        octal(&mut header[136..148], mtime);
+        header[156] = b'0';
+        header[257..263].copy_from_slice(b"ustar\0");
+        header[263..265].copy_from_slice(b"00");
+        header[345..345 + prefix.len()].copy_from_slice(prefix.as_bytes());
+
+        header[148..156].fill(b' ');
+        let sum: u32 = header.iter().map(|&b| b as u32).sum();
+        header[148..156].copy_from_slice(format!("{:06o}\0 ", sum).as_bytes());
+
+        out.extend_from_slice(&header);
+        out.extend_from_slice(&entry.data);
+        out.resize(out.len().next_multiple_of(BLOCK), 0);
+    }
+    out.resize(out.len() + 2 * BLOCK, 0);
+    Ok(out)
+}
+
+/// the plain files in a tar archive. directories and links are passed over.
+pub fn read_tar(bytes: &[u8]) -> Result<Vec<Entry>> {
+    let mut entries = Vec::new();
+    let mut at = 0;
+    while at + BLOCK <= bytes.len() {
+        let header = &bytes[at..at + BLOCK];
+        if header.iter().all(|&b| b == 0) { break; }
+
+        let stored: u32 = parse_octal(&header[148..156]).context("not a tar file")? as u32;
+        let sum: u32 = header.iter().enumerate().map(|(i, &b)| if (148..156).contains(&i) { b' ' as u32 } else { b as u32 }).sum();
+        if stored != sum {
+            bail!("not a tar file, or a damaged one");
+        }
+
+        let size = parse_octal(&header[124..136]).context("a damaged tar header")? as usize;
+        let data_start = at + BLOCK;
+        let data_end = data_start.checked_add(size).filter(|&end| end <= bytes.len()).context("the backup is cut short")?;
+        if matches!(header[156], b'0' | 0) {
+            let name = text(&header[..100]);
+            let prefix = text(&header[345..500]);
+            let name = if prefix.is_empty() { name } else { format!("{}/{}", prefix, name) };
+            entries.push(Entry { name, data: bytes[data_start..data_end].to_vec() });
+        }
+        at = data_start + size.next_multiple_of(BLOCK);
+    }
+    Ok(entries)
+}
+
+/// ustar names over 100 bytes go in two parts, split at a slash
+fn split_name(name: &str) -> Result<(&str, &str)> {
+    if name.len() <= 100 {
+        return Ok(("", name));
+    }
+    name.char_indices()
+        .filter(|&(i, c)| c == '/' && i <= 155 && name.len() - i - 1 <= 100)
+        .map(|(i, _)| (&name[..i], &name[i + 1..]))
+        .next()
+        .with_context(|| format!("{} is too long a path to back up", name))
+}
+
+fn octal(field: &mut [u8], value: u64) {
+    let digits = format!("{:0w$o}", value, w = field.len() - 1);
+    field[..digits.len()].copy_from_slice(digits.as_bytes());
+    field[digits.len()] = 0;
+}
+
+fn parse_octal(field: &[u8]) -> Option<u64> {
+    let digits = text(field);
+    let digits = digits.trim();
+    if digits.is_empty() { return Some(0); }
+    u64::from_str_radix(digits, 8).ok()
+}
+
+fn text(field: &[u8]) -> String {
+    let end = field.iter().position(|&b| b == 0).unwrap_or(field.len());
+    String::from_utf8_lossy(&field[..end]).into_owned()
+}
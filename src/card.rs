@@ -0,0 +1,140 @@
+//! a results card to share, drawn straight to svg in the theme's colors: the headline
+//! numbers, the test, and the wpm chart. nothing here depends on the terminal ui.
+
+use crate::config::Theme;
+use crate::history::TestRecord;
+use crate::utils::paths;
+use anyhow::{Context, Result};
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+
+const WIDTH: f64 = 800.0;
+const HEIGHT: f64 = 400.0;
+
+// where the chart goes on the card
+const CHART_LEFT: f64 = 300.0;
+const CHART_RIGHT: f64 = 770.0;
+const CHART_TOP: f64 = 70.0;
+const CHART_BOTTOM: f64 = 300.0;
+
+/// the card for `record` as an svg document
+pub fn render_svg(record: &TestRecord, theme: &Theme) -> String {
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}" viewBox="0 0 {WIDTH} {HEIGHT}" font-family="'JetBrains Mono', 'Fira Code', Menlo, Consolas, monospace">"#
+    );
+    let _ = writeln!(svg, r#"<rect width="100%" height="100%" rx="16" fill="{}"/>"#, theme.bg);
+    let _ = writeln!(svg, r#"<text x="30" y="45" font-size="22" fill="{}">typa</text>"#, theme.main);
+    let _ = writeln!(svg, r#"<text x="{}" y="45" font-size="14" text-anchor="end" fill="{}">{}</text>"#, CHART_RIGHT, theme.sub, escape(&date(record)));
+
+    big_number(&mut svg, theme, 110.0, "wpm", record.wpm);
+    big_number(&mut svg, theme, 215.0, "acc", record.accuracy.map(|a| a.round()));
+    if record.accuracy.is_some() {
+        let _ = writeln!(svg, r#"<text x="{}" y="215" font-size="28" fill="{}">%</text>"#, 30.0 + digits(record.accuracy) * 38.0, theme.main);
+    }
+
+    chart(&mut svg, record, theme);
+
+    // the details along the bottom
+    let mut details = vec![
+        ("test", test_label(record)),
+        ("raw", record.raw_wpm.map_or("-".to_string(), |w| format!("{:.0}", w))),
+        ("consistency", record.consistency.map_or("-".to_string(), |c| format!("{:.0}%", c))),
+        ("time", format!("{:.0}s", record.duration_secs)),
+    ];
+    if let Some(token) = &record.token {
+        details.push(("verify", token.clone()));
+    }
+    let mut x = 30.0;
+    for (label, value) in details {
+        let _ = writeln!(svg, r#"<text x="{x}" y="345" font-size="13" fill="{}">{label}</text>"#, theme.sub);
+        let _ = writeln!(svg, r#"<text x="{x}" y="370" font-size="18" fill="{}">{}</text>"#, theme.text, escape(&value));
+        x += 30.0 + (value.chars().count().max(label.len()) as f64) * 11.0;
+    }
+    if let Some(source) = &record.quote_source {
+        let credit = match &record.quote_license {
+            Some(license) => format!("{} · {}", source, license),
+            None => source.clone(),
+        };
+        let _ = writeln!(svg, r#"<text x="{}" y="390" font-size="11" text-anchor="end" fill="{}">{}</text>"#, CHART_RIGHT, theme.sub, escape(&credit));
+    }
+
+    // what the token was worked out from, for checking a shared card without its history
+    if let (Some(token), Some(text), Some(keys)) = (&record.token, &record.text_digest, &record.keys_digest) {
+        let _ = writeln!(
+            svg,
+            r#"<metadata id="typa-verify" data-token="{}" data-text="{}" data-keys="{}" data-wpm="{:.2}" data-accuracy="{:.2}" data-secs="{:.2}"/>"#,
+            escape(token), escape(text), escape(keys), record.wpm.unwrap_or(0.0), record.accuracy.unwrap_or(0.0), record.duration_secs,
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// writes the card next to the history file and says where it went
+pub fn save(record: &TestRecord, theme: &Theme) -> Result<PathBuf> {
+    let dir = paths::data_file("cards").context("no data directory to save cards in")?;
+    fs::create_dir_all(&dir)?;
+    let stamp: String = record.timestamp.chars().take(19).filter(|c| c.is_ascii_digit()).collect();
+    let path = dir.join(format!("typa-{}.svg", stamp));
+    fs::write(&path, render_svg(record, theme))?;
+    Ok(path)
+}
+
+fn big_number(svg: &mut String, theme: &Theme, y: f64, label: &str, value: Option<f64>) {
+    let _ = writeln!(svg, r#"<text x="30" y="{}" font-size="16" fill="{}">{label}</text>"#, y - 50.0, theme.sub);
+    let shown = value.map_or("-".to_string(), |v| format!("{:.0}", v));
+    let _ = writeln!(svg, r#"<text x="30" y="{y}" font-size="64" fill="{}">{shown}</text>"#, theme.main);
+}
+
+fn digits(value: Option<f64>) -> f64 {
+    value.map_or(1, |v| format!("{:.0}", v).len()) as f64
+}
+
+/// wpm and raw wpm as lines, errors as dots on their own scale, like the results screen
+fn chart(svg: &mut String, record: &TestRecord, theme: &Theme) {
+    let wpm = record.wpm_history.as_deref().unwrap_or_default();
+    let raw = record.raw_wpm_history.as_deref().unwrap_or_default();
+    let errors = record.errors_history.as_deref().unwrap_or_default();
+    if wpm.is_empty() { return; }
+
+    let x_max = wpm.iter().chain(raw).map(|&(t, _)| t).fold(1.0, f64::max);
+    let y_max = wpm.iter().chain(raw).map(|&(_, w)| w).fold(10.0, f64::max) * 1.1;
+    let e_max = errors.iter().map(|&(_, e)| e).fold(1.0, f64::max);
+    let px = |t: f64| CHART_LEFT + t / x_max * (CHART_RIGHT - CHART_LEFT);
+    let py = |v: f64, max: f64| CHART_BOTTOM - v / max * (CHART_BOTTOM - CHART_TOP);
+
+    let _ = writeln!(svg, r#"<line x1="{CHART_LEFT}" y1="{CHART_BOTTOM}" x2="{CHART_RIGHT}" y2="{CHART_BOTTOM}" stroke="{}" stroke-width="1"/>"#, theme.sub_alt);
+    let _ = writeln!(svg, r#"<text x="{}" y="{}" font-size="11" text-anchor="end" fill="{}">{:.0}</text>"#, CHART_LEFT - 8.0, CHART_TOP + 4.0, theme.sub, y_max);
+    let _ = writeln!(svg, r#"<text x="{}" y="{}" font-size="11" text-anchor="end" fill="{}">0</text>"#, CHART_LEFT - 8.0, CHART_BOTTOM, theme.sub);
+    let _ = writeln!(svg, r#"<text x="{CHART_RIGHT}" y="{}" font-size="11" text-anchor="end" fill="{}">{:.0}s</text>"#, CHART_BOTTOM + 16.0, theme.sub, x_max);
+
+    for (points, color, width) in [(raw, &theme.sub, 1.5), (wpm, &theme.main, 2.5)] {
+        let path: Vec<String> = points.iter().map(|&(t, w)| format!("{:.1},{:.1}", px(t), py(w, y_max))).collect();
+        let _ = writeln!(svg, r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="{width}" stroke-linejoin="round"/>"#, path.join(" "), color);
+    }
+    for &(t, e) in errors.iter().filter(|&&(_, e)| e > 0.0) {
+        let _ = writeln!(svg, r#"<circle cx="{:.1}" cy="{:.1}" r="3.5" fill="{}"/>"#, px(t), py(e, e_max), theme.error);
+    }
+}
+
+/// "time 30 english punctuation", the way the results screen puts it
+fn test_label(record: &TestRecord) -> String {
+    let mut parts = vec![format!("{} {}", record.mode, record.mode_value), record.language.clone()];
+    if record.use_punctuation { parts.push("punctuation".to_string()); }
+    if record.use_numbers { parts.push("numbers".to_string()); }
+    parts.join(" ")
+}
+
+fn date(record: &TestRecord) -> String {
+    chrono::DateTime::parse_from_rfc3339(&record.timestamp)
+        .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
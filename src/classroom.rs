@@ -0,0 +1,310 @@
+//! classroom mode: the same fixed test for everyone, judged on accuracy first, with each
+//! result added as a row to a csv file that opens in any spreadsheet. a teacher can also
+//! run `typa classroom serve` to collect results from the whole room as they finish: each
+//! student's typa sends one line of json per result over the local network.
+
+use crate::app::App;
+use crate::utils::paths;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{IpAddr, SocketAddr, TcpListener, TcpStream, ToSocketAddrs, UdpSocket};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+pub const HEADER: &str = "student,date,time,test,wpm,accuracy,passed,note";
+pub const DEFAULT_PORT: u16 = 7878;
+
+/// more than any real submission, so a stray connection can't send forever
+const MAX_SUBMISSION: u64 = 4096;
+
+/// more than a classroom sends at once, so a flood of connections can't pile up threads
+const MAX_HANDLERS: usize = 64;
+
+/// whose results these are, and where they go
+#[derive(Debug, Clone)]
+pub struct Export {
+    pub student: String,
+    pub path: PathBuf,
+    /// the teacher's `typa classroom serve`, if there is one
+    pub server: Option<String>,
+}
+
+/// `csv` from config, or classroom.csv in the data directory
+pub fn results_path(csv: Option<&str>) -> Option<PathBuf> {
+    csv.map(PathBuf::from).or_else(|| paths::data_file("classroom.csv"))
+}
+
+/// one finished test, as it goes in the csv and over the network
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Submission {
+    pub student: String,
+    pub date: String,
+    pub time: String,
+    pub test: String,
+    pub wpm: f64,
+    pub accuracy: f64,
+    pub passed: bool,
+    /// why it failed, if it did
+    #[serde(default)]
+    pub note: String,
+}
+
+impl Submission {
+    pub fn from_app(student: &str, app: &App, at: chrono::DateTime<chrono::Local>) -> Self {
+        let t = &app.test;
+        Self {
+            student: student.to_string(),
+            date: at.format("%Y-%m-%d").to_string(),
+            time: at.format("%H:%M").to_string(),
+            test: app.test_type_label(),
+            wpm: t.final_wpm,
+            accuracy: t.final_accuracy,
+            passed: t.fail_reason.is_none(),
+            note: t.fail_reason.as_ref().map(|r| r.describe()).unwrap_or_default(),
+        }
+    }
+
+    /// a csv row, in `HEADER`'s order
+    pub fn row(&self) -> String {
+        [
+            field(&self.student),
+            field(&self.date),
+            field(&self.time),
+            field(&self.test),
+            format!("{:.0}", self.wpm),
+            format!("{:.1}", self.accuracy),
+            if self.passed { "yes" } else { "no" }.to_string(),
+            field(&self.note),
+        ].join(",")
+    }
+}
+
+/// adds `row` to the end of the file at `path`, with the header first if it's new
+pub fn append(path: &Path, row: &str) -> Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    let is_new = fs::metadata(path).map(|m| m.len() == 0).unwrap_or(true);
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    if is_new {
+        writeln!(file, "{}", HEADER)?;
+    }
+    writeln!(file, "{}", row)?;
+    Ok(())
+}
+
+/// quoted when it has to be. a leading = + - or @ is quoted away too, so a name can't turn
+/// into a formula when the file is opened in a spreadsheet.
+pub fn field(value: &str) -> String {
+    let formula = value.starts_with(['=', '+', '-', '@']);
+    if !formula && !value.contains([',', '"', '\n', '\r']) {
+        return value.to_string();
+    }
+    let value = if formula { format!("'{}", value) } else { value.to_string() };
+    format!("\"{}\"", value.replace('"', "\"\""))
+}
+
+/// `server` from config, with the default port if it doesn't name one. a bare ipv6 address
+/// is full of colons, so it's bracketed before the port goes on.
+pub fn server_address(server: &str) -> String {
+    let bare = server.strip_prefix('[').and_then(|s| s.strip_suffix(']')).unwrap_or(server);
+    if let Ok(ip) = bare.parse::<IpAddr>() {
+        return SocketAddr::new(ip, DEFAULT_PORT).to_string();
+    }
+    if server.contains(':') { server.to_string() } else { format!("{}:{}", server, DEFAULT_PORT) }
+}
+
+/// sends a result to the teacher's server, giving up after a few seconds
+pub fn submit(server: &str, submission: &Submission) -> Result<()> {
+    const TIMEOUT: Duration = Duration::from_secs(3);
+    let addr = server_address(server);
+    let socket = addr.to_socket_addrs()?.next().with_context(|| format!("couldn't find {}", addr))?;
+    let mut stream = TcpStream::connect_timeout(&socket, TIMEOUT)?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+    writeln!(stream, "{}", serde_json::to_string(submission)?)?;
+
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply)?;
+    match reply.trim() {
+        "ok" => {}
+        "busy" => bail!("{} is busy with other results, try again in a moment", addr),
+        _ => bail!("{} didn't take the result", addr),
+    }
+    Ok(())
+}
+
+/// results on their way to the teacher's server, each sent on a thread of its own so a server
+/// that's slow to answer can't hold up the results screen. each thread's outcome comes back here.
+#[derive(Debug)]
+pub struct Uploads {
+    tx: Sender<Result<String>>,
+    rx: Receiver<Result<String>>,
+    pending: usize,
+}
+
+impl Default for Uploads {
+    fn default() -> Self {
+        let (tx, rx) = mpsc::channel();
+        Self { tx, rx, pending: 0 }
+    }
+}
+
+impl Uploads {
+    pub fn send(&mut self, server: String, submission: Submission) {
+        let tx = self.tx.clone();
+        self.pending += 1;
+        thread::spawn(move || {
+            let _ = tx.send(submit(&server, &submission).map(|()| server));
+        });
+    }
+
+    /// how a finished upload went, the server's address if it took the result
+    pub fn poll(&mut self) -> Option<Result<String>> {
+        let outcome = self.rx.try_recv().ok()?;
+        self.pending -= 1;
+        Some(outcome)
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.pending > 0
+    }
+
+    /// waits up to `limit` for the uploads still going, so quitting doesn't cut one off.
+    /// the last outcome, if any came in.
+    pub fn wait(&mut self, limit: Duration) -> Option<Result<String>> {
+        let deadline = std::time::Instant::now() + limit;
+        let mut last = None;
+        while self.pending > 0 {
+            let Ok(outcome) = self.rx.recv_timeout(deadline.saturating_duration_since(std::time::Instant::now())) else { break };
+            self.pending -= 1;
+            last = Some(outcome);
+        }
+        last
+    }
+}
+
+/// what the results screen says about an upload
+pub fn upload_notice(outcome: &Result<String>) -> String {
+    match outcome {
+        Ok(server) => format!("result sent to {}", server),
+        Err(e) => format!("couldn't send the result: {}", e),
+    }
+}
+
+/// a submission off the wire, or None if it isn't one. what's shown and saved is kept to
+/// one short line each, whatever was sent.
+pub fn parse_submission(line: &str) -> Option<Submission> {
+    let mut s: Submission = serde_json::from_str(line.trim()).ok()?;
+    let clean = |v: &str, max: usize| v.chars().filter(|c| !c.is_control()).take(max).collect::<String>().trim().to_string();
+    s.student = clean(&s.student, 40);
+    s.date = clean(&s.date, 10);
+    s.time = clean(&s.time, 5);
+    s.test = clean(&s.test, 60);
+    s.note = clean(&s.note, 80);
+    let sane = s.wpm.is_finite() && s.wpm >= 0.0 && (0.0..=100.0).contains(&s.accuracy);
+    (!s.student.is_empty() && sane).then_some(s)
+}
+
+/// listens on `addr` and hands over each submission as it comes in. connections past
+/// `MAX_HANDLERS` at once are turned away rather than each getting a thread.
+pub fn serve(addr: &str) -> Result<Receiver<Submission>> {
+    let listener = TcpListener::bind(addr).with_context(|| format!("couldn't listen on {}", addr))?;
+    let (tx, submissions) = mpsc::channel();
+    let handling = Arc::new(AtomicUsize::new(0));
+    thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let Some(slot) = HandlerSlot::take(&handling) else {
+                let _ = stream.write_all(b"busy\n");
+                continue;
+            };
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let _slot = slot;
+                if let Some(submission) = receive(stream) {
+                    let _ = tx.send(submission);
+                }
+            });
+        }
+    });
+    Ok(submissions)
+}
+
+/// one of the `MAX_HANDLERS` connections being read, given back when the handler ends
+struct HandlerSlot(Arc<AtomicUsize>);
+
+impl HandlerSlot {
+    fn take(handling: &Arc<AtomicUsize>) -> Option<Self> {
+        handling.fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| (n < MAX_HANDLERS).then_some(n + 1)).ok()?;
+        Some(Self(Arc::clone(handling)))
+    }
+}
+
+impl Drop for HandlerSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+fn receive(mut stream: TcpStream) -> Option<Submission> {
+    stream.set_read_timeout(Some(Duration::from_secs(5))).ok()?;
+    let mut line = String::new();
+    BufReader::new((&stream).take(MAX_SUBMISSION)).read_line(&mut line).ok()?;
+    let submission = parse_submission(&line);
+    let _ = stream.write_all(if submission.is_some() { b"ok\n" } else { b"bad\n" });
+    submission
+}
+
+/// this machine's address on the local network, for telling students where to send results.
+/// nothing is sent: connecting a udp socket only picks the route.
+pub fn lan_address() -> Option<IpAddr> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("192.0.2.1:9").ok()?;
+    socket.local_addr().ok().map(|a| a.ip()).filter(|ip| !ip.is_unspecified())
+}
+
+/// one student's results so far
+#[derive(Debug, Clone)]
+pub struct StudentResults {
+    pub name: String,
+    pub tests: usize,
+    pub passed: usize,
+    pub best_wpm: f64,
+    pub latest: Submission,
+}
+
+/// everyone who's sent a result, in the order they first did
+#[derive(Debug, Default)]
+pub struct Roster {
+    pub students: Vec<StudentResults>,
+    pub results: usize,
+}
+
+impl Roster {
+    pub fn add(&mut self, submission: Submission) {
+        self.results += 1;
+        let passed = submission.passed as usize;
+        let wpm = if submission.passed { submission.wpm } else { 0.0 };
+        match self.students.iter_mut().find(|s| s.name == submission.student) {
+            Some(s) => {
+                s.tests += 1;
+                s.passed += passed;
+                s.best_wpm = s.best_wpm.max(wpm);
+                s.latest = submission;
+            }
+            None => self.students.push(StudentResults {
+                name: submission.student.clone(),
+                tests: 1,
+                passed,
+                best_wpm: wpm,
+                latest: submission,
+            }),
+        }
+    }
+}
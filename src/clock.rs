@@ -0,0 +1,62 @@
+use chrono::{DateTime, Local};
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// where App gets the time from. the real one in the binary, a hand-cranked one under test.
+pub trait Clock {
+    fn now(&self) -> Instant;
+    /// the date and time of day at `now()`, for what goes by the calendar
+    fn local_now(&self) -> DateTime<Local>;
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn local_now(&self) -> DateTime<Local> {
+        Local::now()
+    }
+}
+
+/// only moves when told to. clones share the same time, so a test can keep one and give App another.
+#[derive(Clone)]
+pub struct ManualClock {
+    now: Rc<Cell<Instant>>,
+    local: Rc<Cell<DateTime<Local>>>,
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        Self { now: Rc::new(Cell::new(Instant::now())), local: Rc::new(Cell::new(Local::now())) }
+    }
+
+    pub fn advance(&self, by: Duration) {
+        self.now.set(self.now.get() + by);
+        self.local.set(self.local.get() + by);
+    }
+
+    /// sets the date and time of day, leaving `now()` where it is
+    pub fn set_local(&self, at: DateTime<Local>) {
+        self.local.set(at);
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.now.get()
+    }
+
+    fn local_now(&self) -> DateTime<Local> {
+        self.local.get()
+    }
+}
@@ -1,7 +1,8 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use config::{Config, File};
-use directories::ProjectDirs;
 use serde::Deserialize;
+use crate::models::Mode;
+use crate::utils::paths;
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct Theme {
@@ -10,7 +11,8 @@ pub struct Theme {
     pub caret: String,   // cursor block color
     pub text: String,    // correct text
     pub sub: String,     // untyped / future text / unactive
-    #[serde(alias = "subAlt")]
+    // the config crate lowercases keys, so "subAlt" arrives as "subalt"
+    #[serde(alias = "subAlt", alias = "subalt")]
     pub sub_alt: String, // subtle UI elements (footer, borders)
     pub error: String,   // incorrect / extra text
 }
@@ -29,9 +31,651 @@ impl Default for Theme {
     }
 }
 
+impl Theme {
+    /// a theme color by its config key, e.g. "error" or "subAlt"
+    pub fn named(&self, key: &str) -> Option<&str> {
+        Some(match key {
+            "bg" => &self.bg,
+            "main" => &self.main,
+            "caret" => &self.caret,
+            "text" => &self.text,
+            "sub" => &self.sub,
+            "subAlt" | "subalt" | "sub_alt" => &self.sub_alt,
+            "error" => &self.error,
+            _ => return None,
+        })
+    }
+
+    /// brings every text color up to `min` contrast against the background (WCAG ratio,
+    /// 1 to 21). with `adjust` off nothing changes. either way, returns what fell short.
+    pub fn enforce_contrast(&mut self, min: f64, adjust: bool) -> Vec<String> {
+        let bg = self.bg.clone();
+        [
+            ("theme.text", &mut self.text),
+            ("theme.sub", &mut self.sub),
+            ("theme.main", &mut self.main),
+            ("theme.error", &mut self.error),
+        ]
+        .into_iter()
+        .filter_map(|(name, color)| enforce_contrast(name, color, &bg, min, adjust))
+        .collect()
+    }
+}
+
+/// `color` brought up to `min` against `bg`, as `Theme::enforce_contrast` does each of its
+/// colors. a note on what fell short, if it did.
+fn enforce_contrast(name: &str, color: &mut String, bg: &str, min: f64, adjust: bool) -> Option<String> {
+    let (fg, bg_rgb) = (parse_hex(color)?, parse_hex(bg)?);
+    let ratio = contrast_ratio(fg, bg_rgb);
+    if ratio >= min { return None; }
+    if !adjust {
+        return Some(format!("{} {} is only {:.1}:1 against {}, below min_contrast {:.1}", name, color, ratio, bg, min));
+    }
+    let fixed = raise_contrast(fg, bg_rgb, min);
+    let note = format!(
+        "{} {} adjusted to {} ({:.1}:1 -> {:.1}:1 against {})",
+        name, color, to_hex(fixed), ratio, contrast_ratio(fixed, bg_rgb), bg,
+    );
+    *color = to_hex(fixed);
+    Some(note)
+}
+
+type Rgb = (u8, u8, u8);
+
+fn parse_hex(hex: &str) -> Option<Rgb> {
+    let hex = hex.trim_start_matches('#');
+    if hex.len() != 6 { return None; }
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
+fn to_hex((r, g, b): Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// WCAG relative luminance
+fn luminance((r, g, b): Rgb) -> f64 {
+    let linear = |c: u8| {
+        let c = c as f64 / 255.0;
+        if c <= 0.03928 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+    };
+    0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+}
+
+/// WCAG contrast ratio, from 1 (identical) to 21 (black on white)
+fn contrast_ratio(a: Rgb, b: Rgb) -> f64 {
+    let (la, lb) = (luminance(a), luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// blends `fg` towards white or black, whichever side of `bg` has more room, just far
+/// enough to reach `min`. keeps the hue as long as it can; falls back to pure white/black.
+fn raise_contrast(fg: Rgb, bg: Rgb, min: f64) -> Rgb {
+    let white = (255, 255, 255);
+    let black = (0, 0, 0);
+    let target = if contrast_ratio(white, bg) >= contrast_ratio(black, bg) { white } else { black };
+    let mix = |c: u8, t: u8, amount: f64| (c as f64 + (t as f64 - c as f64) * amount).round() as u8;
+    (1..=20)
+        .map(|step| {
+            let amount = step as f64 / 20.0;
+            (mix(fg.0, target.0, amount), mix(fg.1, target.1, amount), mix(fg.2, target.2, amount))
+        })
+        .find(|&c| contrast_ratio(c, bg) >= min)
+        .unwrap_or(target)
+}
+
+/// quote length buckets, in characters. a quote up to `short` chars is short, and so on.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct QuoteConfig {
+    /// bucket quotes by their actual text length instead of trusting the pack's groups.
+    /// malformed packs are always rebucketed, whatever this says.
+    pub rebucket: bool,
+    pub short: usize,
+    pub medium: usize,
+    pub long: usize,
+    /// where quote mode takes its quotes from when the language has none of its own, e.g. "english"
+    pub fallback: Option<String>,
+}
+
+impl Default for QuoteConfig {
+    fn default() -> Self {
+        Self {
+            rebucket: false,
+            short: 100,
+            medium: 300,
+            long: 600,
+            fallback: None,
+        }
+    }
+}
+
+/// one test in the `typa warmup` sequence. mode precedence matches the command line: time, words, quote.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct WarmupStep {
+    pub time: Option<u64>,
+    pub words: Option<usize>,
+    pub quote: Option<String>,
+    pub language: Option<String>,
+    pub punctuation: bool,
+    pub numbers: bool,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct WarmupConfig {
+    /// warmups are saved as practice unless this is turned off
+    pub practice: bool,
+    pub steps: Vec<WarmupStep>,
+}
+
+impl Default for WarmupConfig {
+    fn default() -> Self {
+        Self {
+            practice: true,
+            steps: vec![
+                WarmupStep { time: Some(15), ..Default::default() },
+                WarmupStep { time: Some(30), punctuation: true, ..Default::default() },
+                WarmupStep { quote: Some("short".to_string()), ..Default::default() },
+            ],
+        }
+    }
+}
+
+/// a single number that folds accuracy into wpm: wpm × accuracy^n, accuracy as a fraction
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ScoreFormula {
+    #[default]
+    Off,
+    Linear,
+    Squared,
+    Cubed,
+}
+
+impl ScoreFormula {
+    pub fn score(self, wpm: f64, accuracy: f64) -> Option<f64> {
+        let power = match self {
+            Self::Off => return None,
+            Self::Linear => 1,
+            Self::Squared => 2,
+            Self::Cubed => 3,
+        };
+        Some(wpm * (accuracy / 100.0).powi(power))
+    }
+
+    /// how it's written in config and history
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Linear => "linear",
+            Self::Squared => "squared",
+            Self::Cubed => "cubed",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ScoreConfig {
+    pub formula: ScoreFormula,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct AccuracyConfig {
+    /// a wrong key taken back with backspace within this many milliseconds doesn't count
+    /// against settled accuracy. off when unset.
+    pub forgive_ms: Option<u64>,
+    /// a space pressed mid-word goes in as a mistake instead of skipping to the next word
+    pub strict_space: bool,
+}
+
+/// what happens to a word that's half typed when a time test runs out
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum LastWord {
+    /// score what was typed of it and stop
+    #[default]
+    Truncate,
+    /// let it be finished, for up to `grace_ms` past the limit
+    Grace,
+    /// if what was typed of it is right, count the whole word
+    Count,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct TimeConfig {
+    pub last_word: LastWord,
+    pub grace_ms: u64,
+}
+
+impl Default for TimeConfig {
+    fn default() -> Self {
+        Self { last_word: LastWord::Truncate, grace_ms: 2000 }
+    }
+}
+
+/// which words a dictation test lets you see
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Reveal {
+    /// the word being typed, and nothing past it
+    #[default]
+    Current,
+    /// each word for `flash_ms` as it comes up, then it's typed from memory
+    Flash,
+}
+
+/// dictation: the words ahead are hidden, to train holding a word in mind while typing it
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct DictationConfig {
+    pub reveal: Reveal,
+    pub flash_ms: u64,
+}
+
+impl Default for DictationConfig {
+    fn default() -> Self {
+        Self { reveal: Reveal::Current, flash_ms: 800 }
+    }
+}
+
+/// how much practice to get through in each calendar day, week or month. either or both of
+/// `tests` and `hours`; a quota with neither is ignored.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct Quota {
+    pub tests: Option<usize>,
+    pub hours: Option<f64>,
+    pub per: Period,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Period {
+    Day,
+    #[default]
+    Week,
+    Month,
+}
+
+/// the test everyone in a class takes with `--classroom`, and where the results go
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ClassroomConfig {
+    pub time: u64,
+    /// a result below this accuracy doesn't pass
+    pub min_accuracy: f64,
+    pub language: String,
+    pub punctuation: bool,
+    pub numbers: bool,
+    /// the results file. classroom.csv in the data directory when unset.
+    pub csv: Option<String>,
+    /// where the teacher's `typa classroom serve` is, e.g. "192.168.1.20:7878"
+    pub server: Option<String>,
+}
+
+impl Default for ClassroomConfig {
+    fn default() -> Self {
+        Self { time: 60, min_accuracy: 90.0, language: "english".to_string(), punctuation: false, numbers: false, csv: None, server: None }
+    }
+}
+
+/// attract mode: after sitting idle long enough, typa starts typing by itself
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct DemoConfig {
+    /// minutes on a fresh test before the demo starts. off when unset or 0.
+    pub idle_minutes: Option<u64>,
+    pub wpm: f64,
+}
+
+impl Default for DemoConfig {
+    fn default() -> Self {
+        Self { idle_minutes: None, wpm: 80.0 }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct EffectsConfig {
+    /// a second of confetti on the results screen after a new personal best
+    pub confetti: bool,
+}
+
+impl Default for EffectsConfig {
+    fn default() -> Self {
+        Self { confetti: true }
+    }
+}
+
+/// how generated word tests keep words from repeating
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct WordsConfig {
+    /// how many of the latest words a new one is checked against
+    pub repeat_window: usize,
+    /// how many times a word that repeats one of them is drawn again before it's let through
+    pub repeat_retries: usize,
+    /// no word twice in one test, until every word in the list has been used
+    pub no_repeats: bool,
+    /// fill the word list's sentence templates instead, when it has them
+    pub sentences: bool,
+}
+
+impl Default for WordsConfig {
+    fn default() -> Self {
+        Self { repeat_window: 8, repeat_retries: 2, no_repeats: false, sentences: false }
+    }
+}
+
+/// extra number formats for `--numbers`, on top of the everyday ones
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct NumbersConfig {
+    /// roman numerals, "XIV"
+    pub roman: bool,
+    /// quantities with units, "12kg"
+    pub units: bool,
+    /// sizes, "3x4"
+    pub dimensions: bool,
+    /// clock times, "10:30"
+    pub times: bool,
+}
+
+/// where `typa languages fetch` looks for packs
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct LanguagesConfig {
+    /// url of the pack index, a json file listing each pack's files and their sha256
+    pub index: Option<String>,
+}
+
+/// the once-a-day check for a newer release. off unless turned on, and only there with the
+/// `update-check` feature.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct UpdatesConfig {
+    pub check: bool,
+    /// a github style release feed, answering with the latest release's `tag_name`
+    pub feed: String,
+}
+
+impl Default for UpdatesConfig {
+    fn default() -> Self {
+        Self {
+            check: false,
+            feed: "https://api.github.com/repos/uint82/typa/releases/latest".to_string(),
+        }
+    }
+}
+
+/// speeds `typa calibrate` worked out, for `--pace-calibrated` and `--min-wpm-calibrated`
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct PaceConfig {
+    pub wpm: Option<u64>,
+    pub min_wpm: Option<u64>,
+}
+
+/// the keyboard layout typed on, for working out which finger pressed what
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyboardLayout {
+    #[default]
+    Qwerty,
+    Dvorak,
+    Colemak,
+}
+
+impl KeyboardLayout {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Qwerty => "qwerty",
+            Self::Dvorak => "dvorak",
+            Self::Colemak => "colemak",
+        }
+    }
+}
+
+/// the physical shape of the keyboard, for drawing it: staggered ansi or iso rows, or
+/// straight columns, all in one piece or split between the hands
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyboardGeometry {
+    #[default]
+    Ansi,
+    Iso,
+    Ortho,
+    Split,
+}
+
+impl KeyboardGeometry {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Ansi => "ansi",
+            Self::Iso => "iso",
+            Self::Ortho => "ortho",
+            Self::Split => "split",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct KeyboardConfig {
+    pub layout: KeyboardLayout,
+    pub geometry: KeyboardGeometry,
+}
+
+/// a reminder between tests to get up for a bit, after a long stretch of typing
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct BreaksConfig {
+    /// minutes of typing since the last break before one is suggested. off when unset or 0.
+    pub after_minutes: Option<u64>,
+}
+
+/// the live readout above the text while a test is running
 #[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct LiveConfig {
+    /// show live wpm next to the timer
+    pub wpm: bool,
+    /// color the timer and live wpm by how you're doing against this
+    pub goal: Option<f64>,
+    /// theme colors at either end of the gradient: well below goal, and at or above it
+    pub below: String,
+    pub above: String,
+    /// show lifetime chars typed next to the timer while waiting to start
+    pub totals: bool,
+}
+
+impl Default for LiveConfig {
+    fn default() -> Self {
+        Self { wpm: false, goal: None, below: "error".to_string(), above: "main".to_string(), totals: false }
+    }
+}
+
+/// a `main` color of its own for each mode, so it's plain which one is running. a hex color
+/// or the name of a theme color; a mode left out keeps the theme's.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct AccentConfig {
+    pub time: Option<String>,
+    pub words: Option<String>,
+    pub quote: Option<String>,
+}
+
+impl AccentConfig {
+    /// holds the accents given as colors to `min` against `theme`'s background, like the
+    /// theme's own colors. one naming a theme color is whatever that color came to.
+    pub fn enforce_contrast(&mut self, theme: &Theme, min: f64, adjust: bool) -> Vec<String> {
+        [
+            ("accents.time", &mut self.time),
+            ("accents.words", &mut self.words),
+            ("accents.quote", &mut self.quote),
+        ]
+        .into_iter()
+        .filter_map(|(name, accent)| {
+            let color = accent.as_mut().filter(|c| theme.named(c).is_none())?;
+            enforce_contrast(name, color, &theme.bg, min, adjust)
+        })
+        .collect()
+    }
+
+    /// the main color for `mode` under `theme`
+    pub fn main_for(&self, mode: &Mode, theme: &Theme) -> String {
+        let accent = match mode {
+            Mode::Time(_) => &self.time,
+            Mode::Words(_) => &self.words,
+            Mode::Quote(_) => &self.quote,
+        };
+        match accent {
+            Some(color) => theme.named(color).unwrap_or(color).to_string(),
+            None => theme.main.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusFormat {
+    #[default]
+    Plain,
+    Json,
+}
+
+/// a small file with the current state and last result, for tmux or i3status to read
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct StatuslineConfig {
+    pub enabled: bool,
+    /// defaults to $XDG_RUNTIME_DIR/typa-status
+    pub path: Option<String>,
+    pub format: StatusFormat,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ProgressMode {
+    /// only in terminals known to handle it
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct TerminalConfig {
+    /// taskbar progress through OSC 9;4
+    pub progress: ProgressMode,
+    /// live time and wpm in the terminal or tmux pane title
+    pub title: bool,
+    /// pause a running test while the screen is locked. needs a build with the
+    /// `lock-watch` feature, on linux.
+    pub pause_on_lock: bool,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct AccessibilityConfig {
+    pub enabled: bool,
+    /// ring the terminal bell on every incorrect keystroke
+    pub bell: bool,
+    /// plain top-to-bottom screens instead of the centered layout
+    pub linear: bool,
+    /// minimum WCAG contrast ratio between the theme's text colors and its background.
+    /// 4.5 is the usual bar for body text. checked whether or not `enabled` is set.
+    pub min_contrast: Option<f64>,
+    /// fix colors that fall short instead of just warning about them
+    pub adjust_contrast: bool,
+    /// test text at double width with a blank row under each line, and a bigger caret.
+    /// works whether or not `enabled` is set.
+    pub large_text: bool,
+    /// nothing on screen moves or blinks by itself: a steady caret and no confetti. works
+    /// whether or not `enabled` is set.
+    pub reduced_motion: bool,
+}
+
+impl Default for AccessibilityConfig {
+    fn default() -> Self {
+        Self { enabled: false, bell: true, linear: true, min_contrast: None, adjust_contrast: true, large_text: false, reduced_motion: false }
+    }
+}
+
+impl AccessibilityConfig {
+    pub fn rings_bell(&self) -> bool {
+        self.enabled && self.bell
+    }
+
+    pub fn is_linear(&self) -> bool {
+        self.enabled && self.linear
+    }
+
+    /// whether anything may animate at all. everything that moves on a timer of its own asks
+    /// this, so reduced motion turns it all off in one go.
+    pub fn animates(&self) -> bool {
+        !self.reduced_motion
+    }
+
+    /// accessible mode holds the caret still too
+    pub fn blinks_caret(&self) -> bool {
+        self.animates() && !self.enabled
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
 pub struct AppConfig {
     pub theme: Theme,
+    #[serde(default)]
+    pub quotes: QuoteConfig,
+    #[serde(default)]
+    pub warmup: WarmupConfig,
+    #[serde(default)]
+    pub statusline: StatuslineConfig,
+    #[serde(default)]
+    pub terminal: TerminalConfig,
+    #[serde(default)]
+    pub accessibility: AccessibilityConfig,
+    #[serde(default)]
+    pub live: LiveConfig,
+    #[serde(default)]
+    pub accents: AccentConfig,
+    #[serde(default)]
+    pub effects: EffectsConfig,
+    #[serde(default)]
+    pub demo: DemoConfig,
+    #[serde(default)]
+    pub score: ScoreConfig,
+    #[serde(default)]
+    pub accuracy: AccuracyConfig,
+    #[serde(default)]
+    pub time: TimeConfig,
+    #[serde(default)]
+    pub breaks: BreaksConfig,
+    #[serde(default)]
+    pub keyboard: KeyboardConfig,
+    #[serde(default)]
+    pub words: WordsConfig,
+    #[serde(default)]
+    pub numbers: NumbersConfig,
+    #[serde(default)]
+    pub pace: PaceConfig,
+    #[serde(default)]
+    pub languages: LanguagesConfig,
+    #[serde(default)]
+    pub updates: UpdatesConfig,
+    #[serde(default)]
+    pub dictation: DictationConfig,
+    #[serde(default)]
+    pub classroom: ClassroomConfig,
+    #[serde(default)]
+    pub quotas: Vec<Quota>,
 }
 
 impl AppConfig {
@@ -47,10 +691,7 @@ impl AppConfig {
             .set_default("theme.subAlt", defaults.sub_alt)?
             .set_default("theme.error", defaults.error)?;
 
-        if let Some(proj_dirs) = ProjectDirs::from("", "", "typa") {
-            let config_dir = proj_dirs.config_dir();
-            let config_path = config_dir.join("config.toml");
-
+        if let Some(config_path) = paths::config_file("config.toml") {
             if config_path.exists() {
                 builder = builder.add_source(File::from(config_path));
             }
@@ -64,3 +705,49 @@ impl AppConfig {
         Ok(app_config)
     }
 }
+
+/// sets `key = value` under `[section]` in config.toml for each pair, leaving the rest of the
+/// file as it was, comments and all. writes a new file if there isn't one.
+pub fn set_in_file(section: &str, values: &[(&str, String)]) -> Result<std::path::PathBuf> {
+    let path = paths::config_file("config.toml").context("no config directory on this system")?;
+    let existing = if path.exists() { std::fs::read_to_string(&path)? } else { String::new() };
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp_path = path.with_extension("tmp");
+    std::fs::write(&tmp_path, set_toml_keys(&existing, section, values))?;
+    std::fs::rename(&tmp_path, &path)?;
+    Ok(path)
+}
+
+/// `text` with each `key = value` set under `[section]`: an existing key's line is replaced,
+/// a new key goes after the section's last setting, and a missing section goes at the end
+pub fn set_toml_keys(text: &str, section: &str, values: &[(&str, String)]) -> String {
+    let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+    let header = format!("[{}]", section);
+    let is_key = |line: &str, key: &str| {
+        line.trim_start().strip_prefix(key).is_some_and(|rest| rest.trim_start().starts_with('='))
+    };
+
+    let Some(start) = lines.iter().position(|l| l.trim() == header) else {
+        if lines.last().is_some_and(|l| !l.trim().is_empty()) {
+            lines.push(String::new());
+        }
+        lines.push(header);
+        lines.extend(values.iter().map(|(key, value)| format!("{} = {}", key, value)));
+        return lines.join("\n") + "\n";
+    };
+
+    for (key, value) in values {
+        let end = lines[start + 1..].iter().position(|l| l.trim_start().starts_with('[')).map_or(lines.len(), |i| start + 1 + i);
+        let setting = format!("{} = {}", key, value);
+        match lines[start + 1..end].iter().position(|l| is_key(l, key)) {
+            Some(i) => lines[start + 1 + i] = setting,
+            None => {
+                let last = lines[start + 1..end].iter().rposition(|l| !l.trim().is_empty()).map_or(start, |i| start + 1 + i);
+                lines.insert(last + 1, setting);
+            }
+        }
+    }
+    lines.join("\n") + "\n"
+}
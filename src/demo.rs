@@ -0,0 +1,194 @@
+use crate::app::App;
+use crate::models::AppState;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::time::{Duration, Instant};
+
+/// how long the results of a demo test stay up before the next one starts
+const RESULTS_PAUSE: Duration = Duration::from_secs(3);
+const TYPO_CHANCE: f64 = 0.03;
+const TYPO_NOTICE: Duration = Duration::from_millis(250);
+
+/// types the test by itself, attract-mode style. keystrokes come at `wpm` with some
+/// jitter, a beat after each word, and the odd typo that gets noticed and fixed.
+pub struct DemoTyper {
+    wpm: f64,
+    rng: StdRng,
+    next_at: Instant,
+    /// a wrong key is on screen and the next keystroke takes it back
+    fixing: bool,
+}
+
+impl DemoTyper {
+    pub fn new(wpm: f64, now: Instant) -> Self {
+        Self { wpm: wpm.max(1.0), rng: StdRng::from_os_rng(), next_at: now, fixing: false }
+    }
+
+    /// presses every key that's due by now, and starts a fresh test once the last one's results have had their moment
+    pub fn step(&mut self, app: &mut App) {
+        let now = app.now();
+        if app.test.state == AppState::Finished {
+            if app.test.finished_at.is_some_and(|t| now.saturating_duration_since(t) >= RESULTS_PAUSE) {
+                app.restart_test();
+                self.next_at = now + Duration::from_secs(1);
+            }
+            return;
+        }
+
+        while self.next_at <= now && app.test.state != AppState::Finished {
+            let gap = self.press(app);
+            self.next_at += gap;
+        }
+    }
+
+    /// one keystroke, returning how long to wait before the next
+    fn press(&mut self, app: &mut App) -> Duration {
+        let per_char = 60.0 / (self.wpm * 5.0);
+        let jitter = self.rng.random_range(0.6..1.4);
+
+        if self.fixing {
+            self.fixing = false;
+            app.on_backspace();
+            return Duration::from_secs_f64(per_char * jitter);
+        }
+
+        let Some(target) = app.test.text.next_char() else {
+            // nothing left to type that the app hasn't ended on. shouldn't happen, but don't spin.
+            return Duration::from_secs(1);
+        };
+
+        if target != ' ' && self.rng.random_bool(TYPO_CHANCE) {
+            let wrong = (b'a' + self.rng.random_range(0..26)) as char;
+            if wrong != target {
+                self.fixing = true;
+                app.on_key(wrong);
+                return TYPO_NOTICE;
+            }
+        }
+
+        app.on_key(target);
+        let beat = if target == ' ' { 1.5 } else { 1.0 };
+        Duration::from_secs_f64(per_char * jitter * beat)
+    }
+}
+
+/// a key a script presses
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScriptKey {
+    Char(char),
+    Backspace,
+    Tab,
+    Enter,
+    Esc,
+}
+
+/// a parsed `typa demo --script` file: the words to type, if it sets them, and every key
+/// with when to press it, counted from the start
+#[derive(Debug, Default, PartialEq)]
+pub struct Script {
+    pub words: Option<Vec<String>>,
+    pub keys: Vec<(Duration, ScriptKey)>,
+}
+
+/// one step a line, blank lines and `#` comments skipped:
+///
+/// ```text
+/// words the quick brown fox    the test's words, instead of random ones
+/// wpm 90                       speed of the `type` lines after it, 60 to start with
+/// type the quick               the text, a key at a time
+/// wait 1.5s                    nothing for a while, in s or ms
+/// press backspace 3            a key, and how many times: a character, or space, tab,
+///                              enter, esc or backspace
+/// ```
+pub fn parse_script(text: &str) -> anyhow::Result<Script> {
+    use anyhow::{bail, Context};
+
+    let mut script = Script::default();
+    let mut at = Duration::ZERO;
+    let mut per_char = Duration::from_secs_f64(60.0 / (60.0 * 5.0));
+    for (i, line) in text.lines().enumerate() {
+        let n = i + 1;
+        let trimmed = line.trim_end_matches('\r').trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') { continue; }
+        let (command, rest) = trimmed.split_once(' ').unwrap_or((trimmed.trim_end(), ""));
+        match command {
+            "words" => {
+                if !script.keys.is_empty() {
+                    bail!("line {}: words has to come before any keys are pressed", n);
+                }
+                script.words = Some(rest.split_whitespace().map(str::to_string).collect());
+            }
+            "wpm" => {
+                let wpm: f64 = rest.trim().parse().ok().filter(|w: &f64| *w > 0.0).with_context(|| format!("line {}: wpm needs a speed above 0", n))?;
+                per_char = Duration::from_secs_f64(60.0 / (wpm * 5.0));
+            }
+            "type" => {
+                for c in rest.chars() {
+                    script.keys.push((at, ScriptKey::Char(c)));
+                    at += per_char;
+                }
+            }
+            "wait" => at += parse_wait(rest.trim()).with_context(|| format!("line {}: wait takes a time like 2s or 300ms", n))?,
+            "press" => {
+                let mut parts = rest.split_whitespace();
+                let key = match parts.next() {
+                    Some("space") => ScriptKey::Char(' '),
+                    Some("tab") => ScriptKey::Tab,
+                    Some("enter") => ScriptKey::Enter,
+                    Some("esc") => ScriptKey::Esc,
+                    Some("backspace") => ScriptKey::Backspace,
+                    Some(k) if k.chars().count() == 1 => ScriptKey::Char(k.chars().next().unwrap_or(' ')),
+                    _ => bail!("line {}: press takes a single character or space, tab, enter, esc or backspace", n),
+                };
+                let times: usize = match parts.next() {
+                    Some(count) => count.parse().with_context(|| format!("line {}: {} isn't a number of times", n, count))?,
+                    None => 1,
+                };
+                for _ in 0..times {
+                    script.keys.push((at, key));
+                    at += per_char;
+                }
+            }
+            other => bail!("line {}: {} isn't a script step. use words, wpm, type, wait or press", n, other),
+        }
+    }
+    Ok(script)
+}
+
+fn parse_wait(value: &str) -> Option<Duration> {
+    if let Some(ms) = value.strip_suffix("ms") {
+        return ms.trim().parse::<u64>().ok().map(Duration::from_millis);
+    }
+    let secs: f64 = value.strip_suffix('s')?.trim().parse().ok()?;
+    (secs >= 0.0 && secs.is_finite()).then(|| Duration::from_secs_f64(secs))
+}
+
+/// hands out a script's keys as they fall due
+pub struct ScriptPlayer {
+    keys: std::collections::VecDeque<(Duration, ScriptKey)>,
+    started: Instant,
+}
+
+impl ScriptPlayer {
+    pub fn new(script: &Script, now: Instant) -> Self {
+        Self { keys: script.keys.iter().copied().collect(), started: now }
+    }
+
+    /// the next key, if it's due by `now`
+    pub fn next_due(&mut self, now: Instant) -> Option<ScriptKey> {
+        let elapsed = now.saturating_duration_since(self.started);
+        if self.keys.front().is_some_and(|(at, _)| *at <= elapsed) {
+            return self.keys.pop_front().map(|(_, key)| key);
+        }
+        None
+    }
+
+    /// how long until the next key is due
+    pub fn until_next(&self, now: Instant) -> Option<Duration> {
+        let elapsed = now.saturating_duration_since(self.started);
+        self.keys.front().map(|(at, _)| at.saturating_sub(elapsed))
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
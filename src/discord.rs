@@ -42,6 +42,8 @@ fn format_with_mods(base: String, use_punctuation: bool, use_numbers: bool) -> S
 }
 
 impl DiscordPresence {
+    // no Default on purpose: constructing one opens the ipc connection
+    #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
         let start_timestamp = now_unix();
         let mut client = DiscordIpcClient::new("1497293795988078672")
@@ -62,10 +64,8 @@ impl DiscordPresence {
             if last.elapsed().as_millis() < 1000 { return; }
         }
         self.last_activity_call = Some(Instant::now());
-        if self.client.set_activity(act.clone()).is_err() {
-            if self.reconnect() {
-                let _ = self.client.set_activity(act);
-            }
+        if self.client.set_activity(act.clone()).is_err() && self.reconnect() {
+            let _ = self.client.set_activity(act);
         }
     }
 
@@ -132,6 +132,7 @@ impl DiscordPresence {
         );
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn set_result(
         &mut self,
         wpm: f64,
@@ -0,0 +1,323 @@
+//! `typa doctor`: reads every file typa keeps and says what's wrong with any of them, and
+//! what to do about it. each store gets its own check, so a new store only needs a new one.
+
+use crate::app;
+use crate::config::{AppConfig, QuoteConfig, Theme};
+use crate::generator::recent_quotes::RecentQuotes;
+use crate::generator::sourcing;
+use crate::history::TestRecord;
+use crate::models::QuoteData;
+use crate::packs;
+use crate::utils::paths;
+use anyhow::Result;
+use chrono::DateTime;
+use std::collections::HashSet;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Level {
+    Ok,
+    /// typa works around it, but something is off
+    Warning,
+    /// typa can't read it, or refuses to start because of it
+    Problem,
+}
+
+/// what `typa doctor --fix` can do without asking anyone anything
+#[derive(Debug, Clone, PartialEq)]
+pub enum Repair {
+    /// move history lines that aren't test records to history.rejected.jsonl
+    SetAsideHistoryLines,
+    /// delete a file typa would rebuild or never reads
+    Remove(PathBuf),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub store: &'static str,
+    pub level: Level,
+    pub message: String,
+    /// what to do about it, in words
+    pub advice: Option<String>,
+    pub repair: Option<Repair>,
+}
+
+impl Finding {
+    fn ok(store: &'static str, message: impl Into<String>) -> Self {
+        Self { store, level: Level::Ok, message: message.into(), advice: None, repair: None }
+    }
+
+    fn warning(store: &'static str, message: impl Into<String>) -> Self {
+        Self { store, level: Level::Warning, message: message.into(), advice: None, repair: None }
+    }
+
+    fn problem(store: &'static str, message: impl Into<String>) -> Self {
+        Self { store, level: Level::Problem, message: message.into(), advice: None, repair: None }
+    }
+
+    fn advice(mut self, advice: impl Into<String>) -> Self {
+        self.advice = Some(advice.into());
+        self
+    }
+
+    fn repair(mut self, repair: Repair) -> Self {
+        self.repair = Some(repair);
+        self
+    }
+}
+
+/// every check, in the order typa reads the files at startup
+pub fn run() -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    let config = check_config(&mut findings);
+    let quote_config = config.map(|c| c.quotes).unwrap_or_default();
+
+    let history = paths::data_file("history.json");
+    match history.as_ref().map(|p| (p, fs::read_to_string(p))) {
+        Some((_, Ok(raw))) => findings.extend(check_history(&raw)),
+        Some((path, Err(_))) if !path.exists() => findings.push(Finding::ok("history", "no history yet")),
+        Some((path, Err(e))) => findings.push(Finding::problem("history", format!("can't read {}: {}", path.display(), e))),
+        None => findings.push(Finding::warning("history", "no data directory on this system, so nothing is saved")),
+    }
+    for (store, name) in [("history", "history.tmp"), ("recent quotes", "recent_quotes.tmp")] {
+        if let Some(path) = paths::data_file(name).filter(|p| p.exists()) {
+            findings.push(
+                Finding::warning(store, format!("{} was left behind by a save that didn't finish", name))
+                    .advice("the file it was replacing is intact, so this can go")
+                    .repair(Repair::Remove(path)),
+            );
+        }
+    }
+
+    for name in packs::installed() {
+        if let Err(e) = app::load_word_data(&name) {
+            findings.push(
+                Finding::problem("packs", format!("{}: {:#}", name, e))
+                    .advice("fetch it again with `typa languages fetch`, or delete it to fall back on the built-in one if there is one"),
+            );
+        }
+    }
+
+    let mut packs = Vec::new();
+    for language in app::all_languages() {
+        if let Ok(quotes) = app::load_quote_data(&language) {
+            findings.extend(check_quotes(&language, &quotes, &quote_config));
+            packs.push((language, quotes));
+        }
+    }
+    if let Some(path) = paths::data_file("recent_quotes.json").filter(|p| p.exists()) {
+        findings.extend(check_recent_quotes(&path, &packs));
+    }
+    findings
+}
+
+fn check_config(findings: &mut Vec<Finding>) -> Option<AppConfig> {
+    let path = paths::config_file("config.toml").filter(|p| p.exists());
+    let config = match AppConfig::load() {
+        Ok(config) => config,
+        Err(e) => {
+            findings.push(
+                Finding::problem("config", format!("config.toml doesn't load, so typa starts with defaults: {}", e))
+                    .advice("fix the setting it names, or move the file away to start over"),
+            );
+            return None;
+        }
+    };
+    match path {
+        Some(path) => findings.push(Finding::ok("config", format!("{} loads", path.display()))),
+        None => findings.push(Finding::ok("config", "no config.toml, using defaults")),
+    }
+    findings.extend(check_theme(&config.theme));
+    Some(config)
+}
+
+/// theme colors that aren't `#rrggbb` come out white
+pub fn check_theme(theme: &Theme) -> Vec<Finding> {
+    let colors = [
+        ("bg", &theme.bg), ("main", &theme.main), ("caret", &theme.caret), ("text", &theme.text),
+        ("sub", &theme.sub), ("subAlt", &theme.sub_alt), ("error", &theme.error),
+    ];
+    colors.iter()
+        .filter(|(_, hex)| {
+            let digits = hex.trim_start_matches('#');
+            digits.len() != 6 || !digits.chars().all(|c| c.is_ascii_hexdigit())
+        })
+        .map(|(name, hex)| {
+            Finding::warning("config", format!("theme.{} is \"{}\", which isn't a #rrggbb color, so it shows as white", name, hex))
+        })
+        .collect()
+}
+
+/// one line per test record, or the old single json array. a line that isn't a record stops
+/// the whole history from loading, so that's the one worth fixing.
+pub fn check_history(raw: &str) -> Vec<Finding> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return vec![Finding::ok("history", "no tests saved yet")];
+    }
+
+    let records: Vec<TestRecord> = if trimmed.starts_with('[') {
+        match serde_json::from_str(trimmed) {
+            Ok(records) => records,
+            Err(e) => {
+                return vec![
+                    Finding::problem("history", format!("history.json is in the old format and doesn't parse: {}", e))
+                        .advice("restore it from a backup with `typa restore`"),
+                ];
+            }
+        }
+    } else {
+        let mut records = Vec::new();
+        let mut bad = Vec::new();
+        for (i, line) in raw.lines().enumerate() {
+            if line.trim().is_empty() { continue; }
+            match serde_json::from_str::<TestRecord>(line) {
+                Ok(record) => records.push(record),
+                // serde counts lines within the one it was given, which is always line 1
+                Err(e) => bad.push((i + 1, format!("{} at column {}", e.to_string().split(" at line ").next().unwrap_or_default(), e.column()))),
+            }
+        }
+        if let Some((first, e)) = bad.first() {
+            let lines = if bad.len() == 1 { format!("line {} isn't a test record", first) } else { format!("{} lines, starting at line {}, aren't test records", bad.len(), first) };
+            return vec![
+                Finding::problem("history", format!("{} ({}), so none of the history loads", lines, e))
+                    .advice("`typa doctor --fix` moves them to history.rejected.jsonl so the rest loads again, or restore a backup with `typa restore`")
+                    .repair(Repair::SetAsideHistoryLines),
+            ];
+        }
+        records
+    };
+
+    let mut findings = vec![Finding::ok("history", format!("{} test record(s)", records.len()))];
+    let undated = records.iter().filter(|r| DateTime::parse_from_rfc3339(&r.timestamp).is_err()).count();
+    if undated > 0 {
+        findings.push(Finding::warning("history", format!("{} record(s) have a timestamp typa can't read, so they don't count towards streaks", undated)));
+    }
+    let odd = records.iter()
+        .filter(|r| !r.duration_secs.is_finite() || r.duration_secs < 0.0 || r.wpm.is_some_and(|w| !w.is_finite() || w < 0.0))
+        .count();
+    if odd > 0 {
+        findings.push(Finding::warning("history", format!("{} record(s) have a negative or non-finite number where a time or wpm should be", odd)));
+    }
+    findings
+}
+
+/// a pack whose length groups are missing or broken, or whose stored lengths no longer match
+/// the quotes, puts quotes in the wrong length category
+pub fn check_quotes(language: &str, quotes: &QuoteData, config: &QuoteConfig) -> Vec<Finding> {
+    let store = "quotes";
+    if config.rebucket {
+        return vec![Finding::ok(store, format!("{}: bucketed by text length, as config asks", language))];
+    }
+    if !sourcing::groups_well_formed(quotes) {
+        return vec![Finding::ok(store, format!("{}: the pack's length groups are unusable, so it's bucketed by text length", language))];
+    }
+
+    let mut findings = Vec::new();
+    let stale = quotes.quotes.iter().filter(|q| q.length != q.text.chars().count()).count();
+    if stale > 0 {
+        findings.push(
+            Finding::warning(store, format!("{}: {} quote(s) have a stored length that doesn't match their text, so they may be in the wrong category", language, stale))
+                .advice("set `rebucket = true` under [quotes] to bucket by the actual text"),
+        );
+    }
+    let defaults = QuoteConfig::default();
+    if (config.short, config.medium, config.long) != (defaults.short, defaults.medium, defaults.long) {
+        findings.push(
+            Finding::warning(store, format!("{}: the thresholds under [quotes] are ignored, the pack's own groups are used", language))
+                .advice("set `rebucket = true` under [quotes] for the thresholds to apply"),
+        );
+    }
+    if findings.is_empty() {
+        findings.push(Finding::ok(store, format!("{}: {} quotes in 4 length groups", language, quotes.quotes.len())));
+    }
+    findings
+}
+
+fn check_recent_quotes(path: &Path, packs: &[(String, QuoteData)]) -> Vec<Finding> {
+    let store = "recent quotes";
+    let recent: RecentQuotes = match fs::read_to_string(path).map_err(anyhow::Error::from).and_then(|raw| Ok(serde_json::from_str(&raw)?)) {
+        Ok(recent) => recent,
+        Err(e) => {
+            return vec![
+                Finding::warning(store, format!("recent_quotes.json doesn't parse ({}), so quotes you've just seen can come up again", e))
+                    .advice("`typa doctor --fix` deletes it and the next quote test starts a fresh one")
+                    .repair(Repair::Remove(path.to_path_buf())),
+            ];
+        }
+    };
+
+    let mut findings = Vec::new();
+    for (language, ids) in recent.languages() {
+        let Some((_, pack)) = packs.iter().find(|(l, _)| l == language) else {
+            findings.push(Finding::warning(store, format!("remembers quotes for {}, which isn't a language typa has", language)));
+            continue;
+        };
+        let known: HashSet<usize> = pack.quotes.iter().map(|q| q.id).collect();
+        let unknown = ids.iter().filter(|id| !known.contains(id)).count();
+        if unknown > 0 {
+            findings.push(Finding::warning(store, format!("{}: {} remembered quote id(s) aren't in the pack, they'll be ignored", language, unknown)));
+        }
+    }
+    if findings.is_empty() {
+        findings.push(Finding::ok(store, "fine"));
+    }
+    findings
+}
+
+/// carries out every repair in `findings` and says what it did
+pub fn repair(findings: &[Finding]) -> Result<Vec<String>> {
+    let mut done = Vec::new();
+    for repair in findings.iter().filter_map(|f| f.repair.as_ref()) {
+        match repair {
+            Repair::SetAsideHistoryLines => {
+                let Some(path) = paths::data_file("history.json") else { continue };
+                let rejected = paths::data_file("history.rejected.jsonl").unwrap_or_else(|| path.with_extension("rejected.jsonl"));
+                let raw = fs::read_to_string(&path)?;
+                let (good, bad) = split_history(&raw);
+
+                let mut out = fs::OpenOptions::new().create(true).append(true).open(&rejected)?;
+                for line in &bad {
+                    writeln!(out, "{}", line)?;
+                }
+                let tmp_path = path.with_extension("tmp");
+                fs::write(&tmp_path, good.iter().map(|l| format!("{}\n", l)).collect::<String>())?;
+                fs::rename(&tmp_path, &path)?;
+                done.push(format!("moved {} line(s) out of history.json into {}", bad.len(), rejected.display()));
+            }
+            Repair::Remove(path) => {
+                fs::remove_file(path)?;
+                done.push(format!("deleted {}", path.display()));
+            }
+        }
+    }
+    Ok(done)
+}
+
+/// the history lines that are test records, and the ones that aren't
+pub fn split_history(raw: &str) -> (Vec<&str>, Vec<&str>) {
+    raw.lines()
+        .filter(|l| !l.trim().is_empty())
+        .partition(|l| serde_json::from_str::<TestRecord>(l).is_ok())
+}
+
+/// the findings as lines for the terminal, with the advice under whichever needs it
+pub fn report(findings: &[Finding]) -> String {
+    let mut out = String::new();
+    let width = findings.iter().map(|f| f.store.len()).max().unwrap_or(0);
+    for f in findings {
+        let mark = match f.level {
+            Level::Ok => "ok  ",
+            Level::Warning => "warn",
+            Level::Problem => "FAIL",
+        };
+        out.push_str(&format!("{} {:<w$}  {}\n", mark, f.store, f.message, w = width));
+        if let Some(advice) = &f.advice {
+            out.push_str(&format!("     {:<w$}  -> {}\n", "", advice, w = width));
+        }
+    }
+    out
+}
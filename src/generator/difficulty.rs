@@ -0,0 +1,40 @@
+//! how hard a test's text is to type, as a factor around 1.0: plain prose of average length
+//! words is 1, a stream of short common words comes in under it, and long words, rare
+//! letters, numbers, capitals and punctuation push it up.
+
+/// the average english word, in letters
+const BASE_WORD_LENGTH: f64 = 4.7;
+
+/// letters under about 2% of english text
+const RARE_LETTERS: &str = "bvkjxqz";
+
+/// what each thing adds: per letter of word length over the base, and per share of the
+/// text's characters
+const PER_EXTRA_LETTER: f64 = 0.08;
+const PER_RARE_SHARE: f64 = 1.5;
+const PER_SHIFTED_SHARE: f64 = 2.0;
+
+/// None for text with nothing in it to type
+pub fn difficulty<'a>(words: impl IntoIterator<Item = &'a str>) -> Option<f64> {
+    let (mut count, mut chars, mut rare, mut shifted) = (0usize, 0usize, 0usize, 0usize);
+    for word in words {
+        count += 1;
+        for c in word.chars() {
+            chars += 1;
+            if c.is_ascii_digit() || RARE_LETTERS.contains(c.to_ascii_lowercase()) || !c.is_ascii() {
+                rare += 1;
+            } else if c.is_uppercase() || !c.is_alphanumeric() {
+                // capitals and punctuation, which mostly take shift or a reach off the letters
+                shifted += 1;
+            }
+        }
+    }
+    if chars == 0 { return None; }
+
+    let average_length = chars as f64 / count as f64;
+    let score = 1.0
+        + PER_EXTRA_LETTER * (average_length - BASE_WORD_LENGTH)
+        + PER_RARE_SHARE * rare as f64 / chars as f64
+        + PER_SHIFTED_SHARE * shifted as f64 / chars as f64;
+    Some(score.max(0.5))
+}
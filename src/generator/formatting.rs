@@ -2,12 +2,12 @@ use crate::utils::strings;
 
 pub fn apply_contextual_capitalization(
     new_words: &mut [String],
-    existing_stream: &[String],
+    last_existing: Option<&str>,
     use_punctuation: bool,
 ) {
     if !use_punctuation { return; }
     if let Some(first_new) = new_words.first_mut() {
-        if let Some(last_existing) = existing_stream.last() {
+        if let Some(last_existing) = last_existing {
             if strings::is_sentence_end(last_existing) {
                 strings::capitalize_word(first_new);
             }
@@ -56,9 +56,9 @@ pub fn finalize_stream_punctuation(stream: &mut Vec<String>) {
                 last.pop();
             }
         }
-        // ellipsis is a valid ending, only force a period if truly bare
-        let c = last.chars().last().unwrap_or(' ');
-        if !matches!(c, '.' | '!' | '?') && !last.is_empty() {
+        // ellipsis is a valid ending, and so is a terminator inside quotes or brackets.
+        // only force a period if truly bare
+        if !strings::ends_with_terminator(last) && !last.is_empty() {
             last.push('.');
         }
     }
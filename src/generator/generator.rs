@@ -1,60 +1,109 @@
-use crate::models::{Mode, QuoteData, WordData, Word, WordState};
+use crate::models::{Mode, QuoteCredit, QuoteData, SentencePack, WordData};
 use super::formatting;
-use super::punctuation::PunctuationRules;
+use super::punctuation::{NumberStyles, PunctuationRules};
 use super::sourcing::TextSource;
 use super::quote_controller;
-use super::word_controller;
+use super::sentences;
+use super::recent_quotes::RecentQuotes;
+use super::word_controller::{self, RepeatRules, StreamTail};
+use anyhow::{Context, Result};
+use rand::Rng;
 
 pub struct WordGenerator {
     source: TextSource,
     rules: PunctuationRules,
+    repeats: RepeatRules,
+    /// fill the pack's sentence templates instead of drawing words one at a time
+    sentences: Option<SentencePack>,
 }
 
 pub struct GeneratedWords {
-    pub word_stream: Vec<Word>,
+    pub word_stream: Vec<String>,
     pub quote_pool: Vec<String>,
     pub total_quote_words: usize,
-    pub current_quote_source: String,
+    pub quote_sources: Vec<QuoteCredit>,
     pub generated_count: usize,
-    pub next_index: usize,
 }
 
 impl WordGenerator {
     pub fn new(word_data: WordData, use_numbers: bool, use_punctuation: bool) -> Self {
         Self {
-            source: TextSource::new(word_data),
             rules: PunctuationRules {
                 use_numbers,
                 use_punctuation,
+                number_format: word_data.number_format.clone(),
+                number_styles: NumberStyles::default(),
             },
+            source: TextSource::new(word_data),
+            repeats: RepeatRules::default(),
+            sentences: None,
+        }
+    }
+
+    /// word tests made of sentences from the pack's templates. fails for a pack without any.
+    pub fn with_sentences(mut self) -> Result<Self> {
+        let pack = self.source.sentences()
+            .filter(|pack| sentences::is_usable(pack))
+            .cloned()
+            .with_context(|| format!("the {} word list has no sentence templates", self.source.name()))?;
+        self.sentences = Some(pack);
+        Ok(self)
+    }
+
+    /// whole sentences until there are `count` words, the last one cut to fit
+    fn sentence_stream(&self, pack: &SentencePack, count: usize, rng: &mut impl Rng) -> Vec<String> {
+        let mut stream = Vec::with_capacity(count);
+        while stream.len() < count {
+            stream.extend(sentences::sentence(pack, self.rules.use_punctuation, rng));
         }
+        stream.truncate(count);
+        stream
+    }
+
+    pub fn with_number_styles(mut self, styles: NumberStyles) -> Self {
+        self.rules.number_styles = styles;
+        self
+    }
+
+    pub fn with_repeats(mut self, repeats: RepeatRules) -> Self {
+        self.repeats = repeats;
+        self
+    }
+
+    /// a tail for the stream so far, ready for this generator's next word
+    pub fn tail(&self, words: &[String]) -> StreamTail {
+        StreamTail::from_words(words, &self.repeats)
     }
 
     pub fn generate_initial_words(
         &self,
         mode: &Mode,
+        quote_count: usize,
         quote_data: &QuoteData,
+        recent_quotes: Option<&mut RecentQuotes>,
     ) -> GeneratedWords {
         let mut rng = rand::rng();
 
         let mut quote_pool = Vec::new();
         let mut total_quote_words = 0;
-        let mut current_quote_source = String::new();
+        let mut quote_sources = Vec::new();
         let mut generated_count = 0;
 
-        let mut raw_stream = match mode {
-            Mode::Time(_) => {
-                word_controller::generate_time_batch(&self.source, &self.rules, &mut rng)
+        let mut raw_stream = match (mode, &self.sentences) {
+            (Mode::Time(_), Some(pack)) => self.sentence_stream(pack, 100, &mut rng),
+            (Mode::Words(count), Some(pack)) => self.sentence_stream(pack, *count, &mut rng),
+            (Mode::Time(_), None) => {
+                word_controller::generate_time_batch(&self.source, &self.rules, &self.repeats, &mut rng)
             }
-            Mode::Words(count) => {
+            (Mode::Words(count), None) => {
                 let (stream, _) = word_controller::generate_count_batch(&self.source, &self.rules, *count, &mut rng);
                 stream
             }
-            Mode::Quote(selector) => {
-                let result = quote_controller::generate(&self.source, selector, quote_data, &mut rng);
+            (Mode::Quote(selector), _) => {
+                let result = quote_controller::generate(&self.source, selector, quote_count, quote_data, recent_quotes, &mut rng);
                 quote_pool = result.quote_pool;
                 total_quote_words = result.total_words;
-                current_quote_source = result.source_text;
+                quote_sources = result.sources;
                 result.word_stream
             }
         };
@@ -68,50 +117,53 @@ impl WordGenerator {
             generated_count = raw_stream.len();
         }
 
-        let word_stream: Vec<Word> = raw_stream
-            .into_iter()
-            .enumerate()
-            .map(|(i, text)| {
-                let mut w = Word::new(text, i);
-                if i == 0 {
-                    w.state = WordState::Active;
-                }
-                w
-            })
-            .collect();
-
-        let next_index = word_stream.len();
-
         GeneratedWords {
-            word_stream,
+            word_stream: raw_stream,
             quote_pool,
             total_quote_words,
-            current_quote_source,
+            quote_sources,
             generated_count,
-            next_index,
         }
     }
 
+    /// builds a whole words-mode stream up front, growing it the same way a running test does.
+    pub fn generate_stream(&self, count: usize, rng: &mut impl Rng) -> Vec<String> {
+        let mut stream = match &self.sentences {
+            Some(pack) => self.sentence_stream(pack, count, rng),
+            None => word_controller::generate_count_batch(&self.source, &self.rules, count, rng).0,
+        };
+        if self.rules.use_punctuation {
+            formatting::finalize_stream_punctuation(&mut stream);
+        }
+
+        let mut tail = self.tail(&stream);
+        while stream.len() < count {
+            let mut new_words = word_controller::generate_next_word(&self.source, &self.rules, &self.repeats, &mut tail, rng);
+            formatting::apply_contextual_capitalization(&mut new_words, tail.last(), self.rules.use_punctuation);
+            new_words.truncate(count - stream.len());
+            tail.extend(&new_words);
+            stream.extend(new_words);
+        }
+        stream
+    }
+
+    /// the next word or two of a running test. `tail` is what came before, and takes in what comes out.
     pub fn add_one_word(
         &self,
         mode: &Mode,
-        existing_stream: &[Word],
+        tail: &mut StreamTail,
         quote_pool: &mut Vec<String>,
         generated_count: usize,
-        next_index: usize,
-    ) -> Option<(Vec<Word>, usize)> {
+    ) -> Option<Vec<String>> {
         let mut rng = rand::rng();
 
-        let context_strings: Vec<String> = existing_stream.iter().map(|w| w.text.clone()).collect();
-
         let new_raw_words = match mode {
             Mode::Time(_) => {
-                let is_sentence_start = context_strings.last()
-                    .map(|w| word_controller::is_sentence_end_pub(w))
-                    .unwrap_or(true);
-                let ctx = word_controller::build_context_pub(&context_strings);
-                let mut new_words = word_controller::generate_smart_word(&self.source, &self.rules, &mut rng, is_sentence_start, &ctx);
-                formatting::apply_contextual_capitalization(&mut new_words, &context_strings, self.rules.use_punctuation);
+                let mut new_words = match &self.sentences {
+                    Some(pack) => sentences::sentence(pack, self.rules.use_punctuation, &mut rng),
+                    None => word_controller::generate_time_word(&self.source, &self.rules, &self.repeats, tail, &mut rng),
+                };
+                formatting::apply_contextual_capitalization(&mut new_words, tail.last(), self.rules.use_punctuation);
                 Some(new_words)
             }
             Mode::Quote(_) => {
@@ -120,8 +172,11 @@ impl WordGenerator {
             Mode::Words(target) => {
                 if generated_count < *target {
                     let remaining = *target - generated_count;
-                    let mut new_words = word_controller::generate_next_word(&self.source, &self.rules, &context_strings, &mut rng);
-                    formatting::apply_contextual_capitalization(&mut new_words, &context_strings, self.rules.use_punctuation);
+                    let mut new_words = match &self.sentences {
+                        Some(pack) => sentences::sentence(pack, self.rules.use_punctuation, &mut rng),
+                        None => word_controller::generate_next_word(&self.source, &self.rules, &self.repeats, tail, &mut rng),
+                    };
+                    formatting::apply_contextual_capitalization(&mut new_words, tail.last(), self.rules.use_punctuation);
                     // a word+dash pair could overshoot the last slot cap to remaining
                     new_words.truncate(remaining);
                     Some(new_words)
@@ -131,16 +186,9 @@ impl WordGenerator {
             }
         };
 
-        new_raw_words.map(|strs| {
-            let mut current_index = next_index;
-            let words: Vec<Word> = strs.into_iter().map(|text| {
-                let w = Word::new(text, current_index);
-                current_index += 1;
-                w
-            }).collect();
-
-            let new_next_index = current_index;
-            (words, new_next_index)
-        })
+        if let Some(words) = &new_raw_words {
+            tail.extend(words);
+        }
+        new_raw_words
     }
 }
@@ -1,8 +1,14 @@
+pub mod difficulty;
 pub mod formatting;
+#[allow(clippy::module_inception)]
 pub mod generator;
 pub mod punctuation;
 pub mod sourcing;
 pub mod quote_controller;
+pub mod recent_quotes;
+pub mod sentences;
 pub mod word_controller;
 
-pub use generator::{WordGenerator};
+pub use generator::{GeneratedWords, WordGenerator};
+pub use punctuation::NumberStyles;
+pub use word_controller::{RepeatRules, StreamTail};
@@ -1,5 +1,6 @@
 use rand::prelude::IndexedRandom;
-use rand::Rng;
+use rand::{Rng, RngCore};
+use crate::models::NumberFormat;
 use crate::utils::strings;
 
 const MIN_SENTENCE_WORDS: usize = 6;
@@ -8,6 +9,21 @@ const MIN_COMMA_GAP: usize = 3;
 pub struct PunctuationRules {
     pub use_punctuation: bool,
     pub use_numbers: bool,
+    pub number_format: NumberFormat,
+    pub number_styles: NumberStyles,
+}
+
+/// number formats beyond the everyday ones, each off unless asked for
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NumberStyles {
+    /// "XIV", "MCMXCIV"
+    pub roman: bool,
+    /// "12kg", "2.5GB"
+    pub units: bool,
+    /// "3x4", "1920x1080"
+    pub dimensions: bool,
+    /// "10:30", "23:05"
+    pub times: bool,
 }
 
 /// state the caller threads through so apply() can make gap-aware decisions
@@ -16,6 +32,12 @@ pub struct GenerationContext {
     pub words_since_last_comma: usize,
 }
 
+impl Default for GenerationContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl GenerationContext {
     pub fn new() -> Self {
         Self { words_since_terminator: 0, words_since_last_comma: MIN_COMMA_GAP }
@@ -47,7 +69,7 @@ impl PunctuationRules {
     ) -> String {
         // digits look wrong at sentence start (right after . ! ?)
         if self.use_numbers && !is_sentence_start && rng.random_bool(0.12) {
-            return self.generate_number(rng);
+            return generate_number(rng, &self.number_format, &self.number_styles);
         }
 
         if !self.use_punctuation {
@@ -66,24 +88,12 @@ impl PunctuationRules {
             let p_type = rng.random_range(0..100u32);
             match p_type {
                 // comma: 25% share (down from 40%) and gated by MIN_COMMA_GAP
-                0..=24 => {
-                    if can_comma { word.push(','); }
-                }
-                25..=42 => {
-                    if can_end_sentence { word.push('.'); }
-                }
-                43..=52 => {
-                    if can_end_sentence { word.push(';'); }
-                }
-                53..=57 => {
-                    if can_end_sentence { word.push(':'); }
-                }
-                58..=65 => {
-                    if can_end_sentence { word.push('!'); }
-                }
-                66..=73 => {
-                    if can_end_sentence { word.push('?'); }
-                }
+                0..=24 if can_comma => word.push(','),
+                25..=42 if can_end_sentence => word.push('.'),
+                43..=52 if can_end_sentence => word.push(';'),
+                53..=57 if can_end_sentence => word.push(':'),
+                58..=65 if can_end_sentence => word.push('!'),
+                66..=73 if can_end_sentence => word.push('?'),
                 74..=78 => {
                     // ellipsis is fine at any point. it trails off rather than ends
                     word.push_str("...");
@@ -101,30 +111,6 @@ impl PunctuationRules {
         self.use_punctuation && rng.random_bool(0.02)
     }
 
-    fn generate_number(&self, rng: &mut impl Rng) -> String {
-        match rng.random_range(0..100u32) {
-            0..=34  => rng.random_range(0..=9999u32).to_string(),
-            35..=54 => {
-                let n = rng.random_range(1..=100u32);
-                format!("{}{}", n, ordinal_suffix(n))
-            }
-            55..=69 => {
-                let whole = rng.random_range(0..=99u32);
-                let frac  = rng.random_range(0..=9u32);
-                format!("{}.{}", whole, frac)
-            }
-            70..=79 => format!("{}%", rng.random_range(1..=100u32)),
-            80..=89 => format!("-{}", rng.random_range(1..=999u32)),
-            // en dash ranges: years, pages, scores, quantities
-            90..=99 => {
-                let lo = rng.random_range(1..=999u32);
-                let hi = lo + rng.random_range(1..=100u32);
-                format!("{}–{}", lo, hi)
-            }
-            _       => rng.random_range(0..=9999u32).to_string(),
-        }
-    }
-
     fn apply_contraction(&self, original: &str, rng: &mut impl Rng) -> String {
         let lower = original.to_lowercase();
         if let Some(replacements) = self.get_contraction_replacements(&lower) {
@@ -144,7 +130,7 @@ impl PunctuationRules {
             return replacement.to_uppercase();
         }
 
-        let first_is_upper = original.chars().next().map_or(false, |c| c.is_uppercase());
+        let first_is_upper = original.chars().next().is_some_and(|c| c.is_uppercase());
         if first_is_upper {
             let mut chars = replacement.chars();
             chars.next().map_or_else(String::new, |f| {
@@ -197,10 +183,108 @@ impl PunctuationRules {
     }
 }
 
+/// writes one of the optional number styles
+type StyleFn = fn(&mut dyn RngCore, &NumberFormat) -> String;
+
+const UNITS: [&str; 14] = ["kg", "g", "km", "m", "cm", "mm", "ml", "l", "s", "ms", "h", "KB", "MB", "GB"];
+
+/// a random number the way `format` writes them: counts, ordinals, decimals, percentages,
+/// negatives and ranges, and each of `styles` that's on with a tenth of the share
+pub fn generate_number(rng: &mut impl Rng, format: &NumberFormat, styles: &NumberStyles) -> String {
+    let extras: Vec<StyleFn> = [
+        (styles.roman, roman_number as StyleFn),
+        (styles.units, unit_number),
+        (styles.dimensions, dimensions),
+        (styles.times, clock_time),
+    ].into_iter().filter_map(|(on, style)| on.then_some(style)).collect();
+
+    match rng.random_range(0..100 + 10 * extras.len() as u32) {
+        0..=34  => group_thousands(rng.random_range(0..=9999u32), format),
+        35..=54 => {
+            let n = rng.random_range(1..=100u32);
+            format!("{}{}", n, ordinal_suffix(n))
+        }
+        55..=69 => {
+            let whole = rng.random_range(0..=99u32);
+            let frac  = rng.random_range(0..=9u32);
+            format!("{}{}{}", whole, format.decimal, frac)
+        }
+        70..=79 => format!("{}%", rng.random_range(1..=100u32)),
+        80..=89 => format!("-{}", rng.random_range(1..=999u32)),
+        // en dash ranges: years, pages, scores, quantities
+        90..=99 => {
+            let lo = rng.random_range(1..=999u32);
+            let hi = lo + rng.random_range(1..=100u32);
+            format!("{}–{}", lo, group_thousands(hi, format))
+        }
+        n       => extras[(n as usize - 100) / 10](rng, format),
+    }
+}
+
+/// chapter numbers mostly, and now and then a year
+fn roman_number(rng: &mut dyn RngCore, _: &NumberFormat) -> String {
+    let n = if rng.random_bool(0.7) { rng.random_range(1..=30) } else { rng.random_range(1900..=2030) };
+    to_roman(n)
+}
+
+pub fn to_roman(mut n: u32) -> String {
+    const NUMERALS: [(u32, &str); 13] = [
+        (1000, "M"), (900, "CM"), (500, "D"), (400, "CD"), (100, "C"), (90, "XC"),
+        (50, "L"), (40, "XL"), (10, "X"), (9, "IX"), (5, "V"), (4, "IV"), (1, "I"),
+    ];
+    let mut out = String::new();
+    for (value, numeral) in NUMERALS {
+        while n >= value {
+            out.push_str(numeral);
+            n -= value;
+        }
+    }
+    out
+}
+
+/// a quantity with its unit straight after it, sometimes with a decimal
+fn unit_number(rng: &mut dyn RngCore, format: &NumberFormat) -> String {
+    let unit = UNITS[rng.random_range(0..UNITS.len())];
+    if rng.random_bool(0.25) {
+        format!("{}{}{}{}", rng.random_range(0..=99u32), format.decimal, rng.random_range(1..=9u32), unit)
+    } else {
+        format!("{}{}", rng.random_range(1..=500u32), unit)
+    }
+}
+
+/// "3x4" grid sizes, and screen sizes now and then
+fn dimensions(rng: &mut dyn RngCore, _: &NumberFormat) -> String {
+    if rng.random_bool(0.2) {
+        let (w, h) = [(1920, 1080), (1280, 720), (2560, 1440), (800, 600), (1024, 768)][rng.random_range(0..5)];
+        format!("{}x{}", w, h)
+    } else {
+        format!("{}x{}", rng.random_range(1..=20u32), rng.random_range(1..=20u32))
+    }
+}
+
+/// a time on the 24 hour clock
+fn clock_time(rng: &mut dyn RngCore, _: &NumberFormat) -> String {
+    format!("{}:{:02}", rng.random_range(0..=23u32), rng.random_range(0..12u32) * 5)
+}
+
+/// `n` with the thousands separator every three digits, if the language uses one
+fn group_thousands(n: u32, format: &NumberFormat) -> String {
+    let digits = n.to_string();
+    let Some(sep) = format.thousands else { return digits };
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(sep);
+        }
+        out.push(c);
+    }
+    out
+}
+
 fn ordinal_suffix(n: u32) -> &'static str {
     // teens (11th–13th) are irregular. they always use "th"
     match n % 100 {
-        11 | 12 | 13 => "th",
+        11..=13 => "th",
         _ => match n % 10 {
             1 => "st",
             2 => "nd",
@@ -1,20 +1,35 @@
-use crate::models::{QuoteData, QuoteSelector};
+use crate::models::{QuoteCredit, QuoteData, QuoteSelector};
+use super::recent_quotes::RecentQuotes;
 use super::sourcing::TextSource;
 
 pub struct QuoteResult {
     pub word_stream: Vec<String>,
     pub quote_pool: Vec<String>,
     pub total_words: usize,
-    pub source_text: String,
+    pub sources: Vec<QuoteCredit>,
 }
 
+/// chains `count` quotes into one stream. an id selector always yields that single quote.
 pub fn generate(
     source: &TextSource,
     selector: &QuoteSelector,
+    count: usize,
     quote_data: &QuoteData,
+    mut recent: Option<&mut RecentQuotes>,
     rng: &mut impl rand::Rng,
 ) -> QuoteResult {
-    if let Some((all_words, quote_source)) = source.get_quote_text(selector, quote_data, rng) {
+    let count = if matches!(selector, QuoteSelector::Id(_)) { 1 } else { count.max(1) };
+
+    let mut all_words: Vec<String> = Vec::new();
+    let mut sources: Vec<QuoteCredit> = Vec::new();
+    for _ in 0..count {
+        if let Some((words, credit)) = source.get_quote_text(selector, quote_data, recent.as_deref_mut(), rng) {
+            all_words.extend(words);
+            sources.push(credit);
+        }
+    }
+
+    if !all_words.is_empty() {
         let total_words = all_words.len();
         let (word_stream, quote_pool) = if all_words.len() > 100 {
             let stream = all_words[..100].to_vec();
@@ -29,14 +44,14 @@ pub fn generate(
             word_stream,
             quote_pool,
             total_words,
-            source_text: quote_source,
+            sources,
         }
     } else {
         QuoteResult {
             word_stream: vec!["No".to_string(), "Quote".to_string(), "Found".to_string()],
             quote_pool: Vec::new(),
             total_words: 3,
-            source_text: "System".to_string(),
+            sources: vec![QuoteCredit { source: "System".to_string(), ..Default::default() }],
         }
     }
 }
@@ -0,0 +1,62 @@
+use crate::utils::paths;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+
+/// quote ids already shown, per language, oldest first. a quote stays here until
+/// every other quote in its pool has had a turn, then the pool starts over.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RecentQuotes {
+    languages: HashMap<String, Vec<usize>>,
+    /// loaded from the file, and so saved back to it. a default one only lives in memory.
+    #[serde(skip)]
+    on_disk: bool,
+}
+
+impl RecentQuotes {
+    /// a missing or unreadable file just means nothing has been shown yet.
+    pub fn load() -> Self {
+        let recent: Self = paths::data_file("recent_quotes.json")
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default();
+        Self { on_disk: true, ..recent }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = paths::data_file("recent_quotes.json").filter(|_| self.on_disk) else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, serde_json::to_string(self)?)?;
+        fs::rename(&tmp_path, &path)?;
+        Ok(())
+    }
+
+    pub fn was_shown(&self, language: &str, id: usize) -> bool {
+        self.languages.get(language).is_some_and(|ids| ids.contains(&id))
+    }
+
+    pub fn mark_shown(&mut self, language: &str, id: usize) {
+        let ids = self.languages.entry(language.to_string()).or_default();
+        ids.retain(|&i| i != id);
+        ids.push(id);
+    }
+
+    /// each language and the ids remembered for it
+    pub fn languages(&self) -> impl Iterator<Item = (&String, &Vec<usize>)> {
+        self.languages.iter()
+    }
+
+    /// called when a pool is exhausted so its quotes become eligible again.
+    /// ids outside the pool are kept, other categories have their own cycle.
+    pub fn forget(&mut self, language: &str, pool: &[usize]) {
+        if let Some(ids) = self.languages.get_mut(language) {
+            ids.retain(|id| !pool.contains(id));
+        }
+    }
+}
@@ -0,0 +1,42 @@
+//! sentence-like streams from a word pack's templates: each template is a line of slots like
+//! "{det} {adj} {noun} {verb}", and each slot is filled from the words tagged with its name.
+//! anything outside braces goes in as it is.
+
+use crate::models::SentencePack;
+use rand::prelude::IndexedRandom;
+use rand::Rng;
+
+/// the tag a slot names, or None for a literal word
+fn slot_tag(slot: &str) -> Option<&str> {
+    slot.strip_prefix('{')?.strip_suffix('}')
+}
+
+/// every template has something in it, and every tag it names has words to fill it
+pub fn is_usable(pack: &SentencePack) -> bool {
+    !pack.templates.is_empty()
+        && pack.templates.iter().all(|template| {
+            template.split_whitespace().next().is_some()
+                && template.split_whitespace().filter_map(slot_tag).all(|tag| pack.tags.get(tag).is_some_and(|words| !words.is_empty()))
+        })
+}
+
+/// one filled in template. with punctuation it opens with a capital and ends with a period.
+pub fn sentence(pack: &SentencePack, use_punctuation: bool, rng: &mut impl Rng) -> Vec<String> {
+    let Some(template) = pack.templates.choose(rng) else { return Vec::new() };
+    let mut words: Vec<String> = template
+        .split_whitespace()
+        .filter_map(|slot| match slot_tag(slot) {
+            Some(tag) => pack.tags.get(tag)?.choose(rng).cloned(),
+            None => Some(slot.to_string()),
+        })
+        .collect();
+    if use_punctuation {
+        if let Some(first) = words.first_mut() {
+            crate::utils::strings::capitalize_word(first);
+        }
+        if let Some(last) = words.last_mut() {
+            last.push('.');
+        }
+    }
+    words
+}
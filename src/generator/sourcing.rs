@@ -1,8 +1,10 @@
-use crate::models::{QuoteData, QuoteEntry, QuoteLength, QuoteSelector, WordData};
+use crate::config::QuoteConfig;
+use crate::models::{QuoteCredit, QuoteData, QuoteEntry, QuoteLength, QuoteSelector, QuoteThresholds, SentencePack, WordData};
 use crate::utils::strings;
 use rand::prelude::IndexedRandom;
 use rand::seq::SliceRandom;
 use rand::Rng;
+use super::recent_quotes::RecentQuotes;
 
 pub struct TextSource {
     word_data: WordData,
@@ -13,6 +15,14 @@ impl TextSource {
         Self { word_data }
     }
 
+    pub fn name(&self) -> &str {
+        &self.word_data.name
+    }
+
+    pub fn sentences(&self) -> Option<&SentencePack> {
+        self.word_data.sentences.as_ref()
+    }
+
     pub fn get_random_word(&self, rng: &mut impl Rng) -> String {
         self.word_data
             .words
@@ -21,6 +31,12 @@ impl TextSource {
             .unwrap_or_else(|| "word".to_string())
     }
 
+    /// a random word that `used` doesn't know, or None once every word has been used
+    pub fn get_unused_word(&self, used: impl Fn(&str) -> bool, rng: &mut impl Rng) -> Option<String> {
+        let left: Vec<&String> = self.word_data.words.iter().filter(|w| !used(w)).collect();
+        left.choose(rng).map(|w| w.to_string())
+    }
+
     pub fn get_unique_batch(&self, count: usize, rng: &mut impl Rng) -> Vec<String> {
         let mut deck = self.word_data.words.clone();
         deck.shuffle(rng);
@@ -31,8 +47,10 @@ impl TextSource {
         &self,
         selector: &QuoteSelector,
         quote_data: &QuoteData,
+        mut recent: Option<&mut RecentQuotes>,
         rng: &mut impl Rng,
-    ) -> Option<(Vec<String>, String)> {
+    ) -> Option<(Vec<String>, QuoteCredit)> {
+        let language = quote_data.language.as_str();
         let q_opt = match selector {
             QuoteSelector::Id(target_id) => quote_data.quotes.iter().find(|q| q.id == *target_id),
             QuoteSelector::Category(len_category) => {
@@ -41,7 +59,7 @@ impl TextSource {
                     QuoteLength::Medium => &quote_data.groups[1],
                     QuoteLength::Long => &quote_data.groups[2],
                     QuoteLength::VeryLong => &quote_data.groups[3],
-                    QuoteLength::All => &vec![0, 9999],
+                    QuoteLength::All => &vec![0, usize::MAX],
                 };
                 let valid: Vec<&QuoteEntry> = quote_data
                     .quotes
@@ -49,16 +67,85 @@ impl TextSource {
                     .filter(|q| q.length >= range[0] && q.length <= range[1])
                     .collect();
 
-                valid.choose(rng).copied()
+                let pick = match recent.as_deref() {
+                    Some(recent) => {
+                        let fresh: Vec<&QuoteEntry> = valid.iter()
+                            .copied()
+                            .filter(|q| !recent.was_shown(language, q.id))
+                            .collect();
+                        fresh.choose(rng).copied()
+                    }
+                    None => valid.choose(rng).copied(),
+                };
+
+                pick.or_else(|| {
+                    // every quote in the pool has been shown. start the cycle over
+                    if let Some(recent) = recent.as_deref_mut() {
+                        let pool: Vec<usize> = valid.iter().map(|q| q.id).collect();
+                        recent.forget(language, &pool);
+                    }
+                    valid.choose(rng).copied()
+                })
             }
         };
 
+        if let (Some(q), Some(recent)) = (q_opt, recent) {
+            recent.mark_shown(language, q.id);
+            let _ = recent.save();
+        }
+
         if let Some(q) = q_opt {
             let clean_text = strings::clean_typography_symbols(&q.text);
             let all_words: Vec<String> = clean_text.split_whitespace().map(String::from).collect();
-            Some((all_words, q.source.clone()))
+            Some((all_words, q.credit()))
         } else {
             None
         }
     }
 }
+
+/// the short everyday words a kids test is made of: two to four letters, from the first few
+/// hundred of the list, which for most lists are the commonest. the whole list if none are.
+pub fn kids_words(words: &[String]) -> Vec<String> {
+    const POOL: usize = 300;
+    let short: Vec<String> = words.iter()
+        .take(POOL)
+        .filter(|w| (2..=4).contains(&w.chars().count()) && w.chars().all(char::is_lowercase))
+        .cloned()
+        .collect();
+    if short.is_empty() { words.to_vec() } else { short }
+}
+
+/// runs once at load. trusts the pack's groups unless told otherwise or the pack is malformed,
+/// and returns the thresholds that are actually in effect so labels agree with selection.
+pub fn bucket_quotes(quote_data: &mut QuoteData, config: &QuoteConfig) -> QuoteThresholds {
+    if !config.rebucket && groups_well_formed(quote_data) {
+        let g = &quote_data.groups;
+        return QuoteThresholds { short: g[0][1], medium: g[1][1], long: g[2][1] };
+    }
+
+    let thresholds = QuoteThresholds {
+        short: config.short,
+        medium: config.medium.max(config.short),
+        long: config.long.max(config.medium).max(config.short),
+    };
+    for q in &mut quote_data.quotes {
+        q.length = q.text.chars().count();
+    }
+    quote_data.groups = vec![
+        vec![0, thresholds.short],
+        vec![thresholds.short.saturating_add(1), thresholds.medium],
+        vec![thresholds.medium.saturating_add(1), thresholds.long],
+        vec![thresholds.long.saturating_add(1), usize::MAX],
+    ];
+    thresholds
+}
+
+// four ascending [lo, hi] pairs and a length on every quote. anything else gets rebucketed.
+pub fn groups_well_formed(quote_data: &QuoteData) -> bool {
+    let g = &quote_data.groups;
+    g.len() == 4
+        && g.iter().all(|r| r.len() == 2 && r[0] <= r[1])
+        && g.windows(2).all(|w| w[0][1] < w[1][0])
+        && quote_data.quotes.iter().all(|q| q.length > 0)
+}
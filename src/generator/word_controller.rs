@@ -2,18 +2,140 @@ use super::punctuation::{GenerationContext, PunctuationRules};
 use super::sourcing::TextSource;
 use crate::utils::strings;
 use rand::Rng;
+use std::collections::{HashSet, VecDeque};
 
-pub fn is_sentence_end_pub(word: &str) -> bool { strings::is_sentence_end(word) }
+/// how a new word is kept from repeating the ones before it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RepeatRules {
+    /// how many of the latest words the repeat check looks back over
+    pub window: usize,
+    /// how many times a word that repeats one of those is drawn again before it's let through
+    pub retries: usize,
+    /// no word comes up twice in a test until the whole list has been used
+    pub forbid: bool,
+}
 
-pub fn build_context_pub(stream: &[String]) -> GenerationContext { build_context(stream) }
+impl Default for RepeatRules {
+    fn default() -> Self {
+        Self { window: 8, retries: 2, forbid: false }
+    }
+}
 
-fn build_context(stream: &[String]) -> GenerationContext {
-    let mut ctx = GenerationContext::new();
-    // replay the stream to get accurate counters without extra storage
-    for word in stream {
-        ctx.advance(word);
+/// random draws for an unused word before going through the list for what's left
+const FORBID_DRAWS: usize = 16;
+
+/// everything the next word depends on from the stream so far. kept up to date as words
+/// are appended, so growing a stream costs the same at word 10,000 as at word 10.
+pub struct StreamTail {
+    ctx: GenerationContext,
+    /// newest last
+    recent: VecDeque<String>,
+    window: usize,
+    /// every word so far, bare and lowercased, when repeats are forbidden
+    seen: Option<HashSet<String>>,
+}
+
+impl Default for StreamTail {
+    fn default() -> Self {
+        Self::new(&RepeatRules::default())
     }
-    ctx
+}
+
+impl StreamTail {
+    pub fn new(repeats: &RepeatRules) -> Self {
+        Self {
+            ctx: GenerationContext::default(),
+            recent: VecDeque::new(),
+            window: repeats.window,
+            seen: repeats.forbid.then(HashSet::new),
+        }
+    }
+
+    pub fn from_words(words: &[String], repeats: &RepeatRules) -> Self {
+        let mut tail = Self::new(repeats);
+        for word in words {
+            tail.push(word);
+        }
+        tail
+    }
+
+    pub fn push(&mut self, word: &str) {
+        self.ctx.advance(word);
+        // the last word is always kept, for sentence starts
+        if self.recent.len() >= self.window.max(1) {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(word.to_string());
+        if let Some(seen) = self.seen.as_mut() {
+            seen.insert(bare(word));
+        }
+    }
+
+    pub fn extend(&mut self, words: &[String]) {
+        for word in words {
+            self.push(word);
+        }
+    }
+
+    pub fn last(&self) -> Option<&str> {
+        self.recent.back().map(String::as_str)
+    }
+
+    fn is_sentence_start(&self) -> bool {
+        self.last().map(strings::is_sentence_end).unwrap_or(true)
+    }
+
+    fn is_recent(&self, word: &str) -> bool {
+        let word = bare(word);
+        self.recent.iter().rev().take(self.window).any(|w| bare(w) == word)
+    }
+
+    fn has_seen(&self, word: &str) -> bool {
+        self.seen.as_ref().is_some_and(|seen| seen.contains(&bare(word)))
+    }
+}
+
+/// strips punctuation and capitals so neither "fast," nor "Fast" passes "fast" through the
+/// repeat checks
+fn bare(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric() && c != '\'').to_lowercase()
+}
+
+/// a raw word that doesn't break the repeat rules, if the list allows one
+fn pick_word(source: &TextSource, repeats: &RepeatRules, tail: &mut StreamTail, rng: &mut impl Rng) -> String {
+    let mut word = source.get_random_word(rng);
+    if repeats.forbid {
+        for _ in 0..FORBID_DRAWS {
+            if !tail.has_seen(&word) { return word; }
+            word = source.get_random_word(rng);
+        }
+        match source.get_unused_word(|w| tail.has_seen(w), rng) {
+            Some(unused) => return unused,
+            // every word has had its turn. start another round.
+            None => if let Some(seen) = tail.seen.as_mut() { seen.clear(); },
+        }
+        return word;
+    }
+
+    for _ in 0..repeats.retries {
+        if tail.is_recent(&word) {
+            word = source.get_random_word(rng);
+        } else {
+            break;
+        }
+    }
+    word
+}
+
+/// `count` raw words with no repeats, going round the list again if it's shorter than that
+fn unique_words(source: &TextSource, count: usize, rng: &mut impl Rng) -> Vec<String> {
+    let mut words = Vec::with_capacity(count);
+    while words.len() < count {
+        let batch = source.get_unique_batch(count - words.len(), rng);
+        if batch.is_empty() { break; }
+        words.extend(batch);
+    }
+    words
 }
 
 // em dash rules. these contexts must never precede an em dash:
@@ -42,13 +164,20 @@ fn maybe_append_dash(word: String, rules: &PunctuationRules, rng: &mut impl Rng)
 pub fn generate_time_batch(
     source: &TextSource,
     rules: &PunctuationRules,
+    repeats: &RepeatRules,
     rng: &mut impl Rng,
 ) -> Vec<String> {
     let mut stream: Vec<String> = Vec::new();
     let mut ctx = GenerationContext::new();
-    for _ in 0..100 {
+    let raw_words: Vec<String> = if repeats.forbid {
+        unique_words(source, 100, rng)
+    } else {
+        (0..100).map(|_| source.get_random_word(rng)).collect()
+    };
+    for raw in raw_words {
         let is_sentence_start = stream.last().map(|w| strings::is_sentence_end(w)).unwrap_or(true);
-        let new_words = generate_smart_word(source, rules, rng, is_sentence_start, &ctx);
+        let processed = rules.apply(raw, rng, is_sentence_start, &ctx);
+        let new_words = maybe_append_dash(processed, rules, rng);
         for w in &new_words { ctx.advance(w); }
         stream.extend(new_words);
     }
@@ -86,37 +215,28 @@ pub fn generate_count_batch(
 pub fn generate_next_word(
     source: &TextSource,
     rules: &PunctuationRules,
-    existing_stream: &[String],
+    repeats: &RepeatRules,
+    tail: &mut StreamTail,
     rng: &mut impl Rng,
 ) -> Vec<String> {
-    // strip punctuation before comparing so "fast," doesn't pass "fast" through the dedup check
-    let recent: Vec<String> = existing_stream.iter().rev().take(8)
-        .map(|w| w.trim_end_matches(|c: char| !c.is_alphanumeric() && c != '\'').to_string())
-        .collect();
-
-    let mut raw_word = source.get_random_word(rng);
-    for _ in 0..2 {
-        if recent.iter().any(|w| w == &raw_word) {
-            raw_word = source.get_random_word(rng);
-        } else {
-            break;
-        }
-    }
-
-    let is_sentence_start = existing_stream.last().map(|w| strings::is_sentence_end(w)).unwrap_or(true);
-    let ctx = build_context(existing_stream);
-    let placed = rules.apply(raw_word, rng, is_sentence_start, &ctx);
+    let raw_word = pick_word(source, repeats, tail, rng);
+    let placed = rules.apply(raw_word, rng, tail.is_sentence_start(), &tail.ctx);
     maybe_append_dash(placed, rules, rng)
 }
 
-pub fn generate_smart_word(
+/// the next time-mode word, which skips the recent words check but not a ban on repeats
+pub fn generate_time_word(
     source: &TextSource,
     rules: &PunctuationRules,
+    repeats: &RepeatRules,
+    tail: &mut StreamTail,
     rng: &mut impl Rng,
-    is_sentence_start: bool,
-    ctx: &GenerationContext,
 ) -> Vec<String> {
-    let raw = source.get_random_word(rng);
-    let processed = rules.apply(raw, rng, is_sentence_start, ctx);
+    let raw = if repeats.forbid {
+        pick_word(source, repeats, tail, rng)
+    } else {
+        source.get_random_word(rng)
+    };
+    let processed = rules.apply(raw, rng, tail.is_sentence_start(), &tail.ctx);
     maybe_append_dash(processed, rules, rng)
 }
@@ -19,6 +19,7 @@ pub(crate) fn build_row_cache(records: &[TestRecord]) -> Vec<RowCache> {
             let mut s = format!("{} {}", r.mode, r.mode_value);
             if r.use_punctuation { s.push_str(" punctuation"); }
             if r.use_numbers     { s.push_str(" numbers"); }
+            if r.practice        { s.push_str(" practice"); }
             s
         };
         let fmt_u = |v: Option<usize>| -> String {
@@ -66,6 +67,7 @@ pub(crate) fn build_detail_cache(
         let mut s = format!("{} {}", record.mode, record.mode_value);
         if record.use_punctuation { s.push_str(" punctuation"); }
         if record.use_numbers     { s.push_str(" numbers"); }
+        if record.practice        { s.push_str(" practice"); }
         s
     };
 
@@ -99,11 +101,22 @@ pub(crate) fn build_detail_cache(
         ("duration",              format!("{:.1}s", record.duration_secs)),
         ("wpm",                   fmt_f0(record.wpm)),
         ("raw wpm",               fmt_f0(record.raw_wpm)),
+        ("adjusted wpm",          fmt_f0(record.wpm.zip(record.difficulty).map(|(w, d)| super::stats::adjusted_wpm(w, d)))),
         ("accuracy",              fmt_f1(record.accuracy, "%")),
+        ("settled accuracy",      fmt_f1(record.settled_accuracy, "%")),
         ("consistency",           fmt_f1(record.consistency, "%")),
+        ("difficulty",            record.difficulty.map(|d| format!("{:.2}", d)).unwrap_or_else(|| "-".into())),
+        ("score",                 record.score.map(|s| format!("{:.0} ({})", s, record.score_formula.as_deref().unwrap_or("?"))).unwrap_or_else(|| "-".into())),
         ("char  cor/inc/ext/mis", char_stats),
         ("keys  cor/inc/total",   key_stats),
+        ("corrected errors",      fmt_u(record.corrected_errors)),
+        ("backspaces",            record.backspaces.map(|b| {
+            let per_100 = record.total_keystrokes.filter(|&k| k > 0).map(|k| b as f64 / k as f64 * 100.0).unwrap_or(0.0);
+            format!("{} ({:.1} per 100 keys)", b, per_100)
+        }).unwrap_or_else(|| "-".into())),
         ("quote source",          record.quote_source.clone().unwrap_or_else(|| "-".into())),
+        ("quote license",         record.quote_license.clone().unwrap_or_else(|| "-".into())),
+        ("quote attribution",     record.quote_attribution_url.clone().unwrap_or_else(|| "-".into())),
     ];
 
     let label_w = fields.iter().map(|(l, _)| l.len()).max().unwrap_or(10) + 2;
@@ -134,6 +147,7 @@ pub(crate) fn build_col_width_cache(records: &[TestRecord]) -> ColWidthCache {
             let mut s = format!("{} {}", r.mode, r.mode_value);
             if r.use_punctuation { s.push_str(" punctuation"); }
             if r.use_numbers     { s.push_str(" numbers"); }
+            if r.practice        { s.push_str(" practice"); }
             s.chars().count()
         })
         .max().unwrap_or(0).max(4);
@@ -227,14 +241,12 @@ pub(crate) fn compute_columns(content_w: usize, cwc: &ColWidthCache) -> ColumnLa
     }
 }
 
-pub(crate) fn build_chart_data(records: &[TestRecord]) -> (
-    Vec<(f64, f64)>,
-    Vec<(f64, f64)>,
-    f64,
-    Vec<usize>,
-) {
+/// (wpm points, accuracy scaled onto the wpm axis, y max, record index per point)
+pub(crate) type ChartData = (Vec<(f64, f64)>, Vec<(f64, f64)>, f64, Vec<usize>);
+
+pub(crate) fn build_chart_data(records: &[TestRecord], include_practice: bool) -> ChartData {
     let completed_chrono: Vec<usize> = records.iter().enumerate()
-        .filter(|(_, r)| r.completed)
+        .filter(|(_, r)| r.completed && (include_practice || !r.practice))
         .map(|(i, _)| i)
         .collect::<Vec<_>>()
         .into_iter().rev().collect();
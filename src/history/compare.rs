@@ -0,0 +1,53 @@
+//! two past tests side by side: `typa history diff <a> <b>`, or c on two rows of the history
+//! table. tests go by the number the table shows them under, 1 for the oldest.
+
+use super::history::TestRecord;
+
+pub struct CompareRow {
+    pub label: &'static str,
+    pub a: String,
+    pub b: String,
+    /// how far b is from a, for the numbers where that means something
+    pub diff: String,
+}
+
+/// where test number `n` sits in newest-first `records`
+pub fn index_of_test(records: &[TestRecord], n: usize) -> Option<usize> {
+    (1..=records.len()).contains(&n).then(|| records.len() - n)
+}
+
+pub fn compare_rows(a: &TestRecord, b: &TestRecord) -> Vec<CompareRow> {
+    let number = |label, a: Option<f64>, b: Option<f64>, suffix: &str, places: usize| {
+        let show = |v: Option<f64>| v.map_or("-".to_string(), |v| format!("{:.*}{}", places, v, suffix));
+        let diff = match (a, b) {
+            (Some(a), Some(b)) => format!("{:+.*}", places, b - a),
+            _ => String::new(),
+        };
+        CompareRow { label, a: show(a), b: show(b), diff }
+    };
+    let text = |label, a: String, b: String| CompareRow { label, a, b, diff: String::new() };
+    let count = |v: Option<usize>| v.map(|v| v as f64);
+
+    vec![
+        text("test", test_label(a), test_label(b)),
+        text("language", a.language.clone(), b.language.clone()),
+        number("wpm", a.wpm, b.wpm, "", 1),
+        number("raw", a.raw_wpm, b.raw_wpm, "", 1),
+        number("accuracy", a.accuracy, b.accuracy, "%", 1),
+        number("consistency", a.consistency, b.consistency, "%", 0),
+        number("time", Some(a.duration_secs), Some(b.duration_secs), "s", 1),
+        number("correct", count(a.correct_chars), count(b.correct_chars), "", 0),
+        number("incorrect", count(a.incorrect_chars), count(b.incorrect_chars), "", 0),
+        number("extra", count(a.extra_chars), count(b.extra_chars), "", 0),
+        number("missed", count(a.missed_chars), count(b.missed_chars), "", 0),
+        number("backspaces", count(a.backspaces), count(b.backspaces), "", 0),
+    ]
+}
+
+fn test_label(record: &TestRecord) -> String {
+    let mut s = format!("{} {}", record.mode, record.mode_value);
+    if record.use_punctuation { s.push_str(" punct"); }
+    if record.use_numbers     { s.push_str(" num"); }
+    if !record.completed      { s.push_str(" (incomplete)"); }
+    s
+}
@@ -0,0 +1,103 @@
+//! lifetime totals: characters, words and time typed, over every test ever saved. they're
+//! kept in their own small file, topped up by `record_test`, so deleting tests or clearing
+//! the history leaves them be.
+
+use super::history::TestRecord;
+use crate::app::App;
+use crate::utils::paths;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::ops::AddAssign;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Counters {
+    pub chars: u64,
+    pub words: u64,
+    pub seconds: f64,
+}
+
+impl AddAssign for Counters {
+    fn add_assign(&mut self, other: Self) {
+        self.chars += other.chars;
+        self.words += other.words;
+        self.seconds += other.seconds;
+    }
+}
+
+impl Counters {
+    /// what a test `app` just ran adds
+    pub fn for_test(app: &App, seconds: f64) -> Self {
+        let t = &app.test;
+        Self {
+            chars: (t.live_correct_keystrokes + t.live_incorrect_keystrokes) as u64,
+            words: t.text.started_words() as u64,
+            seconds,
+        }
+    }
+
+    /// a first count, from whatever history there is. records don't say how many words they
+    /// had, so it's chars over five, the way wpm counts them. tests that weren't finished
+    /// only count for their time.
+    pub fn from_history(records: &[TestRecord]) -> Self {
+        let mut totals = Self::default();
+        for r in records {
+            let chars = r.total_keystrokes.unwrap_or(0) as u64;
+            totals += Self { chars, words: chars / 5, seconds: r.duration_secs };
+        }
+        totals
+    }
+
+    /// the saved totals, if they've been started
+    pub fn load() -> Option<Self> {
+        paths::data_file("counters.json")
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+    }
+
+    /// the saved totals, or the history counted up if there aren't any yet
+    pub fn load_or_count() -> Self {
+        Self::load().unwrap_or_else(|| Self::from_history(&super::load_history().unwrap_or_default()))
+    }
+
+    fn save(&self) -> Result<()> {
+        let Some(path) = paths::data_file("counters.json") else { return Ok(()) };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // same as the history: write it aside and rename it over, so a crash can't zero it
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, serde_json::to_string(self)?)?;
+        fs::rename(&tmp_path, &path)?;
+        Ok(())
+    }
+
+    /// "1.2M chars typed"
+    pub fn label(&self) -> String {
+        format!("{} chars typed", compact(self.chars))
+    }
+}
+
+/// adds a test to the saved totals and hands back the new ones. the first time, the history
+/// is counted up instead, which already has the test in it.
+pub(super) fn add_test(test: Counters) -> Result<Counters> {
+    let totals = match Counters::load() {
+        Some(mut totals) => {
+            totals += test;
+            totals
+        }
+        None => Counters::from_history(&super::load_history()?),
+    };
+    totals.save()?;
+    Ok(totals)
+}
+
+/// 950, 45.3k, 1.2M
+pub fn compact(n: u64) -> String {
+    match n {
+        0..1_000 => n.to_string(),
+        1_000..1_000_000 => format!("{:.1}k", n as f64 / 1e3),
+        _ => format!("{:.1}M", n as f64 / 1e6),
+    }
+}
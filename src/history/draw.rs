@@ -1,4 +1,5 @@
 use super::cache::ColumnLayout;
+use super::compare::compare_rows;
 use super::{Canvas, View};
 use super::stats::StatSection;
 use ratatui::{
@@ -70,6 +71,7 @@ pub(crate) fn draw(f: &mut Frame, canvas: &Canvas) {
 
     match canvas.view {
         View::Stats   => draw_stats(f, canvas, content_area, &p),
+        View::Compare => draw_compare(f, canvas, content_area, &p),
         View::History | View::Detail | View::Help => {
             draw_history(f, canvas, content_area, &p);
             if canvas.view == View::Detail {
@@ -81,7 +83,12 @@ pub(crate) fn draw(f: &mut Frame, canvas: &Canvas) {
         }
     }
 
-    draw_footer(f, footer_area, &canvas.view, canvas.pending_delete, &p);
+    if let Some(notice) = &canvas.notice {
+        let line = Line::styled(notice.clone(), Style::default().fg(p.sub));
+        f.render_widget(Paragraph::new(line).alignment(Alignment::Center), footer_area);
+    } else {
+        draw_footer(f, footer_area, &canvas.view, canvas.pending_delete, &p);
+    }
 }
 
 fn draw_header(f: &mut Frame, area: Rect, view: &View, p: &Palette) {
@@ -94,6 +101,7 @@ fn draw_header(f: &mut Frame, area: Rect, view: &View, p: &Palette) {
         View::Stats => "  stats",
         View::History | View::Detail
         | View::Help => "  history",
+        View::Compare => "  compare",
     };
     let title = Line::from(vec![
         Span::styled("typa", Style::default().fg(p.main).add_modifier(Modifier::BOLD)),
@@ -114,7 +122,7 @@ fn draw_tab_bar(f: &mut Frame, area: Rect, view: &View, p: &Palette) {
             Style::default().fg(p.main).add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
             Style::default().fg(p.sub),
         ),
-        View::History | View::Detail | View::Help => (
+        View::History | View::Detail | View::Help | View::Compare => (
             Style::default().fg(p.sub),
             Style::default().fg(p.main).add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
         ),
@@ -167,6 +175,12 @@ fn draw_footer(f: &mut Frame, area: Rect, view: &View, pending_delete: bool, p:
         return;
     }
 
+    if *view == View::Compare {
+        let spans = vec![Span::styled("q", key), Span::styled(" back", lbl), dot.clone(), Span::styled("tab", key), Span::styled(" history", lbl)];
+        f.render_widget(Paragraph::new(Line::from(spans)).alignment(Alignment::Center), area);
+        return;
+    }
+
     let has_help = matches!(view, View::History | View::Help);
 
     let spans = if w >= (if has_help { 62 } else { 51 }) {
@@ -248,9 +262,7 @@ fn draw_stat_sections(
     let draw_w  = if overflows { area.width.saturating_sub(1) } else { area.width };
     let bottom  = area.y + area.height;
 
-    let mut y = area.y;
-    for line in lines.iter().skip(scroll) {
-        if y >= bottom { break; }
+    for (y, line) in (area.y..bottom).zip(lines.iter().skip(scroll)) {
 
         match line {
             VLine::SectionTitle { title, col_header } => {
@@ -294,7 +306,6 @@ fn draw_stat_sections(
             }
             VLine::Gap => {}
         }
-        y += 1;
     }
 
     if overflows && total > 0 {
@@ -392,7 +403,7 @@ fn draw_stats_dashboard_chart(
             .marker(symbols::Marker::Braille)
             .graph_type(GraphType::Line)
             .style(Style::default().fg(p.main).add_modifier(Modifier::BOLD))
-            .data(&wpm_data),
+            .data(wpm_data),
     ])
     .block(Block::default().borders(Borders::NONE))
     .style(Style::default().bg(p.bg))
@@ -607,6 +618,9 @@ fn draw_help_modal(f: &mut Frame, area: Rect, p: &Palette) {
     let act_rows = [
         ("enter",   "open detail"),
         ("d",       "delete record"),
+        ("i",       "save image card"),
+        ("c",       "compare two tests"),
+        ("p",       "count practice"),
         ("tab",     "switch view"),
         ("?",       "toggle help"),
         ("q / esc", "quit / close"),
@@ -808,7 +822,7 @@ fn draw_table_rows(
         let row_y    = rows_area.y + display_idx as u16;
         let row_area = Rect::new(rows_area.x, row_y, rows_area.width, 1);
         let is_sel   = history_pos == canvas.selected;
-        let cursor   = if is_sel { ">" } else { " " };
+        let cursor   = if is_sel { ">" } else if canvas.compare_mark == Some(real_idx) { "*" } else { " " };
         let fg       = if is_sel { p.main } else { p.sub };
 
         let record    = &canvas.records[real_idx];
@@ -848,3 +862,105 @@ fn draw_table(f: &mut Frame, canvas: &Canvas, area: Rect, p: &Palette) {
     draw_table_header(f, sections[0], &canvas.cols, p);
     draw_table_rows(f, canvas, sections[1], &canvas.cols, p);
 }
+
+/// two tests side by side, with their wpm charts laid over each other
+fn draw_compare(f: &mut Frame, canvas: &Canvas, area: Rect, p: &Palette) {
+    let Some((a, b)) = canvas.compare else { return };
+    let (ra, rb) = (&canvas.records[a], &canvas.records[b]);
+    let (na, nb) = (canvas.records.len() - a, canvas.records.len() - b);
+    let rows = compare_rows(ra, rb);
+
+    let head_a = format!("#{} {}", na, canvas.record_dates[a].0);
+    let head_b = format!("#{} {}", nb, canvas.record_dates[b].0);
+    let label_w = rows.iter().map(|r| r.label.len()).max().unwrap_or(0) + 2;
+    let a_w = rows.iter().map(|r| r.a.chars().count()).chain([head_a.chars().count()]).max().unwrap_or(0) + 3;
+    let b_w = rows.iter().map(|r| r.b.chars().count()).chain([head_b.chars().count()]).max().unwrap_or(0) + 3;
+    let table_w = (label_w + a_w + b_w + 8) as u16;
+
+    let sections = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(rows.len() as u16 + 2),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+        ])
+        .split(area);
+    let table_area = Rect::new(
+        area.x + area.width.saturating_sub(table_w) / 2,
+        sections[0].y,
+        table_w.min(area.width),
+        sections[0].height,
+    );
+
+    let sub = Style::default().fg(p.sub);
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(format!("{:<label_w$}", ""), sub),
+            Span::styled(format!("{:<a_w$}", head_a), Style::default().fg(p.main).add_modifier(Modifier::BOLD)),
+            Span::styled(format!("{:<b_w$}", head_b), sub.add_modifier(Modifier::BOLD)),
+            Span::styled("diff", sub),
+        ]),
+        Line::styled("─".repeat(table_area.width as usize), sub),
+    ];
+    for row in &rows {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{:<label_w$}", row.label), sub),
+            Span::styled(format!("{:<a_w$}", row.a), Style::default().fg(p.main)),
+            Span::styled(format!("{:<b_w$}", row.b), Style::default().fg(p.main)),
+            Span::styled(row.diff.clone(), sub),
+        ]));
+    }
+    f.render_widget(Paragraph::new(lines), table_area);
+
+    let empty = Vec::new();
+    let series_a = ra.wpm_history.as_ref().unwrap_or(&empty);
+    let series_b = rb.wpm_history.as_ref().unwrap_or(&empty);
+    let chart_area = sections[2];
+    if chart_area.height < 4 || (series_a.len() < 2 && series_b.len() < 2) {
+        return;
+    }
+
+    let x_max = series_a.iter().chain(series_b).map(|&(x, _)| x).fold(1.0, f64::max);
+    let y_max = (series_a.iter().chain(series_b).map(|&(_, y)| y).fold(0.0, f64::max) * 1.1).max(10.0);
+    let chart_rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Fill(1), Constraint::Length(1)])
+        .split(chart_area);
+
+    let chart = Chart::new(vec![
+        // b first, so a's line is drawn over it where they cross
+        Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(p.sub))
+            .data(series_b),
+        Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(p.main).add_modifier(Modifier::BOLD))
+            .data(series_a),
+    ])
+    .block(Block::default().borders(Borders::NONE))
+    .style(Style::default().bg(p.bg))
+    .x_axis(
+        Axis::default()
+            .style(sub)
+            .bounds([1.0, x_max])
+            .labels(vec![Span::styled("1s", sub), Span::styled(format!("{:.0}s", x_max), sub)]),
+    )
+    .y_axis(
+        Axis::default()
+            .style(sub)
+            .bounds([0.0, y_max])
+            .labels(vec![Span::styled("0", sub), Span::styled(format!("{:.0}", y_max), sub)]),
+    );
+    f.render_widget(chart, chart_rows[0]);
+
+    let legend = Line::from(vec![
+        Span::styled("━━ ", Style::default().fg(p.main).add_modifier(Modifier::BOLD)),
+        Span::styled(format!("#{} wpm  ", na), sub),
+        Span::styled("── ", sub),
+        Span::styled(format!("#{} wpm", nb), sub),
+    ]);
+    f.render_widget(Paragraph::new(legend).alignment(Alignment::Center), chart_rows[1]);
+}
@@ -1,8 +1,10 @@
+use super::counters::{self, Counters};
 use crate::app::App;
 use crate::models::Mode;
+use crate::verify;
+use crate::utils::paths;
 use anyhow::Result;
 use chrono::Utc;
-use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
 use std::fs::{self, OpenOptions};
 use std::io::Write;
@@ -18,11 +20,25 @@ pub struct TestRecord {
     pub language: String,
     pub use_punctuation: bool,
     pub use_numbers: bool,
+    /// practice runs are kept but left out of bests, averages and streaks unless asked for
+    #[serde(default)]
+    pub practice: bool,
 
     pub wpm: Option<f64>,
     pub raw_wpm: Option<f64>,
     pub accuracy: Option<f64>,
+    /// accuracy with quickly fixed mistakes forgiven, when a forgiveness window was set
+    #[serde(default)]
+    pub settled_accuracy: Option<f64>,
     pub consistency: Option<f64>,
+    /// wpm folded together with accuracy, by whichever formula was configured at the time
+    #[serde(default)]
+    pub score: Option<f64>,
+    #[serde(default)]
+    pub score_formula: Option<String>,
+    /// how hard the text was, around 1.0 for plain prose
+    #[serde(default)]
+    pub difficulty: Option<f64>,
     pub duration_secs: f64,
 
     pub correct_chars: Option<usize>,
@@ -32,17 +48,40 @@ pub struct TestRecord {
     pub correct_keystrokes: Option<usize>,
     pub incorrect_keystrokes: Option<usize>,
     pub total_keystrokes: Option<usize>,
+    /// incorrect keystrokes that were backspaced
+    #[serde(default)]
+    pub corrected_errors: Option<usize>,
+    #[serde(default)]
+    pub backspaces: Option<usize>,
 
     pub quote_source: Option<String>,
+    /// the quotes' license and attribution link, for packs that give them
+    #[serde(default)]
+    pub quote_license: Option<String>,
+    #[serde(default)]
+    pub quote_attribution_url: Option<String>,
 
     pub wpm_history: Option<Vec<(f64, f64)>>,
     pub raw_wpm_history: Option<Vec<(f64, f64)>>,
     pub errors_history: Option<Vec<(f64, f64)>>,
+    /// how many of each second's errors were backspaced later on
+    #[serde(default)]
+    pub corrected_history: Option<Vec<(f64, f64)>>,
+    /// the launch of typa the test was taken in. records from before sessions were kept have none.
+    #[serde(default)]
+    pub session: Option<String>,
+    /// what `verify::token` was worked out from, so the token can be checked again later
+    #[serde(default)]
+    pub text_digest: Option<String>,
+    #[serde(default)]
+    pub keys_digest: Option<String>,
+    #[serde(default)]
+    pub token: Option<String>,
 }
 
 
 fn history_path() -> Option<PathBuf> {
-    ProjectDirs::from("", "", "typa").map(|dirs| dirs.data_local_dir().join("history.json"))
+    paths::data_file("history.json")
 }
 
 pub fn load_history() -> Result<Vec<TestRecord>> {
@@ -117,16 +156,12 @@ pub fn clear_history() -> Result<()> {
     Ok(())
 }
 
-pub fn record_test(app: &App, completed: bool) -> Result<()> {
-    let duration_secs = app.test.start_time
-        .map(|t| t.elapsed().as_secs_f64())
+/// the history entry for the test `app` just ran, as `record_test` would save it
+pub fn build_record(app: &App, completed: bool) -> TestRecord {
+    let duration_secs = app.elapsed()
+        .map(|d| d.as_secs_f64())
         .unwrap_or(0.0);
 
-    // bail early. no point saving a test the user barely started.
-    if duration_secs < 1.0 {
-        return Ok(());
-    }
-
     let (mode_str, mode_value) = match &app.config.mode {
         Mode::Time(t)  => ("time".to_string(),  t.to_string()),
         Mode::Words(w) => ("words".to_string(), w.to_string()),
@@ -134,11 +169,11 @@ pub fn record_test(app: &App, completed: bool) -> Result<()> {
             use crate::models::QuoteSelector;
             use crate::ui::utils::get_quote_length_category;
             let label = match q {
-                QuoteSelector::Id(_) => get_quote_length_category(app.test.original_quote_length).to_string(),
+                QuoteSelector::Id(_) => get_quote_length_category(app.test.original_quote_length, &app.config.quote_thresholds).to_string(),
                 QuoteSelector::Category(len) => {
                     let s = format!("{:?}", len).to_lowercase();
                     if s == "all" {
-                        get_quote_length_category(app.test.original_quote_length).to_string()
+                        get_quote_length_category(app.test.original_quote_length, &app.config.quote_thresholds).to_string()
                     } else {
                         s
                     }
@@ -148,16 +183,20 @@ pub fn record_test(app: &App, completed: bool) -> Result<()> {
         }
     };
 
-    let quote_source = if app.test.current_quote_source.is_empty() {
+    let quote_source = if app.test.quote_sources.is_empty() {
         None
     } else {
-        Some(app.test.current_quote_source.clone())
+        Some(app.test.quote_source_label())
     };
 
-    let timestamp = Utc::now().to_rfc3339();
+    let timestamp = app.local_now().with_timezone(&Utc).to_rfc3339();
     let (correct_chars, incorrect_chars, extra_chars, missed_chars) = app.resolved_char_stats();
 
-    let record = TestRecord {
+    let text_digest = verify::text_digest(&app.test.words());
+    let keys_digest = verify::keystroke_digest(&app.test.keystrokes);
+    let token = verify::token(&text_digest, &keys_digest, app.test.final_wpm, app.test.final_accuracy, duration_secs);
+
+    TestRecord {
         timestamp,
         completed,
         mode: mode_str,
@@ -165,12 +204,17 @@ pub fn record_test(app: &App, completed: bool) -> Result<()> {
         language:        app.config.word_data.name.clone(),
         use_punctuation: app.config.use_punctuation,
         use_numbers:     app.config.use_numbers,
+        practice:        app.config.practice,
         duration_secs,
 
-        wpm:         completed.then(|| app.test.final_wpm),
-        raw_wpm:     completed.then(|| app.test.final_raw_wpm),
-        accuracy:    completed.then(|| app.test.final_accuracy),
-        consistency: completed.then(|| app.test.final_consistency),
+        wpm:         completed.then_some(app.test.final_wpm),
+        raw_wpm:     completed.then_some(app.test.final_raw_wpm),
+        accuracy:    completed.then_some(app.test.final_accuracy),
+        settled_accuracy: app.test.final_settled_accuracy.filter(|_| completed),
+        consistency: completed.then_some(app.test.final_consistency),
+        score:         app.test.final_score.filter(|_| completed),
+        score_formula: app.test.final_score.filter(|_| completed).map(|_| app.config.score_formula.name().to_string()),
+        difficulty:    app.test.final_difficulty.filter(|_| completed),
 
         correct_chars:        completed.then_some(correct_chars),
         incorrect_chars:      completed.then_some(incorrect_chars),
@@ -181,15 +225,35 @@ pub fn record_test(app: &App, completed: bool) -> Result<()> {
         total_keystrokes:     completed.then_some(
             app.test.live_correct_keystrokes + app.test.live_incorrect_keystrokes
         ),
+        corrected_errors:     completed.then_some(app.error_counts().corrected),
+        backspaces:           completed.then_some(app.test.backspaces),
 
         quote_source,
+        quote_license: app.test.quote_license_label(),
+        quote_attribution_url: app.test.quote_attribution_label(),
         wpm_history:     completed.then(|| app.test.wpm_history.clone()),
         raw_wpm_history: completed.then(|| app.test.raw_wpm_history.clone()),
         errors_history:  completed.then(|| app.test.errors_history.clone()),
-    };
+        corrected_history: completed.then(|| app.test.corrected_history.clone()),
+        session: Some(app.session.id.clone()),
+        text_digest: completed.then_some(text_digest),
+        keys_digest: completed.then_some(keys_digest),
+        token:       completed.then_some(token),
+    }
+}
+
+/// saves the test and adds it to the lifetime counters, handing back the record and the new
+/// totals. None if it was too short to keep.
+pub fn record_test(app: &App, completed: bool) -> Result<Option<(TestRecord, Counters)>> {
+    let record = build_record(app, completed);
+
+    // bail early. no point saving a test the user barely started.
+    if record.duration_secs < 1.0 {
+        return Ok(None);
+    }
 
     let Some(path) = history_path() else {
-        return Ok(());
+        return Ok(None);
     };
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
@@ -217,5 +281,8 @@ pub fn record_test(app: &App, completed: bool) -> Result<()> {
     // append only. O(1) no matter how long the history gets. that's the whole point of jsonl.
     let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
     writeln!(file, "{}", serde_json::to_string(&record)?)?;
-    Ok(())
+    drop(file);
+
+    let totals = counters::add_test(Counters::for_test(app, record.duration_secs))?;
+    Ok(Some((record, totals)))
 }
@@ -0,0 +1,72 @@
+//! lifetime totals from history in the prometheus text format, for `typa metrics`. point a
+//! textfile collector or a cron job writing into one at its output.
+
+use super::stats::compute_streaks;
+use super::TestRecord;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// every metric with its help and type lines. practice runs count towards the totals, since
+/// they were still typed, but not towards best wpm or the streaks.
+pub fn prometheus(records: &[TestRecord]) -> String {
+    let mut out = String::new();
+    let completed = records.iter().filter(|r| r.completed).count();
+    let practice = records.iter().filter(|r| r.practice).count();
+
+    counter(&mut out, "typa_tests_total", "tests started, finished or not", &[
+        ("completed=\"true\"".into(), completed as f64),
+        ("completed=\"false\"".into(), (records.len() - completed) as f64),
+    ]);
+    counter(&mut out, "typa_practice_tests_total", "tests saved as practice", &[(String::new(), practice as f64)]);
+    counter(&mut out, "typa_seconds_typed_total", "time spent typing in tests", &[
+        (String::new(), records.iter().fold(0.0, |total, r| total + r.duration_secs)),
+    ]);
+    counter(&mut out, "typa_keystrokes_total", "keys pressed in tests", &[
+        (String::new(), records.iter().filter_map(|r| r.total_keystrokes).sum::<usize>() as f64),
+    ]);
+
+    // one series per test type, so a 15s sprint doesn't hide behind a 60s best
+    let mut best: BTreeMap<(&str, &str), f64> = BTreeMap::new();
+    for r in records.iter().filter(|r| r.completed && !r.practice) {
+        let Some(wpm) = r.wpm else { continue };
+        let entry = best.entry((r.mode.as_str(), r.mode_value.as_str())).or_insert(0.0);
+        *entry = entry.max(wpm);
+    }
+    let best: Vec<(String, f64)> = best.into_iter()
+        .map(|((mode, value), wpm)| (format!("mode=\"{}\",mode_value=\"{}\"", escape(mode), escape(value)), wpm))
+        .collect();
+    gauge(&mut out, "typa_best_wpm", "best wpm for each test type", &best);
+
+    let last: Vec<(String, f64)> = records.iter().rev().find(|r| r.completed).and_then(|r| r.wpm).map(|w| (String::new(), w)).into_iter().collect();
+    gauge(&mut out, "typa_last_wpm", "wpm of the most recent finished test", &last);
+
+    let (current, longest) = compute_streaks(records, false);
+    gauge(&mut out, "typa_streak_days", "days in a row with a finished test, up to today", &[(String::new(), current as f64)]);
+    gauge(&mut out, "typa_best_streak_days", "the longest run of days in a row with a finished test", &[(String::new(), longest as f64)]);
+    out
+}
+
+fn counter(out: &mut String, name: &str, help: &str, samples: &[(String, f64)]) {
+    metric(out, name, help, "counter", samples);
+}
+
+fn gauge(out: &mut String, name: &str, help: &str, samples: &[(String, f64)]) {
+    metric(out, name, help, "gauge", samples);
+}
+
+fn metric(out: &mut String, name: &str, help: &str, kind: &str, samples: &[(String, f64)]) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+    for (labels, value) in samples {
+        if labels.is_empty() {
+            let _ = writeln!(out, "{} {}", name, value);
+        } else {
+            let _ = writeln!(out, "{}{{{}}} {}", name, labels, value);
+        }
+    }
+}
+
+/// label values can't hold a bare quote, backslash or newline
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
@@ -1,13 +1,21 @@
 mod cache;
+pub mod compare;
+pub mod counters;
+pub mod quotas;
 mod draw;
+pub mod metrics;
 pub mod stats;
+#[allow(clippy::module_inception)]
 pub mod history;
 
-pub use history::{clear_history, delete_record, load_history, record_test, TestRecord};
+pub use history::{build_record, clear_history, delete_record, load_history, record_test, TestRecord};
 
-use crate::config::Theme;
+use crate::card;
+use crate::verify;
+use crate::config::{Quota, Theme};
 use crate::ui::utils::hex_to_rgb;
-use anyhow::Result;
+use anyhow::{Context, Result};
+use counters::Counters;
 use cache::{
     build_chart_data, build_col_width_cache, build_detail_cache, build_row_cache,
     ColWidthCache, ColumnLayout, DetailCache, RowCache, compute_columns,
@@ -31,6 +39,7 @@ pub(crate) enum View {
     History,
     Detail,
     Help,
+    Compare,
 }
 
 pub(crate) struct Canvas {
@@ -39,7 +48,6 @@ pub(crate) struct Canvas {
     pub(crate) selected: usize,
     pub(crate) scroll_offset: usize,
     should_quit: bool,
-    #[allow(dead_code)]
     theme: Theme,
     pub(crate) terminal_width: u16,
     pub(crate) terminal_height: u16,
@@ -60,22 +68,34 @@ pub(crate) struct Canvas {
     pub(crate) stats_scroll: usize,
     pub(crate) stats_content_lines: usize,
     pub(crate) palette: Palette,
+    // practice runs only feed the stats view and trend charts when this is on
+    include_practice: bool,
     pending_g: bool,
     pub(crate) pending_delete: bool,
+    /// where the last card was saved, shown in the footer until the next key
+    pub(crate) notice: Option<String>,
+    lifetime: Counters,
+    quotas: Vec<Quota>,
+    /// the first of two tests picked to compare, waiting for the second
+    pub(crate) compare_mark: Option<usize>,
+    /// the two tests being compared, as indices into records
+    pub(crate) compare: Option<(usize, usize)>,
 }
 
 impl Canvas {
-    fn new(theme: Theme) -> Result<Self> {
+    fn new(theme: Theme, quotas: Vec<Quota>, include_practice: bool) -> Result<Self> {
         let mut records = load_history()?;
         records.reverse(); // newest first. the whole ui assumes this order.
+        let lifetime = Counters::load().unwrap_or_else(|| Counters::from_history(&records));
 
-        let stat_sections      = build_stat_sections(&records);
+        let quota_progress = quotas::progress(&quotas, &records, chrono::Local::now());
+        let stat_sections      = build_stat_sections(&records, &lifetime, &quota_progress, include_practice);
         let stats_content_lines = sections_total_lines(&stat_sections);
         let record_dates: Vec<(String, String)> = records.iter()
             .map(|r| local_datetime(&r.timestamp))
             .collect();
         let (stats_wpm_data, stats_acc_scaled, stats_y_max,
-             trend_record_indices) = build_chart_data(&records);
+             trend_record_indices) = build_chart_data(&records, include_practice);
 
         let history_indices: Vec<usize> = records.iter().enumerate()
             .filter(|(_, r)| r.completed)
@@ -121,8 +141,14 @@ impl Canvas {
             stats_scroll: 0,
             stats_content_lines,
             palette,
+            include_practice,
             pending_g: false,
             pending_delete: false,
+            notice: None,
+            lifetime,
+            quotas,
+            compare_mark: None,
+            compare: None,
         })
     }
 
@@ -157,10 +183,33 @@ impl Canvas {
         self.view = match self.view {
             View::Stats              => View::History,
             View::History            => View::Stats,
-            View::Detail | View::Help => View::History,
+            View::Detail | View::Help | View::Compare => View::History,
         };
     }
 
+    /// marks the selected test to compare, or compares it with the one already marked
+    fn mark_for_compare(&mut self) {
+        let Some(&real_idx) = self.history_indices.get(self.selected) else { return };
+        match self.compare_mark.take() {
+            None => {
+                self.compare_mark = Some(real_idx);
+                self.notice = Some(format!("test #{} marked, pick another and press c", self.records.len() - real_idx));
+            }
+            Some(marked) if marked == real_idx => {}
+            Some(marked) => self.open_compare(marked, real_idx),
+        }
+    }
+
+    fn open_compare(&mut self, a: usize, b: usize) {
+        self.compare = Some((a, b));
+        self.view = View::Compare;
+    }
+
+    fn close_compare(&mut self) {
+        self.compare = None;
+        self.view = View::History;
+    }
+
     fn open_detail(&mut self) {
         if !self.history_indices.is_empty() {
             let real_idx = self.history_indices[self.selected];
@@ -171,6 +220,14 @@ impl Canvas {
         }
     }
 
+    fn save_card(&mut self) {
+        let Some(&real_idx) = self.history_indices.get(self.selected) else { return };
+        self.notice = Some(match card::save(&self.records[real_idx], &self.theme) {
+            Ok(path) => format!("card saved to {}", path.display()),
+            Err(e) => format!("couldn't save the card: {}", e),
+        });
+    }
+
     fn close_detail(&mut self) {
         self.detail_cache = None;
         self.view = View::History;
@@ -273,6 +330,24 @@ impl Canvas {
         self.view = View::History;
     }
 
+    fn toggle_practice(&mut self) {
+        self.include_practice = !self.include_practice;
+        self.rebuild_stats();
+    }
+
+    fn rebuild_stats(&mut self) {
+        let (wpm, acc, ymax, trend) = build_chart_data(&self.records, self.include_practice);
+        self.stats_wpm_data         = wpm;
+        self.stats_acc_scaled       = acc;
+        self.stats_y_max            = ymax;
+        self.trend_record_indices   = trend;
+        let quota_progress          = quotas::progress(&self.quotas, &self.records, chrono::Local::now());
+        self.stat_sections          = build_stat_sections(&self.records, &self.lifetime, &quota_progress, self.include_practice);
+        self.stats_content_lines    = sections_total_lines(&self.stat_sections);
+        let viewport = self.content_height() as usize;
+        self.stats_scroll = self.stats_scroll.min(self.stats_content_lines.saturating_sub(viewport));
+    }
+
     fn confirm_delete(&mut self) {
         let real_idx = self.history_indices[self.selected];
 
@@ -304,14 +379,9 @@ impl Canvas {
         self.record_dates       = self.records.iter()
             .map(|r| local_datetime(&r.timestamp))
             .collect();
-        let (wpm, acc, ymax, trend) = build_chart_data(&self.records);
-        self.stats_wpm_data         = wpm;
-        self.stats_acc_scaled       = acc;
-        self.stats_y_max            = ymax;
-        self.trend_record_indices   = trend;
-        self.stat_sections          = build_stat_sections(&self.records);
-        self.stats_content_lines    = sections_total_lines(&self.stat_sections);
+        self.rebuild_stats();
         self.detail_cache           = None;
+        self.compare_mark           = None;
 
         let vis        = self.visible_rows().max(1);
         let max_offset = self.history_indices.len().saturating_sub(vis);
@@ -319,14 +389,46 @@ impl Canvas {
     }
 }
 
-pub fn run(theme: Theme) -> Result<()> {
-    let mut canvas = Canvas::new(theme)?;
+pub fn run(theme: Theme, quotas: Vec<Quota>, include_practice: bool) -> Result<()> {
+    let canvas = Canvas::new(theme, quotas, include_practice)?;
 
     if canvas.records.is_empty() || canvas.history_indices.is_empty() {
         println!("\n  No history yet. Complete a test to start tracking your progress.\n");
         return Ok(());
     }
+    show(canvas)
+}
 
+/// opens the history browser on tests `a` and `b` side by side, numbered as its table numbers them
+pub fn diff(theme: Theme, quotas: Vec<Quota>, a: usize, b: usize) -> Result<()> {
+    let mut canvas = Canvas::new(theme, quotas, false)?;
+    let find = |n| compare::index_of_test(&canvas.records, n)
+        .with_context(|| format!("there's no test #{}, history has {}", n, canvas.records.len()));
+    let (a, b) = (find(a)?, find(b)?);
+    canvas.open_compare(a, b);
+    show(canvas)
+}
+
+/// `typa history verify <n>`: works test `n`'s token out again from what it was made of,
+/// numbered as the history table numbers it. an error if the two don't match.
+pub fn verify(n: usize) -> Result<()> {
+    let records = load_history()?;
+    let record = n.checked_sub(1).and_then(|i| records.get(i))
+        .with_context(|| format!("there's no test #{}, history has {}", n, records.len()))?;
+    let token = record.token.as_deref().unwrap_or("-");
+    match verify::check(record) {
+        Some(true) => {
+            println!("test #{} checks out: {}", n, token);
+            println!("  wpm {:.2}, accuracy {:.2}%, {:.2}s", record.wpm.unwrap_or(0.0), record.accuracy.unwrap_or(0.0), record.duration_secs);
+            println!("  text {}, keys {}", record.text_digest.as_deref().unwrap_or("-"), record.keys_digest.as_deref().unwrap_or("-"));
+            Ok(())
+        }
+        Some(false) => anyhow::bail!("test #{} doesn't match its token {}: the record was changed after it was saved", n, token),
+        None => anyhow::bail!("test #{} has no token to check. only tests finished since tokens were added have one", n),
+    }
+}
+
+fn show(mut canvas: Canvas) -> Result<()> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
@@ -359,6 +461,7 @@ fn run_loop(
         if event::poll(std::time::Duration::from_millis(16))? {
             match event::read()? {
                 Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    canvas.notice = None;
                     match key.code {
                         KeyCode::Char('q')
                         | KeyCode::Char('\x1b')
@@ -369,6 +472,10 @@ fn run_loop(
                                 canvas.close_help();
                             } else if canvas.view == View::Detail {
                                 canvas.close_detail();
+                            } else if canvas.view == View::Compare {
+                                canvas.close_compare();
+                            } else if canvas.compare_mark.is_some() {
+                                canvas.compare_mark = None;
                             } else {
                                 canvas.quit();
                             }
@@ -389,7 +496,19 @@ fn run_loop(
                         | KeyCode::Char('1')
                         | KeyCode::Char('2') => canvas.switch_view(),
                         KeyCode::Enter if canvas.view == View::History => canvas.open_detail(),
+                        KeyCode::Char('p') if matches!(canvas.view, View::Stats | View::History) => {
+                            canvas.pending_g = false;
+                            canvas.toggle_practice();
+                        }
                         KeyCode::Char('?') if canvas.view == View::History => canvas.open_help(),
+                        KeyCode::Char('c') if canvas.view == View::History => {
+                            canvas.pending_g = false;
+                            canvas.mark_for_compare();
+                        }
+                        KeyCode::Char('i') if matches!(canvas.view, View::History | View::Detail) => {
+                            canvas.pending_g = false;
+                            canvas.save_card();
+                        }
                         KeyCode::Char('d')
                             if !key.modifiers.contains(KeyModifiers::CONTROL)
                             && canvas.view == View::History =>
@@ -400,12 +519,12 @@ fn run_loop(
                         KeyCode::Down | KeyCode::Char('j') => match canvas.view {
                             View::History => canvas.move_down(),
                             View::Stats   => canvas.stats_scroll_down(),
-                            View::Detail | View::Help => {}
+                            View::Detail | View::Help | View::Compare => {}
                         },
                         KeyCode::Up | KeyCode::Char('k') => match canvas.view {
                             View::History => canvas.move_up(),
                             View::Stats   => canvas.stats_scroll_up(),
-                            View::Detail | View::Help => {}
+                            View::Detail | View::Help | View::Compare => {}
                         },
                         KeyCode::Char('G') if canvas.view == View::History => {
                             canvas.pending_g = false;
@@ -0,0 +1,130 @@
+//! progress toward the quotas under [[quotas]]: completed tests, or hours of any test, since
+//! the start of the current day, week or month. weeks start on monday.
+
+use super::history::TestRecord;
+use crate::config::{Period, Quota};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveTime, TimeZone};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Unit {
+    Tests,
+    Hours,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuotaProgress {
+    pub unit: Unit,
+    pub per: Period,
+    /// the start of the window `done` counts
+    pub start: DateTime<Local>,
+    pub done: f64,
+    pub target: f64,
+}
+
+impl QuotaProgress {
+    /// a test just saved, counted toward this window. one from a later window starts it over.
+    pub fn add(&mut self, record: &TestRecord) {
+        let Some(at) = saved_at(record) else { return };
+        self.roll(at);
+        if at >= self.start {
+            self.tally(record);
+        }
+    }
+
+    /// starts the count over once `now` is past the window it's for. true if it did.
+    pub fn roll(&mut self, now: DateTime<Local>) -> bool {
+        let start = window_start(self.per, now);
+        if start <= self.start {
+            return false;
+        }
+        self.start = start;
+        self.done = 0.0;
+        true
+    }
+
+    fn tally(&mut self, record: &TestRecord) {
+        match self.unit {
+            Unit::Tests => self.done += record.completed as u8 as f64,
+            Unit::Hours => self.done += record.duration_secs / 3600.0,
+        }
+    }
+
+    pub fn is_met(&self) -> bool {
+        self.done >= self.target
+    }
+
+    /// "tests / month"
+    pub fn label(&self) -> String {
+        let unit = match self.unit { Unit::Tests => "tests", Unit::Hours => "hours" };
+        format!("{} / {}", unit, period_name(self.per))
+    }
+
+    /// "120/200", or "3.2/5h"
+    pub fn count(&self) -> String {
+        match self.unit {
+            Unit::Tests => format!("{:.0}/{:.0}", self.done, self.target),
+            Unit::Hours => format!("{:.1}/{}h", self.done, self.target),
+        }
+    }
+
+    /// "120/200 tests this month", for the waiting screen
+    pub fn summary(&self) -> String {
+        match self.unit {
+            Unit::Tests => format!("{} tests this {}", self.count(), period_name(self.per)),
+            Unit::Hours => format!("{} this {}", self.count(), period_name(self.per)),
+        }
+    }
+
+    /// `width` cells of bar, filled as far as the quota is
+    pub fn bar(&self, width: usize) -> String {
+        let filled = ((self.done / self.target.max(f64::EPSILON)).min(1.0) * width as f64).round() as usize;
+        format!("{}{}", "█".repeat(filled), "░".repeat(width - filled))
+    }
+}
+
+fn period_name(per: Period) -> &'static str {
+    match per {
+        Period::Day => "day",
+        Period::Week => "week",
+        Period::Month => "month",
+    }
+}
+
+/// midnight on the first day of the window `now` is in
+pub fn window_start(per: Period, now: DateTime<Local>) -> DateTime<Local> {
+    let today = now.date_naive();
+    let first = match per {
+        Period::Day => today,
+        Period::Week => today - Duration::days(today.weekday().num_days_from_monday() as i64),
+        Period::Month => today.with_day(1).unwrap_or(today),
+    };
+    // a midnight skipped by a clock change falls back on the hour after
+    let midnight = first.and_time(NaiveTime::MIN);
+    Local.from_local_datetime(&midnight).earliest()
+        .or_else(|| Local.from_local_datetime(&(midnight + Duration::hours(1))).earliest())
+        .unwrap_or(now)
+}
+
+/// each quota's progress over its current window, a quota with both tests and hours counting
+/// as two
+pub fn progress(quotas: &[Quota], records: &[TestRecord], now: DateTime<Local>) -> Vec<QuotaProgress> {
+    let targets = quotas.iter().flat_map(|q| {
+        let tests = q.tests.filter(|&t| t > 0).map(|t| (Unit::Tests, q.per, t as f64));
+        let hours = q.hours.filter(|&h| h > 0.0).map(|h| (Unit::Hours, q.per, h));
+        tests.into_iter().chain(hours)
+    });
+    targets.map(|(unit, per, target)| {
+        let start = window_start(per, now);
+        let mut p = QuotaProgress { unit, per, start, done: 0.0, target };
+        for r in records {
+            if saved_at(r).is_some_and(|at| at >= start && at <= now) {
+                p.tally(r);
+            }
+        }
+        p
+    }).collect()
+}
+
+fn saved_at(record: &TestRecord) -> Option<DateTime<Local>> {
+    DateTime::parse_from_rfc3339(&record.timestamp).ok().map(|t| t.with_timezone(&Local))
+}
@@ -1,3 +1,5 @@
+use super::counters::Counters;
+use super::quotas::QuotaProgress;
 use super::history::TestRecord;
 use chrono::{DateTime, Local, NaiveDate};
 use std::collections::HashMap;
@@ -27,9 +29,27 @@ pub(crate) fn local_datetime(ts: &str) -> (String, String) {
     }
 }
 
-pub(crate) fn compute_streaks(records: &[TestRecord]) -> (usize, usize) {
+/// wpm with the text's difficulty folded in: a stream of short common words counts for less,
+/// a quote full of punctuation and long words for more. the same text typed the same way
+/// always gives the same number, so it's only worth comparing across different texts.
+pub fn adjusted_wpm(wpm: f64, difficulty: f64) -> f64 {
+    wpm * difficulty
+}
+
+/// fewer past results than this and a percentile says more about luck than typing
+const MIN_RANKED: usize = 5;
+
+/// the top share of `past` results that `wpm` lands in, in whole percent: 1 beats all of them,
+/// 100 none. None until there's enough history to rank against.
+pub fn top_percent(past: &[f64], wpm: f64) -> Option<f64> {
+    if past.len() < MIN_RANKED { return None; }
+    let above = past.iter().filter(|&&w| w > wpm).count();
+    Some(((above + 1) as f64 / (past.len() + 1) as f64 * 100.0).ceil())
+}
+
+pub fn compute_streaks(records: &[TestRecord], include_practice: bool) -> (usize, usize) {
     let mut dates: Vec<NaiveDate> = records.iter()
-        .filter(|r| r.completed)
+        .filter(|r| r.completed && (include_practice || !r.practice))
         .filter_map(|r| {
             DateTime::parse_from_rfc3339(&r.timestamp).ok()
                 .map(|dt| dt.with_timezone(&Local).date_naive())
@@ -66,29 +86,55 @@ pub(crate) struct StatSection {
 }
 
 /// built once on load and never touched again. it's not live.
-pub(crate) fn build_stat_sections(records: &[TestRecord]) -> Vec<StatSection> {
-    let completed: Vec<&TestRecord> = records.iter().filter(|r| r.completed).collect();
+/// overview counts every test; everything past it skips practice runs unless include_practice is set.
+/// lifetime is the running totals, which count tests since deleted too.
+pub(crate) fn build_stat_sections(records: &[TestRecord], lifetime: &Counters, quotas: &[QuotaProgress], include_practice: bool) -> Vec<StatSection> {
     let total      = records.len();
-    let done       = completed.len();
+    let done       = records.iter().filter(|r| r.completed).count();
     let incomplete = total - done;
+    let practice   = records.iter().filter(|r| r.practice).count();
+
+    let completed: Vec<&TestRecord> = records.iter()
+        .filter(|r| r.completed && (include_practice || !r.practice))
+        .collect();
 
     let lifetime_ks: usize = records.iter().filter_map(|r| r.total_keystrokes).sum();
     let total_secs: u64    = records.iter().map(|r| r.duration_secs as u64).sum();
 
     let mut sections: Vec<StatSection> = Vec::new();
 
+    let mut overview_rows = vec![
+        ("tests".into(),        total.to_string()),
+        ("completed".into(),    done.to_string()),
+        ("incomplete".into(),   incomplete.to_string()),
+        ("total time".into(),   format_duration(total_secs)),
+        ("lifetime keys".into(), lifetime_ks.to_string()),
+    ];
+    if practice > 0 {
+        let state = if include_practice { "counted" } else { "excluded" };
+        overview_rows.push(("practice".into(), format!("{} ({})", practice, state)));
+    }
+
+    sections.push(StatSection { title: "overview".into(), col_header: None, rows: overview_rows });
+
     sections.push(StatSection {
-        title: "overview".into(),
+        title: "lifetime".into(),
         col_header: None,
         rows: vec![
-            ("tests".into(),        total.to_string()),
-            ("completed".into(),    done.to_string()),
-            ("incomplete".into(),   incomplete.to_string()),
-            ("total time".into(),   format_duration(total_secs)),
-            ("lifetime keys".into(), lifetime_ks.to_string()),
+            ("chars typed".into(),     lifetime.chars.to_string()),
+            ("words typed".into(),     lifetime.words.to_string()),
+            ("hours practiced".into(), format!("{:.1}", lifetime.seconds / 3600.0)),
         ],
     });
 
+    if !quotas.is_empty() {
+        sections.push(StatSection {
+            title: "quotas".into(),
+            col_header: None,
+            rows: quotas.iter().map(|q| (q.label(), format!("{} {}", q.bar(6), q.count()))).collect(),
+        });
+    }
+
     if completed.is_empty() {
         return sections;
     }
@@ -110,15 +156,45 @@ pub(crate) fn build_stat_sections(records: &[TestRecord]) -> Vec<StatSection> {
         ("avg acc".into(),  format!("{:.1}%", avg_acc)),
     ];
 
+    // tests from before difficulty was rated have nothing to adjust by, so they sit this out
+    let adj_vals: Vec<f64> = completed.iter().filter_map(|r| Some(adjusted_wpm(r.wpm?, r.difficulty?))).collect();
+    if !adj_vals.is_empty() {
+        let avg_adj = adj_vals.iter().sum::<f64>() / adj_vals.len() as f64;
+        let best_adj = adj_vals.iter().copied().fold(0.0_f64, f64::max);
+        perf_rows.push(("avg adj wpm".into(), format!("{:.0}", avg_adj)));
+        perf_rows.push(("best adj wpm".into(), format!("{:.0}", best_adj)));
+    }
+
     let con_vals: Vec<f64> = completed.iter().filter_map(|r| r.consistency).collect();
     if !con_vals.is_empty() {
         let avg_con = con_vals.iter().sum::<f64>() / con_vals.len() as f64;
         perf_rows.push(("avg con".into(), format!("{:.0}%", avg_con)));
     }
 
+    // pooled rather than averaged per test, so short tests don't swing it
+    let (bksp, keys) = completed.iter()
+        .filter_map(|r| Some((r.backspaces?, r.total_keystrokes?)))
+        .fold((0usize, 0usize), |(b, k), (rb, rk)| (b + rb, k + rk));
+    if keys > 0 {
+        perf_rows.push(("bksp/100 keys".into(), format!("{:.1}", bksp as f64 / keys as f64 * 100.0)));
+    }
+
+    let mut best_scores: Vec<(&str, f64)> = Vec::new();
+    for r in &completed {
+        let (Some(score), Some(formula)) = (r.score, r.score_formula.as_deref()) else { continue };
+        match best_scores.iter_mut().find(|(f, _)| *f == formula) {
+            Some((_, best)) => *best = best.max(score),
+            None => best_scores.push((formula, score)),
+        }
+    }
+    best_scores.sort_by(|a, b| a.0.cmp(b.0));
+    for (formula, best) in best_scores {
+        perf_rows.push((format!("best score {}", formula), format!("{:.0}", best)));
+    }
+
     sections.push(StatSection { title: "performance".into(), col_header: None, rows: perf_rows });
 
-    let (current_streak, best_streak) = compute_streaks(records);
+    let (current_streak, best_streak) = compute_streaks(records, include_practice);
     if best_streak > 0 {
         sections.push(StatSection {
             title: "streaks".into(),
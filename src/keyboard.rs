@@ -0,0 +1,228 @@
+//! which finger types which key, for the common layouts, and what that says about a test:
+//! how often consecutive keys alternated hands or landed on the same finger, and how fast
+//! each kind of pair went. also where each key sits on the board, for drawing it.
+
+use crate::config::{KeyboardGeometry, KeyboardLayout};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hand {
+    Left,
+    Right,
+}
+
+/// touch typing fingers, left pinky to right pinky. the thumbs only ever get space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Finger {
+    LeftPinky,
+    LeftRing,
+    LeftMiddle,
+    LeftIndex,
+    Thumb,
+    RightIndex,
+    RightMiddle,
+    RightRing,
+    RightPinky,
+}
+
+impl Finger {
+    pub fn hand(self) -> Option<Hand> {
+        match self {
+            Self::LeftPinky | Self::LeftRing | Self::LeftMiddle | Self::LeftIndex => Some(Hand::Left),
+            Self::Thumb => None,
+            _ => Some(Hand::Right),
+        }
+    }
+}
+
+use Finger::*;
+
+/// the finger for each column of the letter rows, and the keys right of them
+const LETTER_COLUMNS: [Finger; 13] = [
+    LeftPinky, LeftRing, LeftMiddle, LeftIndex, LeftIndex,
+    RightIndex, RightIndex, RightMiddle, RightRing, RightPinky, RightPinky, RightPinky, RightPinky,
+];
+/// the number row starts a column further left, with the backtick
+const NUMBER_COLUMNS: [Finger; 13] = [
+    LeftPinky, LeftPinky, LeftRing, LeftMiddle, LeftIndex, LeftIndex,
+    RightIndex, RightIndex, RightMiddle, RightRing, RightPinky, RightPinky, RightPinky,
+];
+
+/// each row unshifted and shifted: numbers, top, home, bottom
+type Rows = [(&'static str, &'static str); 4];
+
+const QWERTY: Rows = [
+    ("`1234567890-=", "~!@#$%^&*()_+"),
+    ("qwertyuiop[]\\", "QWERTYUIOP{}|"),
+    ("asdfghjkl;'", "ASDFGHJKL:\""),
+    ("zxcvbnm,./", "ZXCVBNM<>?"),
+];
+const DVORAK: Rows = [
+    ("`1234567890[]", "~!@#$%^&*(){}"),
+    ("',.pyfgcrl/=\\", "\"<>PYFGCRL?+|"),
+    ("aoeuidhtns-", "AOEUIDHTNS_"),
+    (";qjkxbmwvz", ":QJKXBMWVZ"),
+];
+const COLEMAK: Rows = [
+    ("`1234567890-=", "~!@#$%^&*()_+"),
+    ("qwfpgjluy;[]\\", "QWFPGJLUY:{}|"),
+    ("arstdhneio'", "ARSTDHNEIO\""),
+    ("zxcvbkm,./", "ZXCVBKM<>?"),
+];
+
+fn rows(layout: KeyboardLayout) -> &'static Rows {
+    match layout {
+        KeyboardLayout::Qwerty => &QWERTY,
+        KeyboardLayout::Dvorak => &DVORAK,
+        KeyboardLayout::Colemak => &COLEMAK,
+    }
+}
+
+/// the row and column of the key `c` is on, shifted or not
+fn key_position(layout: KeyboardLayout, c: char) -> Option<(usize, usize)> {
+    rows(layout).iter().enumerate().find_map(|(row, (plain, shifted))| {
+        let column = plain.chars().position(|k| k == c).or_else(|| shifted.chars().position(|k| k == c))?;
+        Some((row, column))
+    })
+}
+
+/// the finger that types `c` on `layout`, or None for a character that isn't on it
+pub fn finger(layout: KeyboardLayout, c: char) -> Option<Finger> {
+    if c == ' ' { return Some(Thumb); }
+    let (row, column) = key_position(layout, c)?;
+    let columns = if row == 0 { &NUMBER_COLUMNS } else { &LETTER_COLUMNS };
+    columns.get(column).copied()
+}
+
+/// how far apart keys are drawn, in cells: three for the key and one between
+pub const KEY_PITCH: u16 = 4;
+/// the space between the halves of a split board, in cells
+const SPLIT_GAP: u16 = 4;
+
+/// a key as drawn: what's on it, unshifted and shifted, its row from the number row down, and
+/// how far in from the left it starts, in cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyCap {
+    pub label: char,
+    pub shifted: char,
+    pub row: u16,
+    pub x: u16,
+}
+
+/// where each row starts, in cells. ansi and iso rows step right under tab, caps lock and
+/// shift, and iso's shorter shift leaves room for a key more. ortho rows line up in columns
+/// under the number row's second key.
+fn row_indents(geometry: KeyboardGeometry) -> [u16; 4] {
+    match geometry {
+        KeyboardGeometry::Ansi => [0, 6, 7, 9],
+        KeyboardGeometry::Iso => [0, 6, 7, 5],
+        KeyboardGeometry::Ortho | KeyboardGeometry::Split => [0, 4, 4, 4],
+    }
+}
+
+/// the keys of `layout` on a `geometry` board. iso moves the key at the end of the top row
+/// down beside left shift, and split leaves a gap where the left hand's keys end. fingers
+/// don't change with the board: they're worked out the same on every one.
+pub fn keymap(layout: KeyboardLayout, geometry: KeyboardGeometry) -> Vec<KeyCap> {
+    let mut board: Vec<Vec<(char, char)>> = rows(layout).iter().map(|(plain, shifted)| plain.chars().zip(shifted.chars()).collect()).collect();
+    if geometry == KeyboardGeometry::Iso {
+        if let Some(key) = board[1].pop() {
+            board[3].insert(0, key);
+        }
+    }
+
+    let indents = row_indents(geometry);
+    let mut keys = Vec::new();
+    for (row, row_keys) in board.iter().enumerate() {
+        let columns = if row == 0 { &NUMBER_COLUMNS } else { &LETTER_COLUMNS };
+        let left_hand = columns.iter().take_while(|f| f.hand() == Some(Hand::Left)).count();
+        let mut x = indents[row];
+        for (column, &(label, shifted)) in row_keys.iter().enumerate() {
+            if geometry == KeyboardGeometry::Split && column == left_hand {
+                x += SPLIT_GAP;
+            }
+            keys.push(KeyCap { label, shifted, row: row as u16, x });
+            x += KEY_PITCH;
+        }
+    }
+    keys
+}
+
+/// the cells `keys` take up across
+pub fn keymap_width(keys: &[KeyCap]) -> u16 {
+    keys.iter().map(|k| k.x + KEY_PITCH - 1).max().unwrap_or(0)
+}
+
+/// pairs of one kind: how many, and their average gap
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PairTally {
+    pub count: usize,
+    pub total_secs: f64,
+}
+
+impl PairTally {
+    fn add(&mut self, secs: f64) {
+        self.count += 1;
+        self.total_secs += secs;
+    }
+
+    /// the wpm this kind of pair was typed at, a key every average gap
+    pub fn wpm(&self) -> Option<f64> {
+        (self.count > 0 && self.total_secs > 0.0).then(|| 12.0 * self.count as f64 / self.total_secs)
+    }
+}
+
+/// consecutive key pairs from a test, by how the hands moved between them. pairs with space
+/// or a key off the layout in them are left out, and so are repeats of the same key.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct HandStats {
+    /// left to right or right to left
+    pub alternating: PairTally,
+    /// two fingers on the same hand
+    pub same_hand: PairTally,
+    /// two different keys on the same finger
+    pub same_finger: PairTally,
+}
+
+impl HandStats {
+    pub fn total(&self) -> usize {
+        self.alternating.count + self.same_hand.count + self.same_finger.count
+    }
+
+    /// `tally`'s share of all the pairs, in percent
+    pub fn share(&self, tally: &PairTally) -> f64 {
+        match self.total() {
+            0 => 0.0,
+            total => tally.count as f64 / total as f64 * 100.0,
+        }
+    }
+}
+
+/// goes through a test's keystroke log, pairing each key with the one typed before it. a
+/// backspace breaks the chain: the key after it wasn't typed straight after the one before.
+pub fn hand_stats(layout: KeyboardLayout, keystrokes: &[(f64, Option<char>)]) -> HandStats {
+    let mut stats = HandStats::default();
+    let mut previous: Option<(f64, char)> = None;
+    for &(at, key) in keystrokes {
+        let Some(c) = key else {
+            previous = None;
+            continue;
+        };
+        if let Some((before, p)) = previous {
+            let gap = at - before;
+            match (finger(layout, p), finger(layout, c)) {
+                (Some(a), Some(b)) if a != Thumb && b != Thumb && key_position(layout, p) != key_position(layout, c) => {
+                    if a == b {
+                        stats.same_finger.add(gap);
+                    } else if a.hand() == b.hand() {
+                        stats.same_hand.add(gap);
+                    } else {
+                        stats.alternating.add(gap);
+                    }
+                }
+                _ => {}
+            }
+        }
+        previous = Some((at, c));
+    }
+    stats
+}
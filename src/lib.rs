@@ -0,0 +1,26 @@
+pub mod app;
+pub mod backup;
+pub mod card;
+pub mod classroom;
+pub mod clock;
+pub mod config;
+pub mod demo;
+pub mod doctor;
+pub mod models;
+pub mod opponents;
+pub mod packs;
+pub mod session;
+pub mod sources;
+pub mod statusline;
+pub mod text;
+pub mod ui;
+pub mod update;
+pub mod verify;
+pub mod widget;
+pub mod history;
+pub mod keyboard;
+pub mod lock;
+pub mod utils;
+pub mod generator;
+pub mod discord;
+pub mod testing;
@@ -0,0 +1,171 @@
+//! screen lock notifications, so a test left running when the screen locks is paused
+//! instead of timing out into a garbage result. listens with `dbus-monitor` on both buses:
+//! the session bus for the screensaver interfaces desktops implement, and the system bus
+//! for logind's lock and unlock, which covers `loginctl lock-session` and most lockers
+//! that don't bother with a screensaver. logind signals every session's locking on the one
+//! bus, so only those for typa's own session count. only built in with the `lock-watch`
+//! feature, on linux. without it `LockWatch::spawn` always comes back empty.
+
+/// screensaver interfaces that announce locking with an ActiveChanged(bool) signal
+#[cfg_attr(not(all(target_os = "linux", feature = "lock-watch")), allow(dead_code))]
+const SCREENSAVERS: [&str; 5] = [
+    "org.freedesktop.ScreenSaver",
+    "org.gnome.ScreenSaver",
+    "org.cinnamon.ScreenSaver",
+    "org.mate.ScreenSaver",
+    "org.xfce.ScreenSaver",
+];
+
+/// logind's object path for session `id`, escaped the way sd-bus escapes labels: anything
+/// but a letter or digit, and a leading digit, becomes `_` and two hex digits
+pub fn session_path(id: &str) -> String {
+    let label: String = id.bytes().enumerate().map(|(i, b)| {
+        if b.is_ascii_alphabetic() || (b.is_ascii_digit() && i > 0) { (b as char).to_string() } else { format!("_{:02x}", b) }
+    }).collect();
+    format!("/org/freedesktop/login1/session/{}", if label.is_empty() { "_".to_string() } else { label })
+}
+
+/// the object path in a `dbus-send --print-reply` answer, as GetSessionByPID gives one
+pub fn reply_object_path(reply: &str) -> Option<String> {
+    let rest = reply.split("object path \"").nth(1)?;
+    Some(rest.split('"').next()?.to_string())
+}
+
+/// turns `dbus-monitor` output into lock state changes, one line at a time
+#[derive(Debug, Default)]
+pub struct SignalParser {
+    /// an ActiveChanged header came in and its bool is on the next line
+    awaiting_state: bool,
+    /// the logind session whose Lock and Unlock count. with none, neither does.
+    session: Option<String>,
+}
+
+impl SignalParser {
+    /// a parser that takes logind's Lock and Unlock for the session at `path`
+    pub fn for_session(path: &str) -> Self {
+        Self { session: Some(path.to_string()), ..Self::default() }
+    }
+
+    /// Some(true) when the screen just locked, Some(false) when it unlocked
+    pub fn feed(&mut self, line: &str) -> Option<bool> {
+        let line = line.trim();
+        if line.starts_with("signal ") {
+            let member = line.rsplit("member=").next().unwrap_or("");
+            self.awaiting_state = member == "ActiveChanged";
+            let path = line.split("path=").nth(1).and_then(|p| p.split(';').next());
+            if path.is_none() || path != self.session.as_deref() { return None; }
+            return match member {
+                "Lock" => Some(true),
+                "Unlock" => Some(false),
+                _ => None,
+            };
+        }
+        if !std::mem::take(&mut self.awaiting_state) { return None; }
+        match line {
+            "boolean true" => Some(true),
+            "boolean false" => Some(false),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "lock-watch"))]
+mod imp {
+    use super::{reply_object_path, session_path, SignalParser, SCREENSAVERS};
+    use std::io::{BufRead, BufReader};
+    use std::process::{Child, Command, Stdio};
+    use std::sync::mpsc::{self, Receiver, Sender};
+    use std::thread;
+
+    pub struct LockWatch {
+        children: Vec<Child>,
+        events: Receiver<bool>,
+    }
+
+    impl LockWatch {
+        pub fn spawn() -> Option<Self> {
+            let (tx, events) = mpsc::channel();
+            let session: Vec<String> = SCREENSAVERS
+                .iter()
+                .map(|i| format!("type='signal',interface='{}',member='ActiveChanged'", i))
+                .collect();
+            let mut children: Vec<Child> = monitor("--session", &session, SignalParser::default(), tx.clone()).into_iter().collect();
+            // without knowing which session is ours, any other user's locking would pause the test
+            if let Some(path) = own_session() {
+                let system: Vec<String> = ["Lock", "Unlock"]
+                    .iter()
+                    .map(|m| format!("type='signal',interface='org.freedesktop.login1.Session',member='{}',path='{}'", m, path))
+                    .collect();
+                children.extend(monitor("--system", &system, SignalParser::for_session(&path), tx));
+            }
+            if children.is_empty() { return None; }
+            Some(Self { children, events })
+        }
+
+        /// the latest lock state since the last poll, if it changed
+        pub fn poll(&self) -> Option<bool> {
+            self.events.try_iter().last()
+        }
+    }
+
+    impl Drop for LockWatch {
+        fn drop(&mut self) {
+            for child in &mut self.children {
+                let _ = child.kill();
+                let _ = child.wait();
+            }
+        }
+    }
+
+    /// logind's path for the session typa runs in: from XDG_SESSION_ID, or asked for by pid
+    fn own_session() -> Option<String> {
+        if let Some(id) = std::env::var("XDG_SESSION_ID").ok().filter(|id| !id.is_empty()) {
+            return Some(session_path(&id));
+        }
+        let output = Command::new("dbus-send")
+            .args(["--system", "--print-reply", "--dest=org.freedesktop.login1", "/org/freedesktop/login1"])
+            .arg("org.freedesktop.login1.Manager.GetSessionByPID")
+            .arg(format!("uint32:{}", std::process::id()))
+            .stderr(Stdio::null())
+            .output()
+            .ok()?;
+        reply_object_path(&String::from_utf8_lossy(&output.stdout))
+    }
+
+    fn monitor(bus: &str, rules: &[String], mut parser: SignalParser, tx: Sender<bool>) -> Option<Child> {
+        let mut child = Command::new("dbus-monitor")
+            .arg(bus)
+            .args(rules)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .ok()?;
+        let stdout = child.stdout.take()?;
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if let Some(locked) = parser.feed(&line) {
+                    if tx.send(locked).is_err() { break; }
+                }
+            }
+        });
+        Some(child)
+    }
+}
+
+#[cfg(not(all(target_os = "linux", feature = "lock-watch")))]
+mod imp {
+    pub struct LockWatch;
+
+    impl LockWatch {
+        pub fn spawn() -> Option<Self> {
+            None
+        }
+
+        pub fn poll(&self) -> Option<bool> {
+            None
+        }
+    }
+}
+
+pub use imp::LockWatch;
@@ -1,18 +1,13 @@
-mod app;
-mod config;
-mod models;
-mod ui;
-mod history;
-pub mod utils;
-mod generator;
-mod discord;
-
-use anyhow::Result;
-use app::App;
-use models::{Mode, QuoteLength, QuoteSelector};
+use anyhow::{Context, Result};
+use app::{App, SequenceStep, TestOptions};
+use models::{FailRules, Mode, QuoteSelector};
 use clap::builder::RangedU64ValueParser;
-use clap::{ArgAction, ArgGroup, Parser};
-use config::AppConfig;
+use clap::{ArgAction, ArgGroup, Args, Parser, Subcommand};
+use config::{AppConfig, WarmupStep};
+use demo::DemoTyper;
+use clock::SystemClock;
+use lock::LockWatch;
+use session::Session;
 use crossterm::{
     event::{
         self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers,
@@ -22,6 +17,8 @@ use crossterm::{
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io;
+use std::path::PathBuf;
+use typa::{app, backup, classroom, clock, config, demo, doctor, packs, update, discord, generator, history, lock, models, session, sources, statusline, ui};
 
 #[derive(Parser, Debug)]
 #[command(name = "typa")]
@@ -29,6 +26,7 @@ use std::io;
 #[command(about = "A rusty terminal typing test", long_about = None)]
 #[command(disable_help_flag = true)]
 #[command(disable_version_flag = true)]
+#[command(disable_help_subcommand = true)]
 #[command(help_template = "\
 {name} {version}
 {about-section}
@@ -39,9 +37,12 @@ use std::io;
 #[command(group(
     ArgGroup::new("mode")
         .required(false)
-        .args(&["time", "words", "quote"])
+        .args(&["time", "words", "quote", "man", "git", "file"])
 ))]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Time mode: Custom duration in seconds (e.g. 15, 60, 120, 3600)
     #[arg(short, long, value_parser = RangedU64ValueParser::<u64>::new().range(1..))]
     time: Option<u64>,
@@ -54,9 +55,25 @@ struct Cli {
     #[arg(short, long)]
     quote: Option<String>,
 
-    /// Language: Filename to use (e.g. "english", "indonesian")
-    #[arg(short, long, default_value = "english")]
-    language: String,
+    /// Quote mode: Number of quotes to chain into one test (1 to 20)
+    #[arg(short, long, requires = "quote", default_value_t = 1, value_parser = RangedU64ValueParser::<u64>::new().range(1..=20))]
+    count: u64,
+
+    /// Type the description from an installed man page (e.g. "tar", "ssh_config")
+    #[arg(long, value_name = "PAGE")]
+    man: Option<String>,
+
+    /// Type the text in a file, or "-" to read it from stdin
+    #[arg(long, value_name = "PATH")]
+    file: Option<String>,
+
+    /// Language: Filename to use (e.g. "english", "indonesian") [default: english, or detected from --file, --man or --git text]
+    #[arg(short, long)]
+    language: Option<String>,
+
+    /// Type recent commit messages from the git repository you're in
+    #[arg(long, default_value_t = false, help_heading = "Flags")]
+    git: bool,
 
     /// Include numbers in the test
     #[arg(short, long, default_value_t = false, help_heading = "Flags")]
@@ -66,10 +83,77 @@ struct Cli {
     #[arg(short, long, default_value_t = false, help_heading = "Flags")]
     punctuation: bool,
 
+    /// Type sentences built from the word list's templates instead of random words
+    #[arg(long, default_value_t = false, help_heading = "Flags")]
+    sentences: bool,
+
+    /// Allow quotes to repeat before the whole pool has been shown
+    #[arg(long, default_value_t = false, help_heading = "Flags")]
+    allow_repeats: bool,
+
+    /// Race a bot that types at a steady WPM
+    #[arg(long, value_name = "WPM", value_parser = RangedU64ValueParser::<u64>::new().range(1..=500))]
+    pace: Option<u64>,
+
+    /// Race the pace bot at the speed saved by `typa calibrate`
+    #[arg(long, default_value_t = false, conflicts_with = "pace", help_heading = "Flags")]
+    pace_calibrated: bool,
+
+    /// Fail the test on the first incorrect keystroke
+    #[arg(long, default_value_t = false, help_heading = "Flags")]
+    sudden_death: bool,
+
+    /// Fail the test when an incorrect word is submitted
+    #[arg(long, default_value_t = false, help_heading = "Flags")]
+    expert: bool,
+
+    /// Count a space pressed mid-word as a mistake instead of moving to the next word
+    #[arg(long, default_value_t = false, help_heading = "Flags")]
+    strict_space: bool,
+
+    /// A simple tutor for children: short easy words, a cheer for each one and results in stars
+    #[arg(long, default_value_t = false, help_heading = "Flags")]
+    kids: bool,
+
+    /// Hide the words ahead, so each one is typed from memory (see [dictation] in the config)
+    #[arg(long, default_value_t = false, help_heading = "Flags")]
+    dictation: bool,
+
+    /// Fail the test if net WPM drops below this after the first few seconds
+    #[arg(long, value_name = "WPM", value_parser = RangedU64ValueParser::<u64>::new().range(1..))]
+    min_wpm: Option<u64>,
+
+    /// Fail the test below the --min-wpm floor saved by `typa calibrate`
+    #[arg(long, default_value_t = false, conflicts_with = "min_wpm", help_heading = "Flags")]
+    min_wpm_calibrated: bool,
+
+    /// Fail the test if it ends with accuracy below this percentage
+    #[arg(long, value_name = "PCT", value_parser = RangedU64ValueParser::<u64>::new().range(1..=100))]
+    min_accuracy: Option<u64>,
+
+    /// Classroom mode: the fixed test from [classroom] in the config, each result added to a CSV for the teacher
+    #[arg(long, default_value_t = false, requires = "student", help_heading = "Flags", conflicts_with_all = [
+        "time", "words", "quote", "man", "git", "file", "language", "numbers", "punctuation", "sentences", "allow_repeats",
+        "pace", "sudden_death", "expert", "strict_space", "kids", "dictation", "min_wpm", "min_accuracy", "practice",
+    ])]
+    classroom: bool,
+
+    /// With --classroom: the name to record results under
+    #[arg(long, value_name = "NAME", requires = "classroom")]
+    student: Option<String>,
+
+    /// Save the result as practice: kept in history but left out of bests, averages and streaks
+    #[arg(long, default_value_t = false, help_heading = "Flags")]
+    practice: bool,
+
     /// Show interactive typing stats and history
     #[arg(long, default_value_t = false, help_heading = "Flags")]
     stats: bool,
 
+    /// With --stats: count practice tests in averages, bests and streaks
+    #[arg(long, default_value_t = false, requires = "stats", help_heading = "Flags")]
+    include_practice: bool,
+
     /// Delete all saved history (will prompt for confirmation)
     #[arg(long, default_value_t = false, help_heading = "Flags")]
     clear_history: bool,
@@ -83,19 +167,268 @@ struct Cli {
     version: Option<bool>,
 }
 
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print a generated word stream to stdout without starting a test
+    Generate(GenerateArgs),
+    /// Run the warmup sequence from config and show a combined summary
+    Warmup,
+    /// Play a script of keystrokes into a test, for recording demos or reproducing bugs
+    Demo(DemoArgs),
+    /// Take three short tests and save a pace bot speed and a --min-wpm floor from them
+    Calibrate,
+    /// Two players take turns at the same test, then see their results side by side
+    Hotseat(HotseatArgs),
+    /// Print the last result, today's test count and the streak for a status bar
+    Status(StatusArgs),
+    /// Print lifetime totals from history in the Prometheus text format
+    Metrics,
+    /// List the languages typa has, or download more
+    Languages {
+        #[command(subcommand)]
+        action: LanguagesAction,
+    },
+    /// Collect the results of students' --classroom tests on this machine
+    Classroom {
+        #[command(subcommand)]
+        action: ClassroomAction,
+    },
+    /// Look back over past tests
+    History {
+        #[command(subcommand)]
+        action: HistoryAction,
+    },
+    /// Check config, history and the other files typa keeps for problems
+    Doctor(DoctorArgs),
+    /// Bundle config, history and saved cards into one tar file
+    Backup(BackupArgs),
+    /// Put the files from a backup made with `typa backup` back in place
+    Restore(RestoreArgs),
+    /// Measure how long this terminal takes to show a frame after a keypress
+    Latency(LatencyArgs),
+    /// Time parts of typa itself
+    Bench {
+        #[command(subcommand)]
+        target: BenchTarget,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum BenchTarget {
+    /// Time generating word streams with and without punctuation and numbers
+    Generator(BenchGeneratorArgs),
+}
+
+#[derive(Args, Debug)]
+struct GenerateArgs {
+    /// Number of words to generate (1 to 10000)
+    #[arg(short, long, default_value_t = 50, value_parser = RangedU64ValueParser::<u64>::new().range(1..=10000))]
+    count: u64,
+
+    /// Language: Filename to use (e.g. "english", "indonesian")
+    #[arg(short, long, default_value = "english")]
+    language: String,
+
+    /// Include numbers in the stream
+    #[arg(short, long, default_value_t = false)]
+    numbers: bool,
+
+    /// Include punctuation in the stream
+    #[arg(short, long, default_value_t = false)]
+    punctuation: bool,
+
+    /// Build the stream from the word list's sentence templates
+    #[arg(long, default_value_t = false)]
+    sentences: bool,
+
+    /// Seed for reproducible output
+    #[arg(short, long)]
+    seed: Option<u64>,
+}
+
+#[derive(Args, Debug)]
+struct HotseatArgs {
+    /// Player names, in turn order [default: "player 1" and "player 2"]
+    #[arg(long = "player", value_name = "NAME", num_args = 1, action = ArgAction::Append)]
+    players: Vec<String>,
+}
+
+#[derive(Args, Debug)]
+struct DemoArgs {
+    /// The script to play: one step a line, see the README for the steps
+    #[arg(long, value_name = "FILE")]
+    script: PathBuf,
+}
+
+#[derive(Args, Debug)]
+struct StatusArgs {
+    /// Print JSON for a waybar custom module instead of i3blocks lines
+    #[arg(long, default_value_t = false)]
+    json: bool,
+}
+
+#[derive(Subcommand, Debug)]
+enum LanguagesAction {
+    /// List built-in and downloaded languages
+    List,
+    /// Download a language pack from the pack index into the pack directory
+    Fetch(FetchArgs),
+}
+
+#[derive(Args, Debug)]
+struct FetchArgs {
+    /// Name of the pack in the index, e.g. "german"
+    name: String,
+
+    /// Pack index URL [default: `index` under [languages] in config]
+    #[arg(long, value_name = "URL")]
+    index: Option<String>,
+}
+
+#[derive(Subcommand, Debug)]
+enum HistoryAction {
+    /// Compare two past tests side by side, by the numbers --stats shows them under
+    Diff(DiffArgs),
+    /// Check a past test's verification token against the numbers it was worked out from
+    Verify(VerifyArgs),
+}
+
+#[derive(Args, Debug)]
+struct VerifyArgs {
+    /// The test's number, 1 for the oldest
+    n: usize,
+}
+
+#[derive(Args, Debug)]
+struct DiffArgs {
+    /// The first test's number, 1 for the oldest
+    a: usize,
+    /// The test to compare it with
+    b: usize,
+}
+
+#[derive(Subcommand, Debug)]
+enum ClassroomAction {
+    /// Take results from students on the local network and show them as they come in
+    Serve(ServeArgs),
+}
+
+#[derive(Args, Debug)]
+struct ServeArgs {
+    /// Port to listen on
+    #[arg(short, long, default_value_t = classroom::DEFAULT_PORT)]
+    port: u16,
+}
+
+#[derive(Args, Debug)]
+struct DoctorArgs {
+    /// Carry out the fixes that don't need a decision, like setting aside unreadable history lines
+    #[arg(long, default_value_t = false)]
+    fix: bool,
+}
+
+#[derive(Args, Debug)]
+struct BackupArgs {
+    /// Where to write the archive, e.g. typa-backup.tar
+    path: PathBuf,
+}
+
+#[derive(Args, Debug)]
+struct RestoreArgs {
+    /// The archive to restore from
+    path: PathBuf,
+
+    /// Replace files that already exist on this machine
+    #[arg(long, default_value_t = false)]
+    force: bool,
+}
+
+#[derive(Args, Debug)]
+struct LatencyArgs {
+    /// Number of keypresses to time (5 to 200)
+    #[arg(short, long, default_value_t = 30, value_parser = RangedU64ValueParser::<u64>::new().range(5..=200))]
+    rounds: u64,
+}
+
+#[derive(Args, Debug)]
+struct BenchGeneratorArgs {
+    /// Thousands of words to generate per run (1 to 1000)
+    #[arg(short = 'k', long, default_value_t = 10, value_parser = RangedU64ValueParser::<u64>::new().range(1..=1000))]
+    thousands: u64,
+
+    /// Language to benchmark; repeat for several [default: every embedded language]
+    #[arg(short, long)]
+    language: Vec<String>,
+
+    /// Seed, so runs are comparable across builds
+    #[arg(short, long, default_value_t = 0)]
+    seed: u64,
+}
+
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
-    let app_config = AppConfig::load().unwrap_or_else(|e| {
+    let (warmup, latency, hotseat, calibrate, script) = match cli.command {
+        Some(Command::Generate(args)) => return run_generate(args),
+        Some(Command::Bench { target: BenchTarget::Generator(args) }) => return run_bench_generator(args),
+        Some(Command::Status(args)) => return run_status(args),
+        Some(Command::Metrics) => {
+            print!("{}", history::metrics::prometheus(&history::load_history()?));
+            return Ok(());
+        }
+        Some(Command::Languages { action }) => return run_languages(action),
+        Some(Command::Doctor(args)) => return run_doctor(args),
+        Some(Command::History { action: HistoryAction::Diff(args) }) => {
+            let app_config = AppConfig::load()?;
+            return history::diff(app_config.theme, app_config.quotas, args.a, args.b);
+        }
+        Some(Command::History { action: HistoryAction::Verify(args) }) => return history::verify(args.n),
+        Some(Command::Classroom { action: ClassroomAction::Serve(args) }) => return run_classroom_serve(args),
+        Some(Command::Backup(args)) => {
+            let count = backup::backup(&args.path)?;
+            println!("backed up {} file(s) to {}", count, args.path.display());
+            return Ok(());
+        }
+        Some(Command::Restore(args)) => {
+            let count = backup::restore(&args.path, args.force)?;
+            println!("restored {} file(s) from {}", count, args.path.display());
+            return Ok(());
+        }
+        Some(Command::Warmup) => (true, None, None, false, None),
+        Some(Command::Latency(args)) => (false, Some(args), None, false, None),
+        Some(Command::Hotseat(args)) => (false, None, Some(args), false, None),
+        Some(Command::Calibrate) => (false, None, None, true, None),
+        Some(Command::Demo(args)) => {
+            let text = std::fs::read_to_string(&args.script).with_context(|| format!("couldn't read {}", args.script.display()))?;
+            (false, None, None, false, Some(demo::parse_script(&text)?))
+        }
+        None => (false, None, None, false, None),
+    };
+
+    let mut app_config = AppConfig::load().unwrap_or_else(|e| {
         eprintln!(
             "Warning: Failed to load config, using defaults. Error: {}",
             e
         );
-        AppConfig {
-            theme: config::Theme::default(),
-        }
+        AppConfig::default()
     });
 
+    if let Some(min) = app_config.accessibility.min_contrast {
+        let adjust = app_config.accessibility.adjust_contrast;
+        let mut notes = app_config.theme.enforce_contrast(min, adjust);
+        notes.extend(app_config.accents.enforce_contrast(&app_config.theme, min, adjust));
+        for note in notes {
+            eprintln!("Warning: {}", note);
+        }
+    }
+
+    if warmup {
+        return end_session(run_warmup(app_config));
+    }
+    if let Some(args) = latency {
+        return run_latency(args, app_config.theme);
+    }
+
     if cli.clear_history {
         use std::io::{BufRead, Write};
         print!("  delete all history? this cannot be undone. [y/N] ");
@@ -112,20 +445,22 @@ fn main() -> Result<()> {
     }
 
     if cli.stats {
-        let mut dp = crate::discord::DiscordPresence::new();
+        let mut dp = discord::DiscordPresence::new();
         if dp.connected {
             if let Ok(records) = history::load_history() {
-                use crate::history::stats::compute_streaks;
-                let completed: Vec<_> = records.iter().filter(|r| r.completed).collect();
+                use history::stats::compute_streaks;
+                let completed: Vec<_> = records.iter()
+                    .filter(|r| r.completed && (cli.include_practice || !r.practice))
+                    .collect();
                 let best_wpm = completed.iter()
                     .filter_map(|r| r.wpm)
                     .fold(0.0_f64, f64::max);
                 let total_tests = records.len();
-                let (current_streak, _) = compute_streaks(&records);
+                let (current_streak, _) = compute_streaks(&records, cli.include_practice);
                 dp.set_stats(best_wpm, total_tests, current_streak);
             }
         }
-        history::run(app_config.theme)?;
+        history::run(app_config.theme, app_config.quotas, cli.include_practice)?;
         return Ok(());
     }
 
@@ -135,37 +470,95 @@ fn main() -> Result<()> {
         let count = w as usize;
         Mode::Words(count)
     } else if let Some(q_str) = cli.quote {
-        if let Ok(id) = q_str.parse::<usize>() {
-            Mode::Quote(QuoteSelector::Id(id))
-        } else {
-            let category = match q_str.to_lowercase().as_str() {
-                "short" => QuoteLength::Short,
-                "medium" => QuoteLength::Medium,
-                "long" => QuoteLength::Long,
-                "very_long" | "verylong" => QuoteLength::VeryLong,
-                _ => QuoteLength::All,
-            };
-            Mode::Quote(QuoteSelector::Category(category))
-        }
+        Mode::Quote(QuoteSelector::parse(&q_str))
+    } else if cli.kids {
+        Mode::Words(10)
+    } else if calibrate {
+        Mode::Time(15)
     } else {
         Mode::Time(60)
     };
 
+    let custom_text = if let Some(page) = &cli.man {
+        Some(sources::man_page(page)?)
+    } else if cli.git {
+        Some(sources::git_log()?)
+    } else if let Some(path) = &cli.file {
+        Some(sources::file_text(path)?)
+    } else {
+        None
+    };
+    let language = match (cli.language, &custom_text) {
+        (Some(language), _) => language,
+        (None, Some(custom)) => detected_language(&custom.text),
+        (None, None) => "english".to_string(),
+    };
+
+    let options = TestOptions {
+        mode: initial_mode,
+        language,
+        use_numbers: cli.numbers,
+        use_punctuation: cli.punctuation,
+        allow_repeats: cli.allow_repeats,
+        quote_count: cli.count as usize,
+        practice: cli.practice,
+        pace_wpm: calibrated(cli.pace, cli.pace_calibrated, app_config.pace.wpm, "--pace-calibrated")?,
+        fail_rules: FailRules {
+            sudden_death: cli.sudden_death,
+            expert: cli.expert,
+            min_wpm: calibrated(cli.min_wpm, cli.min_wpm_calibrated, app_config.pace.min_wpm, "--min-wpm-calibrated")?,
+            min_accuracy: cli.min_accuracy.map(|p| p as f64),
+        },
+        custom_text,
+        sentences: cli.sentences,
+        strict_space: cli.strict_space,
+        kids: cli.kids,
+        dictation: cli.dictation,
+        classroom: None,
+    };
+    let options = match cli.student {
+        Some(student) if cli.classroom => classroom_preset(&mut app_config, student)?,
+        _ => options,
+    };
+    if let Some(args) = hotseat {
+        return end_session(run_hotseat(options, app_config, args));
+    }
+    if calibrate {
+        return end_session(run_calibrate(options, app_config));
+    }
+    if let Some(script) = script {
+        return end_session(run_script(options, app_config, script));
+    }
+    let updates = app_config.updates.clone();
+    let mut app = App::new(options, app_config)?;
+    if updates.check {
+        app.update_check = update::UpdateCheck::start(&updates.feed);
+    }
+
+    let res = with_terminal(|terminal| run_app(terminal, &mut app, false));
+    end_session(res.map(|_| app.session))
+}
+
+/// how every run of tests ends, once the terminal is handed back: the tests finished in it
+/// summed up
+fn end_session(res: Result<Session>) -> Result<()> {
+    if let Some(summary) = res?.summary() {
+        println!("  {}", summary);
+    }
+    Ok(())
+}
+
+type Term = Terminal<CrosstermBackend<io::Stdout>>;
+
+/// takes the terminal over for the tui, runs `f`, and hands it back whether or not `f` worked
+fn with_terminal<T>(f: impl FnOnce(&mut Term) -> Result<T>) -> Result<T> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let mut app = App::new(
-        initial_mode,
-        cli.language,
-        cli.numbers,
-        cli.punctuation,
-        app_config.theme,
-    )?;
-
-    let res = run_app(&mut terminal, &mut app);
+    let res = f(&mut terminal);
 
     disable_raw_mode()?;
     execute!(
@@ -175,14 +568,469 @@ fn main() -> Result<()> {
     )?;
     terminal.show_cursor()?;
 
-    if let Err(err) = res {
-        println!("Error: {:?}", err);
+    res
+}
+
+fn run_warmup(app_config: AppConfig) -> Result<Session> {
+    let warmup = app_config.warmup.clone();
+    if warmup.steps.is_empty() {
+        anyhow::bail!("no warmup steps configured. add some under [warmup] in config.toml");
+    }
+    let total = warmup.steps.len();
+    let theme = app_config.theme.clone();
+
+    let mut session = Session::default();
+    with_terminal(|terminal| {
+        let mut done = Vec::new();
+        for (index, step) in warmup.steps.iter().enumerate() {
+            let mut app = App::new(warmup_options(step, warmup.practice), app_config.clone())?;
+            app.sequence = Some(SequenceStep { name: "warmup".to_string(), index, total, fixed_words: false });
+            app.session = std::mem::take(&mut session);
+
+            let exit = run_app(terminal, &mut app, true);
+            session = std::mem::take(&mut app.session);
+            if app.test.state == models::AppState::Finished {
+                done.push(app.step_summary());
+            }
+            if exit? == LoopExit::Quit { break; }
+        }
+
+        if done.is_empty() {
+            return Ok(());
+        }
+        loop {
+            terminal.draw(|f| ui::summary::draw(f, &theme, "warmup", &done, total))?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press { return Ok(()); }
+            }
+        }
+    })?;
+    Ok(session)
+}
+
+/// plays `script` into a test that renders as usual. nothing typed is saved, like the idle
+/// demo. once the script has run out, or a real key is pressed, the keyboard is yours again.
+fn run_script(options: TestOptions, app_config: AppConfig, script: demo::Script) -> Result<Session> {
+    let mut app = App::new(options, app_config)?;
+    app.start_demo();
+    if let Some(words) = script.words.clone() {
+        app.last_test_words = Some(words);
+        app.retry_last_test();
+    }
+    with_terminal(|terminal| {
+        let player = demo::ScriptPlayer::new(&script, app.now());
+        run_app_with(terminal, &mut app, false, Some(player))
+    })?;
+    Ok(app.session)
+}
+
+/// a speed: the one given, or the calibrated one when its `-calibrated` flag is set
+fn calibrated(given: Option<u64>, use_saved: bool, saved: Option<u64>, flag: &str) -> Result<Option<f64>> {
+    if !use_saved {
+        return Ok(given.map(|wpm| wpm as f64));
+    }
+    match saved {
+        Some(wpm) => Ok(Some(wpm as f64)),
+        None => anyhow::bail!("{} needs a saved speed. run `typa calibrate` to save yours, or give one yourself", flag),
     }
+}
+
+/// three short tests at a comfortable pace, then the pace bot speed and a --min-wpm floor
+/// worked out from them go into config.toml. the tests are practice: they're for measuring,
+/// not for bests, and nothing races or fails them.
+fn run_calibrate(options: TestOptions, app_config: AppConfig) -> Result<Session> {
+    const TESTS: usize = 3;
+    let options = TestOptions { practice: true, pace_wpm: None, fail_rules: FailRules::default(), ..options };
+
+    let mut session = Session::default();
+    let done = with_terminal(|terminal| {
+        let mut done = Vec::new();
+        for index in 0..TESTS {
+            let mut app = App::new(options.clone(), app_config.clone())?;
+            app.sequence = Some(SequenceStep { name: "calibration".to_string(), index, total: TESTS, fixed_words: false });
+            app.session = std::mem::take(&mut session);
+
+            let exit = run_app(terminal, &mut app, true);
+            session = std::mem::take(&mut app.session);
+            if app.test.state == models::AppState::Finished {
+                done.push(app.step_summary());
+            }
+            if exit? == LoopExit::Quit { break; }
+        }
+        Ok(done)
+    })?;
+
+    let Some((pace, min_wpm)) = app::calibrated_speeds(&done) else {
+        println!("calibration needs at least two finished tests, nothing saved.");
+        return Ok(session);
+    };
+    let path = config::set_in_file("pace", &[("wpm", pace.to_string()), ("min_wpm", min_wpm.to_string())])?;
+    let wpms: Vec<String> = done.iter().filter(|r| !r.failed).map(|r| format!("{:.0}", r.wpm)).collect();
+    println!("  tests        {} wpm", wpms.join(", "));
+    println!("  comfortable  {} wpm", pace);
+    println!("  saved to {}:", path.display());
+    println!("    --pace-calibrated     races a bot at {} wpm", pace);
+    println!("    --min-wpm-calibrated  fails a test below {} wpm", min_wpm);
+    Ok(session)
+}
+
+/// each player takes the test in turn, on the same words, then both results
+/// go up side by side. it's a game, so none of it counts towards anyone's bests.
+fn run_hotseat(options: TestOptions, app_config: AppConfig, args: HotseatArgs) -> Result<Session> {
+    let mut players = args.players;
+    if players.len() > 2 {
+        anyhow::bail!("hotseat is for two players, got {}", players.len());
+    }
+    for n in players.len()..2 {
+        players.push(format!("player {}", n + 1));
+    }
+    let theme = app_config.theme.clone();
+    let options = TestOptions { practice: true, ..options };
+
+    // a timed test has no end to its words, so both players get a list made up front
+    let mut words = match options.mode {
+        Mode::Time(secs) => Some(hotseat_time_words(&options, &app_config, secs)?),
+        _ => None,
+    };
+    let mut session = Session::default();
+    with_terminal(|terminal| {
+        let mut done = Vec::new();
+        for (index, name) in players.iter().enumerate() {
+            let mut app = App::new(options.clone(), app_config.clone())?;
+            app.sequence = Some(SequenceStep { name: name.clone(), index, total: players.len(), fixed_words: words.is_some() });
+            app.session = std::mem::take(&mut session);
+            if let Some(words) = &words {
+                app.last_test_words = Some(words.clone());
+                app.retry_last_test();
+            }
+
+            let exit = run_app(terminal, &mut app, true);
+            session = std::mem::take(&mut app.session);
+            let exit = exit?;
+            if app.test.state != models::AppState::Finished { return Ok(()); }
+            words.get_or_insert_with(|| app.test.words());
+            done.push(app.step_summary());
+            if exit == LoopExit::Quit { break; }
+        }
+
+        if done.len() < players.len() {
+            return Ok(());
+        }
+        loop {
+            terminal.draw(|f| ui::hotseat::draw(f, &theme, &players, &done))?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press { return Ok(()); }
+            }
+        }
+    })?;
+    Ok(session)
+}
+
+/// more words than anyone types in `secs`, with room for the words a running test keeps
+/// ahead of the caret, so it never has to make more of its own
+fn hotseat_time_words(options: &TestOptions, app_config: &AppConfig, secs: u64) -> Result<Vec<String>> {
+    const FASTEST_WPM: u64 = 300;
+    const LOOKAHEAD: usize = 100;
+    let app = App::detached(options.clone(), app_config.clone(), Box::new(SystemClock))?;
+    Ok(app.generate_words((secs * FASTEST_WPM / 60) as usize + LOOKAHEAD))
+}
+
+/// times each keypress from the moment its event arrives until the terminal has drawn
+/// the next frame. "drawn" is when the terminal answers a cursor position query sent
+/// after the frame: it handles its input in order, so by then it has taken the frame in.
+fn run_latency(args: LatencyArgs, theme: config::Theme) -> Result<()> {
+    use rand::Rng;
+    use std::time::{Duration, Instant};
+
+    let rounds = args.rounds as usize;
+    let mut rng = rand::rng();
+    let mut next_prompt = || (b'a' + rng.random_range(0..26u8)) as char;
+
+    let samples = with_terminal(|terminal| {
+        // (render only, render plus terminal) per keypress. a terminal that never answers
+        // the position query only gets the first half measured.
+        let mut samples: Vec<(Duration, Option<Duration>)> = Vec::with_capacity(rounds);
+        let mut answers = true;
+        let mut prompt = next_prompt();
+        terminal.draw(|f| ui::latency::draw(f, &theme, prompt, 0, rounds, None))?;
+
+        while samples.len() < rounds {
+            let Event::Key(key) = event::read()? else { continue };
+            let received = Instant::now();
+            if key.kind != KeyEventKind::Press { continue; }
+            match key.code {
+                KeyCode::Esc => break,
+                KeyCode::Char(c) if c == prompt => {
+                    prompt = next_prompt();
+                    let last_ms = samples.last().map(|s: &(Duration, Option<Duration>)| s.1.unwrap_or(s.0).as_secs_f64() * 1000.0);
+                    terminal.draw(|f| ui::latency::draw(f, &theme, prompt, samples.len() + 1, rounds, last_ms))?;
+                    let rendered = received.elapsed();
+                    answers = answers && crossterm::cursor::position().is_ok();
+                    samples.push((rendered, answers.then(|| received.elapsed())));
+                }
+                _ => {}
+            }
+        }
+        Ok(samples)
+    })?;
 
+    if samples.is_empty() {
+        println!("no keypresses timed.");
+        return Ok(());
+    }
+
+    let summarize = |mut ms: Vec<f64>| {
+        ms.sort_by(|a, b| a.total_cmp(b));
+        let at = |q: f64| ms[((ms.len() - 1) as f64 * q).round() as usize];
+        format!("min {:>6.2}  median {:>6.2}  p95 {:>6.2}  max {:>6.2}", ms[0], at(0.5), at(0.95), ms[ms.len() - 1])
+    };
+    let to_ms = |d: Duration| d.as_secs_f64() * 1000.0;
+
+    println!("keypress to frame, over {} keypresses (ms)", samples.len());
+    println!("  typa render      {}", summarize(samples.iter().map(|s| to_ms(s.0)).collect()));
+    let shown: Vec<f64> = samples.iter().filter_map(|s| s.1.map(to_ms)).collect();
+    if shown.len() == samples.len() {
+        println!("  with terminal    {}", summarize(shown));
+    } else {
+        println!("  with terminal    unavailable, the terminal didn't answer cursor position queries");
+    }
+    if let Ok(term) = std::env::var("TERM_PROGRAM").or_else(|_| std::env::var("TERM")) {
+        println!("  terminal         {}", term);
+    }
+    Ok(())
+}
+
+/// the teacher's side of classroom mode: a live table of everyone's results, each one also
+/// added to the classroom csv
+fn run_classroom_serve(args: ServeArgs) -> Result<()> {
+    let app_config = AppConfig::load()?;
+    let submissions = classroom::serve(&format!("0.0.0.0:{}", args.port))?;
+    let csv = classroom::results_path(app_config.classroom.csv.as_deref());
+    let address = classroom::lan_address().map(|ip| std::net::SocketAddr::new(ip, args.port).to_string());
+    let mut roster = classroom::Roster::default();
+    let mut scroll = 0;
+
+    with_terminal(|terminal| loop {
+        for submission in submissions.try_iter() {
+            if let Some(path) = &csv {
+                let _ = classroom::append(path, &submission.row());
+            }
+            roster.add(submission);
+        }
+        scroll = scroll.min(roster.students.len().saturating_sub(1));
+        terminal.draw(|f| ui::classroom::draw(f, &app_config.theme, &roster, address.as_deref(), scroll))?;
+
+        if !event::poll(std::time::Duration::from_millis(200))? { continue; }
+        let Event::Key(key) = event::read()? else { continue };
+        if key.kind != KeyEventKind::Press { continue; }
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return Ok(()),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Ok(()),
+            KeyCode::Up => scroll = scroll.saturating_sub(1),
+            KeyCode::Down => scroll += 1,
+            _ => {}
+        }
+    })
+}
+
+/// --classroom: the test from `[classroom]` in config, whatever else was asked for
+fn classroom_preset(app_config: &mut AppConfig, student: String) -> Result<TestOptions> {
+    let room = app_config.classroom.clone();
+    // nothing that would make one student's test different from the next one's
+    app_config.words.sentences = false;
+    app_config.effects.confetti = false;
+    let path = classroom::results_path(room.csv.as_deref()).context("no data directory to keep classroom results in")?;
+    Ok(TestOptions {
+        mode: Mode::Time(room.time),
+        language: room.language,
+        use_numbers: room.numbers,
+        use_punctuation: room.punctuation,
+        fail_rules: FailRules { min_accuracy: Some(room.min_accuracy), ..FailRules::default() },
+        classroom: Some(classroom::Export { student, path, server: room.server }),
+        ..TestOptions::default()
+    })
+}
+
+// same precedence as the command line: time, then words, then quote
+fn warmup_options(step: &WarmupStep, practice: bool) -> TestOptions {
+    let mode = if let Some(t) = step.time {
+        Mode::Time(t)
+    } else if let Some(w) = step.words {
+        Mode::Words(w)
+    } else if let Some(q) = &step.quote {
+        Mode::Quote(QuoteSelector::parse(q))
+    } else {
+        Mode::Time(15)
+    };
+    TestOptions {
+        mode,
+        language: step.language.clone().unwrap_or_else(|| "english".to_string()),
+        use_numbers: step.numbers,
+        use_punctuation: step.punctuation,
+        practice,
+        ..TestOptions::default()
+    }
+}
+
+/// one line for a status bar to poll. reads history and nothing else, so it's quick.
+fn run_status(args: StatusArgs) -> Result<()> {
+    let records = history::load_history()?;
+    let summary = statusline::StatusSummary::from_history(&records, chrono::Local::now().date_naive());
+    println!("{}", if args.json { summary.waybar() } else { summary.i3blocks() });
     Ok(())
 }
 
-fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut App) -> Result<()> {
+/// the language custom text is written in, going by a word list for each language there is,
+/// or english when it's not clear
+fn detected_language(text: &str) -> String {
+    let languages: Vec<(String, Vec<String>)> = sources::one_per_language(app::all_languages())
+        .into_iter()
+        .filter_map(|name| app::load_word_data(&name).ok().map(|data| (name, data.words)))
+        .collect();
+    sources::detect_language(text, &languages).unwrap_or("english").to_string()
+}
+
+fn run_languages(action: LanguagesAction) -> Result<()> {
+    match action {
+        LanguagesAction::List => {
+            let downloaded = packs::installed();
+            for name in app::all_languages() {
+                let from_pack = downloaded.contains(&name);
+                let built_in = app::languages().contains(&name);
+                let note = match (built_in, from_pack) {
+                    (true, true) => "  (downloaded, replaces the built-in one)",
+                    (false, true) => "  (downloaded)",
+                    _ => "",
+                };
+                println!("{}{}", name, note);
+            }
+        }
+        LanguagesAction::Fetch(args) => {
+            let index = match args.index {
+                Some(index) => index,
+                None => AppConfig::load()?.languages.index
+                    .context("no pack index set. pass --index <URL>, or set `index` under [languages] in config.toml")?,
+            };
+            for path in packs::fetch(&index, &args.name)? {
+                println!("saved {}", path.display());
+            }
+            println!("try it with `typa -l {}`", args.name);
+        }
+    }
+    Ok(())
+}
+
+fn run_doctor(args: DoctorArgs) -> Result<()> {
+    let findings = doctor::run();
+    print!("{}", doctor::report(&findings));
+    if args.fix {
+        for done in doctor::repair(&findings)? {
+            println!("fixed: {}", done);
+        }
+    } else if findings.iter().any(|f| f.repair.is_some()) {
+        println!("\nrun `typa doctor --fix` to fix what can be fixed automatically");
+    }
+    // a problem --fix just took care of doesn't count
+    if findings.iter().any(|f| f.level == doctor::Level::Problem && !(args.fix && f.repair.is_some())) {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn run_generate(args: GenerateArgs) -> Result<()> {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::io::Write;
+
+    let word_data = app::load_word_data(&args.language)?;
+    let mut generator = generator::WordGenerator::new(word_data, args.numbers, args.punctuation);
+    if args.sentences {
+        generator = generator.with_sentences()?;
+    }
+
+    let mut rng = match args.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_os_rng(),
+    };
+    let words = generator.generate_stream(args.count as usize, &mut rng);
+
+    writeln!(io::stdout().lock(), "{}", words.join(" "))?;
+    Ok(())
+}
+
+/// one timed `generate_stream` per language and punctuation/numbers combination
+fn run_bench_generator(args: BenchGeneratorArgs) -> Result<()> {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::time::Instant;
+
+    let count = args.thousands as usize * 1000;
+    let languages = if args.language.is_empty() { app::languages() } else { args.language };
+    let word_data = languages.iter().map(|l| app::load_word_data(l)).collect::<Result<Vec<_>>>()?;
+    let variants = [("plain", false, false), ("punctuation", false, true), ("numbers", true, false), ("both", true, true)];
+
+    println!("{} words per run, seed {}", count, args.seed);
+    println!("{:<12} {:<12} {:>10} {:>12} {:>12}", "language", "variant", "ms", "words/s", "chars/s");
+    for (language, word_data) in languages.iter().zip(&word_data) {
+        for (name, numbers, punctuation) in variants {
+            let generator = generator::WordGenerator::new(word_data.clone(), numbers, punctuation);
+            let mut rng = StdRng::seed_from_u64(args.seed);
+
+            let start = Instant::now();
+            let words = generator.generate_stream(count, &mut rng);
+            let secs = start.elapsed().as_secs_f64();
+
+            let chars: usize = words.iter().map(|w| w.chars().count() + 1).sum();
+            println!(
+                "{:<12} {:<12} {:>10.1} {:>12.0} {:>12.0}",
+                language, name, secs * 1000.0, words.len() as f64 / secs, chars as f64 / secs
+            );
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, PartialEq)]
+enum LoopExit {
+    Quit,
+    /// tab on the results screen of a test that's part of a sequence
+    Next,
+}
+
+fn run_app(terminal: &mut Term, app: &mut App, in_sequence: bool) -> Result<LoopExit> {
+    run_app_with(terminal, app, in_sequence, None)
+}
+
+/// the next event to handle: a key from the script when one's due, otherwise whatever the
+/// terminal sends within `frame`. a real keypress ends the script.
+fn next_event(script: &mut Option<demo::ScriptPlayer>, now: std::time::Instant, frame: std::time::Duration) -> Result<Option<Event>> {
+    use crossterm::event::KeyEvent;
+
+    let Some(player) = script.as_mut().filter(|p| !p.is_done()) else {
+        return Ok(if event::poll(frame)? { Some(event::read()?) } else { None });
+    };
+    if let Some(key) = player.next_due(now) {
+        let code = match key {
+            demo::ScriptKey::Char(c) => KeyCode::Char(c),
+            demo::ScriptKey::Backspace => KeyCode::Backspace,
+            demo::ScriptKey::Tab => KeyCode::Tab,
+            demo::ScriptKey::Enter => KeyCode::Enter,
+            demo::ScriptKey::Esc => KeyCode::Esc,
+        };
+        return Ok(Some(Event::Key(KeyEvent::new(code, KeyModifiers::NONE))));
+    }
+    let wait = player.until_next(now).unwrap_or(frame).min(frame);
+    if !event::poll(wait)? {
+        return Ok(None);
+    }
+    let ev = event::read()?;
+    if matches!(ev, Event::Key(_)) {
+        *script = None;
+    }
+    Ok(Some(ev))
+}
+
+fn run_app_with(terminal: &mut Term, app: &mut App, in_sequence: bool, mut script: Option<demo::ScriptPlayer>) -> Result<LoopExit> {
     use std::time::{Duration, Instant};
 
     let size = terminal.size()?;
@@ -193,55 +1041,165 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut App)
 
     let mut last_blink_phase = u128::MAX;
     let mut last_timer_secs = u64::MAX;
+    let mut last_hidden_from = None;
     let mut needs_redraw = true;
     let mut finish_time: Option<Instant> = None;
+    let mut taskbar = ui::taskbar::TaskbarProgress::new(app.config.progress_mode);
+    let mut title = ui::title::TerminalTitle::new(app.config.terminal_title)?;
+    let lock_watch = app.config.pause_on_lock.then(LockWatch::spawn).flatten();
+    let mut was_celebrating = false;
+    let mut demo: Option<DemoTyper> = None;
+    let mut idle_since = app.now();
 
     loop {
         app.check_time();
         if app.test.state == models::AppState::Finished && finish_time.is_none() {
-            finish_time = Some(Instant::now());
+            finish_time = Some(app.now());
+        }
+
+        if let Some(typer) = demo.as_mut() {
+            typer.step(app);
+            needs_redraw = true;
+        } else if let Some(minutes) = app.config.demo.idle_minutes.filter(|&m| m > 0 && !in_sequence) {
+            if app.test.state != models::AppState::Waiting {
+                idle_since = app.now();
+            } else if app.now().saturating_duration_since(idle_since) >= Duration::from_secs(minutes * 60) {
+                app.start_demo();
+                demo = Some(DemoTyper::new(app.config.demo.wpm, app.now()));
+            }
+        }
+
+        if let Some(version) = app.update_check.as_ref().and_then(update::UpdateCheck::poll) {
+            app.newer_version = Some(version);
+            app.update_check = None;
+            needs_redraw = true;
+        }
+
+        if app.roll_quotas() {
+            needs_redraw = true;
+        }
+        if app.poll_uploads() {
+            needs_redraw = true;
+        }
+
+        if let Some(locked) = lock_watch.as_ref().and_then(LockWatch::poll) {
+            if locked { app.pause(); } else { app.resume(); }
+            needs_redraw = true;
         }
 
-        let blink_phase = app.test.caret_epoch.elapsed().as_millis() / BLINK_PERIOD.as_millis();
+        // a caret that doesn't blink needs no frames of its own
+        let blink_phase = if app.config.accessibility.blinks_caret() { app.caret_elapsed().as_millis() / BLINK_PERIOD.as_millis() } else { 0 };
         if blink_phase != last_blink_phase {
             last_blink_phase = blink_phase;
             needs_redraw = true;
         }
 
-        if let Some(start) = app.test.start_time {
-            let secs = start.elapsed().as_secs();
+        // a flashed dictation word hides on its own, with or without a blinking caret
+        let hidden_from = app.hidden_from();
+        if hidden_from != last_hidden_from {
+            last_hidden_from = hidden_from;
+            needs_redraw = true;
+        }
+
+        if let Some(elapsed) = app.elapsed() {
+            let secs = elapsed.as_secs();
             if secs != last_timer_secs {
                 last_timer_secs = secs;
                 needs_redraw = true;
             }
+            // opponents move between whole seconds
+            if !app.opponents.is_empty() {
+                needs_redraw = true;
+            }
+        }
+        // one more frame after the confetti ends, to clear it
+        let celebrating = app.celebration().is_some();
+        if celebrating || was_celebrating {
+            needs_redraw = true;
+        }
+        was_celebrating = celebrating;
+        let replaying = app.replay_position().is_some();
+        if replaying {
+            needs_redraw = true;
         }
 
         if needs_redraw {
             terminal.draw(|f| ui::render(f, app))?;
             needs_redraw = false;
         }
+        taskbar.update(app.progress())?;
+        title.update(app)?;
+
+        // faster frames while the confetti is flying, the demo is typing or a replay is playing
+        let frame = if celebrating || demo.is_some() || replaying { Duration::from_millis(33) } else { Duration::from_millis(100) };
+        let frame = if script.as_ref().is_some_and(|s| !s.is_done()) { Duration::from_millis(33) } else { frame };
+        let frame = match app.flash_deadline() {
+            Some(deadline) => frame.min(deadline.saturating_duration_since(app.now())),
+            None => frame,
+        };
+        if let Some(ev) = next_event(&mut script, app.now(), frame)? {
+            idle_since = app.now();
+
+            // any key or click ends the demo, and does nothing else
+            if demo.is_some() && matches!(ev, Event::Key(_) | Event::Mouse(_)) {
+                demo = None;
+                finish_time = None;
+                app.stop_demo();
+                needs_redraw = true;
+                continue;
+            }
 
-        if event::poll(Duration::from_millis(100))? {
-            let ev = event::read()?;
             match ev {
-                Event::Key(key) => {
-                    if key.kind == KeyEventKind::Press {
-                        let results_locked = app.test.state == models::AppState::Finished
-                            && finish_time.map_or(true, |t| t.elapsed() < RESULTS_LOCKOUT);
-
-                        needs_redraw = true;
-                        match key.code {
-                            KeyCode::Esc => app.quit(),
-                            KeyCode::Char('q') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                                app.quit()
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    let results_locked = app.test.state == models::AppState::Finished
+                        && finish_time.is_none_or(|t| app.now().saturating_duration_since(t) < RESULTS_LOCKOUT);
+
+                    needs_redraw = true;
+                    // the break reminder takes the first key after the lockout, whatever it is
+                    if app.test.break_due && key.code != KeyCode::Esc {
+                        if !results_locked { app.dismiss_break(); }
+                        continue;
+                    }
+                    match key.code {
+                        KeyCode::Esc => app.quit(),
+                        KeyCode::Char('q') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            app.quit()
+                        }
+                        KeyCode::Tab if in_sequence && app.test.state == models::AppState::Finished => {
+                            if !results_locked { return Ok(LoopExit::Next); }
+                            needs_redraw = false;
+                        }
+                        KeyCode::Tab if app.sequence.as_ref().is_some_and(|s| s.fixed_words) => { finish_time = None; app.retry_last_test(); }
+                        KeyCode::Tab if app.drill.is_some() => {
+                            if results_locked { needs_redraw = false; continue; }
+                            finish_time = None;
+                            if app.test.state == models::AppState::Finished { app.drill(); } else { app.retry_last_test(); }
+                        }
+                        KeyCode::Tab => { finish_time = None; app.restart_test(); }
+                        KeyCode::Char('r') if app.test.state == models::AppState::Finished && !results_locked => { finish_time = None; app.retry_last_test(); }
+                        KeyCode::Char(_) | KeyCode::Backspace if results_locked => { needs_redraw = false; }
+                        KeyCode::Char('d') if app.drill.is_none() && app.can_drill() => { finish_time = None; app.drill(); }
+                        KeyCode::Char(c @ '1'..='6') if app.test.state == models::AppState::Finished => {
+                            app.select_results_tab(models::ResultsTab::ALL[c as usize - '1' as usize]);
+                        }
+                        KeyCode::Char('s') if app.test.state == models::AppState::Finished => app.save_card(),
+                        KeyCode::Char('h') if app.test.state == models::AppState::Finished && app.test.results_tab == models::ResultsTab::Chart => app.move_chart_cursor(-1),
+                        KeyCode::Char('l') if app.test.state == models::AppState::Finished && app.test.results_tab == models::ResultsTab::Chart => app.move_chart_cursor(1),
+                        KeyCode::Char(c) => {
+                            let mistakes = app.test.live_incorrect_keystrokes;
+                            app.on_key(c);
+                            if app.config.accessibility.rings_bell() && app.test.live_incorrect_keystrokes > mistakes {
+                                // screen readers and braille displays can't see red
+                                print!("\x07");
+                                io::Write::flush(&mut io::stdout())?;
                             }
-                            KeyCode::Tab => { finish_time = None; app.restart_test(); }
-                            KeyCode::Char('r') if app.test.state == models::AppState::Finished && !results_locked => { finish_time = None; app.retry_last_test(); }
-                            KeyCode::Char(_) | KeyCode::Backspace if results_locked => { needs_redraw = false; }
-                            KeyCode::Char(c) => app.on_key(c),
-                            KeyCode::Backspace => app.on_backspace(),
-                            _ => { needs_redraw = false; }
                         }
+                        KeyCode::Backspace => app.on_backspace(),
+                        KeyCode::Left => app.step_results_tab(-1),
+                        KeyCode::Right => app.step_results_tab(1),
+                        KeyCode::Up => app.scroll_results(-1),
+                        KeyCode::Down => app.scroll_results(1),
+                        _ => { needs_redraw = false; }
                     }
                 }
                 Event::Mouse(_) => {
@@ -257,7 +1215,11 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut App)
         }
 
         if app.should_quit {
-            return Ok(());
+            if app.uploads.is_pending() {
+                terminal.draw(|f| ui::render(f, app))?;
+                app.finish_uploads();
+            }
+            return Ok(LoopExit::Quit);
         }
     }
 }
@@ -15,6 +15,23 @@ pub enum QuoteSelector {
     Id(usize),
 }
 
+impl QuoteSelector {
+    /// a number picks that quote id. anything that isn't a known length means all.
+    pub fn parse(s: &str) -> Self {
+        if let Ok(id) = s.parse::<usize>() {
+            return QuoteSelector::Id(id);
+        }
+        let category = match s.to_lowercase().as_str() {
+            "short" => QuoteLength::Short,
+            "medium" => QuoteLength::Medium,
+            "long" => QuoteLength::Long,
+            "very_long" | "verylong" => QuoteLength::VeryLong,
+            _ => QuoteLength::All,
+        };
+        QuoteSelector::Category(category)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Mode {
     Time(u64),
@@ -22,6 +39,38 @@ pub enum Mode {
     Quote(QuoteSelector),
 }
 
+/// optional ways for a running test to end early. any combination can be on at once.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FailRules {
+    /// any incorrect keystroke ends the test
+    pub sudden_death: bool,
+    /// submitting an incorrect word ends the test
+    pub expert: bool,
+    /// net wpm falling below this (after a short grace period) ends the test
+    pub min_wpm: Option<f64>,
+    /// a test that ends with accuracy below this percentage counts as failed
+    pub min_accuracy: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FailReason {
+    SuddenDeath,
+    Expert,
+    BelowMinWpm { wpm: f64, min: f64 },
+    BelowMinAccuracy { accuracy: f64, min: f64 },
+}
+
+impl FailReason {
+    pub fn describe(&self) -> String {
+        match self {
+            FailReason::SuddenDeath => "sudden death: incorrect keystroke".to_string(),
+            FailReason::Expert => "expert: submitted an incorrect word".to_string(),
+            FailReason::BelowMinWpm { wpm, min } => format!("min wpm: dropped to {:.0} (needed {:.0})", wpm, min),
+            FailReason::BelowMinAccuracy { accuracy, min } => format!("min accuracy: finished at {:.1}% (needed {:.0}%)", accuracy, min),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub enum AppState {
     Waiting,
@@ -29,49 +78,122 @@ pub enum AppState {
     Finished,
 }
 
+/// the pages of the results screen, in the order their number keys go
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ResultsTab {
+    #[default]
+    Summary,
+    Chart,
+    Words,
+    Keys,
+    Hands,
+    Replay,
+}
+
+impl ResultsTab {
+    pub const ALL: [ResultsTab; 6] = [Self::Summary, Self::Chart, Self::Words, Self::Keys, Self::Hands, Self::Replay];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Summary => "summary",
+            Self::Chart => "chart",
+            Self::Words => "words",
+            Self::Keys => "keys",
+            Self::Hands => "hands",
+            Self::Replay => "replay",
+        }
+    }
+
+    /// the tab `step` places along, wrapping round at either end
+    pub fn step(self, step: isize) -> Self {
+        let at = Self::ALL.iter().position(|&t| t == self).unwrap_or(0) as isize;
+        Self::ALL[(at + step).rem_euclid(Self::ALL.len() as isize) as usize]
+    }
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct QuoteEntry {
     pub text: String,
     pub source: String,
+    /// a link to where the quote was found, or to whoever contributed it
+    #[serde(default)]
+    pub attribution_url: Option<String>,
+    /// the terms the quote is shared under, e.g. "CC BY-SA 4.0"
+    #[serde(default)]
+    pub license: Option<String>,
+    #[serde(default)]
     pub length: usize,
     pub id: usize,
 }
 
+impl QuoteEntry {
+    pub fn credit(&self) -> QuoteCredit {
+        QuoteCredit { source: self.source.clone(), attribution_url: self.attribution_url.clone(), license: self.license.clone() }
+    }
+}
+
+/// who a quote in a test is by, and where it's from and under what terms when its pack says
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QuoteCredit {
+    pub source: String,
+    pub attribution_url: Option<String>,
+    pub license: Option<String>,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct QuoteData {
     #[allow(dead_code)]
     pub language: String,
+    #[serde(default)]
     pub groups: Vec<Vec<usize>>,
     pub quotes: Vec<QuoteEntry>,
 }
 
+/// upper bounds (inclusive, in chars) of the short/medium/long buckets. anything longer is very long.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuoteThresholds {
+    pub short: usize,
+    pub medium: usize,
+    pub long: usize,
+}
+
+impl Default for QuoteThresholds {
+    fn default() -> Self {
+        Self { short: 100, medium: 300, long: 600 }
+    }
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct WordData {
     #[allow(dead_code)]
     pub name: String,
     pub words: Vec<String>,
+    /// templates and tagged words for sentence-like tests. most packs don't have them.
+    #[serde(default)]
+    pub sentences: Option<SentencePack>,
+    /// how the language writes numbers, for `--numbers`
+    #[serde(default, rename = "numberFormat")]
+    pub number_format: NumberFormat,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum WordState {
-    Pending,
-    Active,
-    Typed,
+/// the decimal mark and thousands separator a language writes numbers with: `1,234.5` in
+/// english, `1.234,5` in indonesian. packs without one get a point and no grouping.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct NumberFormat {
+    pub decimal: char,
+    pub thousands: Option<char>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct Word {
-    pub text: String,
-    pub state: WordState,
-    pub index: usize,
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self { decimal: '.', thousands: None }
+    }
 }
 
-impl Word {
-    pub fn new(text: String, index: usize) -> Self {
-        Self {
-            text,
-            state: WordState::Pending,
-            index,
-        }
-    }
+/// sentence templates like "{det} {adj} {noun} {verb}", and the words for each tag in them
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct SentencePack {
+    pub templates: Vec<String>,
+    pub tags: std::collections::HashMap<String, Vec<String>>,
 }
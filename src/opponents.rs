@@ -0,0 +1,52 @@
+use crate::text::TypedText;
+use std::time::Duration;
+
+/// anything typing alongside the player: pace bots now, ghosts and race peers later.
+/// positions are in words of the player's own stream so every bar shares one scale.
+pub trait Opponent {
+    fn name(&self) -> String;
+
+    /// how far into `text`'s words this opponent is `elapsed` into the test, and its wpm right then
+    fn position(&self, text: &TypedText, elapsed: Duration) -> OpponentPosition;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct OpponentPosition {
+    /// whole words done plus the fraction of the current one
+    pub words_done: f64,
+    pub wpm: f64,
+}
+
+/// types the stream at a steady speed without ever making a mistake
+pub struct PaceBot {
+    pub wpm: f64,
+}
+
+impl Opponent for PaceBot {
+    fn name(&self) -> String {
+        format!("pace {:.0}", self.wpm)
+    }
+
+    fn position(&self, text: &TypedText, elapsed: Duration) -> OpponentPosition {
+        // five keystrokes to a word, spaces included, same as the wpm the player sees
+        let mut budget = self.wpm * 5.0 / 60.0 * elapsed.as_secs_f64();
+        // the words no longer kept are only a count and a length, so they go as one
+        let (dropped, dropped_chars) = text.dropped();
+        let cost = (dropped_chars + dropped) as f64;
+        if budget < cost {
+            return OpponentPosition { words_done: dropped as f64 * budget / cost, wpm: self.wpm };
+        }
+        budget -= cost;
+        let mut words_done = dropped as f64;
+        for w in text.all_words() {
+            let cost = (w.target().len() + 1) as f64;
+            if budget < cost {
+                words_done += budget / cost;
+                break;
+            }
+            budget -= cost;
+            words_done += 1.0;
+        }
+        OpponentPosition { words_done, wpm: self.wpm }
+    }
+}
@@ -0,0 +1,153 @@
+//! language packs from outside the binary. typa looks in the `packs` folder of its data
+//! directory before its embedded files, so a pack there adds a language or replaces one.
+//! `typa languages fetch` fills it from a community index: a json list of packs, each with
+//! the url and sha256 of its word list and, optionally, its quotes. downloading is only built
+//! in with the `fetch` feature, and goes through `curl`.
+
+use crate::models::{QuoteData, WordData};
+use crate::utils::paths;
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Deserialize)]
+pub struct Index {
+    pub packs: Vec<IndexEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IndexEntry {
+    pub name: String,
+    pub language: PackFile,
+    #[serde(default)]
+    pub quotes: Option<PackFile>,
+}
+
+/// a file to download, and the sha256 it has to come back with. a relative url is taken
+/// from wherever the index is.
+#[derive(Debug, Deserialize)]
+pub struct PackFile {
+    pub url: String,
+    pub sha256: String,
+}
+
+/// `packs/language/<name>.json` or `packs/quotes/<name>.json`, whether or not it's there
+pub fn user_file(kind: &str, name: &str) -> Option<PathBuf> {
+    if !valid_name(name) { return None; }
+    paths::data_file("packs").map(|dir| dir.join(kind).join(format!("{}.json", name)))
+}
+
+/// names of the word lists in the pack directory
+pub fn installed() -> Vec<String> {
+    let Some(dir) = paths::data_file("packs").map(|d| d.join("language")) else { return Vec::new() };
+    let mut names: Vec<String> = fs::read_dir(dir).into_iter().flatten()
+        .filter_map(|e| e.ok()?.file_name().to_str()?.strip_suffix(".json").map(str::to_string))
+        .collect();
+    names.sort();
+    names
+}
+
+/// pack names end up in file names, so they're kept to what can't go anywhere else
+pub fn valid_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+}
+
+/// `url` as written in the index at `index_url`: absolute as it is, relative next to the index
+pub fn resolve_url(index_url: &str, url: &str) -> String {
+    if url.contains("://") {
+        return url.to_string();
+    }
+    match index_url.rfind('/') {
+        Some(i) => format!("{}/{}", &index_url[..i], url.trim_start_matches("./")),
+        None => url.to_string(),
+    }
+}
+
+/// downloads `name`'s word list, and its quotes if it has any, checks each against its
+/// checksum and that typa can read it, and only then puts them in the pack directory.
+/// quotes left from an earlier fetch go if the pack no longer has any
+#[cfg(feature = "fetch")]
+pub fn fetch(index_url: &str, name: &str) -> Result<Vec<PathBuf>> {
+    let index: Index = serde_json::from_slice(&imp::download(index_url)?)
+        .with_context(|| format!("{} isn't a pack index", index_url))?;
+    let Some(entry) = index.packs.iter().find(|p| p.name == name) else {
+        let names: Vec<&str> = index.packs.iter().map(|p| p.name.as_str()).collect();
+        bail!("the index has no pack called {}. it has: {}", name, names.join(", "));
+    };
+    if !valid_name(&entry.name) {
+        bail!("{} isn't a usable pack name", entry.name);
+    }
+
+    let words = imp::download_checked(index_url, &entry.language)?;
+    serde_json::from_slice::<WordData>(&words).context("the downloaded word list isn't one typa can read")?;
+    let mut files = vec![("language", words)];
+    if let Some(quotes) = &entry.quotes {
+        let quotes = imp::download_checked(index_url, quotes)?;
+        serde_json::from_slice::<QuoteData>(&quotes).context("the downloaded quotes aren't ones typa can read")?;
+        files.push(("quotes", quotes));
+    }
+
+    let mut written = Vec::new();
+    for (kind, bytes) in files {
+        let path = user_file(kind, name).context("no data directory to keep packs in")?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, bytes)?;
+        fs::rename(&tmp_path, &path)?;
+        written.push(path);
+    }
+    // quotes a pack used to ship would otherwise outlive it and keep turning up in quote mode
+    if entry.quotes.is_none() {
+        if let Some(stale) = user_file("quotes", name).filter(|p| p.exists()) {
+            fs::remove_file(&stale).with_context(|| format!("couldn't remove the old quotes at {}", stale.display()))?;
+        }
+    }
+    Ok(written)
+}
+
+#[cfg(not(feature = "fetch"))]
+pub fn fetch(_index_url: &str, _name: &str) -> Result<Vec<PathBuf>> {
+    bail!("this typa was built without downloading. reinstall with `cargo install typa --features fetch`, or put packs in the pack directory yourself")
+}
+
+#[cfg(feature = "fetch")]
+mod imp {
+    use super::{resolve_url, PackFile};
+    use crate::utils::net;
+    use anyhow::{bail, Result};
+    use sha2::{Digest, Sha256};
+
+    pub fn download(url: &str) -> Result<Vec<u8>> {
+        net::download(url, 60)
+    }
+
+    pub fn download_checked(index_url: &str, file: &PackFile) -> Result<Vec<u8>> {
+        let url = resolve_url(index_url, &file.url);
+        let bytes = download(&url)?;
+        let sum: String = Sha256::digest(&bytes).iter().map(|b| format!("{:02x}", b)).collect();
+        if !sum.eq_ignore_ascii_case(file.sha256.trim()) {
+            bail!("{} doesn't match its checksum, so it wasn't installed", url);
+        }
+        Ok(bytes)
+    }
+}
+
+/// the word list from the pack directory, if there's one for `language`
+pub fn load_word_data(language: &str) -> Option<Result<WordData>> {
+    let path = user_file("language", language).filter(|p| p.exists())?;
+    Some(read(&path))
+}
+
+/// the quotes from the pack directory, if there are any for `language`
+pub fn load_quote_data(language: &str) -> Option<Result<QuoteData>> {
+    let path = user_file("quotes", language).filter(|p| p.exists())?;
+    Some(read(&path))
+}
+
+fn read<T: serde::de::DeserializeOwned>(path: &std::path::Path) -> Result<T> {
+    let raw = fs::read_to_string(path).with_context(|| format!("couldn't read {}", path.display()))?;
+    serde_json::from_str(&raw).with_context(|| format!("{} isn't a pack typa can read", path.display()))
+}
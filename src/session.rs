@@ -0,0 +1,80 @@
+//! every test finished in one launch of typa. the tests are tallied for a summary on the
+//! way out, and each history record carries the launch's id so they can be grouped later.
+
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+/// a gap this long between tests is a break whether or not one was suggested
+const REST: Duration = Duration::from_secs(5 * 60);
+
+/// one id for the whole launch, shared by every test run in it, warmup steps included
+fn launch_id() -> &'static str {
+    static ID: OnceLock<String> = OnceLock::new();
+    ID.get_or_init(|| format!("{}-{}", chrono::Local::now().format("%Y%m%d%H%M%S"), std::process::id()))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SessionResult {
+    pub wpm: f64,
+    pub accuracy: f64,
+    pub secs: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub id: String,
+    pub results: Vec<SessionResult>,
+    /// seconds spent in tests since the last break
+    pub since_break: f64,
+    last_end: Option<Instant>,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self { id: launch_id().to_string(), results: Vec::new(), since_break: 0.0, last_end: None }
+    }
+}
+
+impl Session {
+    /// a test that finished at `end`
+    pub fn add(&mut self, result: SessionResult, end: Instant) {
+        let start = end.checked_sub(Duration::from_secs_f64(result.secs)).unwrap_or(end);
+        if self.last_end.is_some_and(|last| start.saturating_duration_since(last) >= REST) {
+            self.since_break = 0.0;
+        }
+        self.since_break += result.secs;
+        self.last_end = Some(end);
+        self.results.push(result);
+    }
+
+    pub fn take_break(&mut self) {
+        self.since_break = 0.0;
+    }
+
+    /// averages are weighted by time, so a 15s test doesn't count as much as a 2 minute one
+    pub fn average_wpm(&self) -> Option<f64> {
+        let secs: f64 = self.results.iter().map(|r| r.secs).sum();
+        (secs > 0.0).then(|| self.results.iter().map(|r| r.wpm * r.secs).sum::<f64>() / secs)
+    }
+
+    pub fn average_accuracy(&self) -> Option<f64> {
+        let secs: f64 = self.results.iter().map(|r| r.secs).sum();
+        (secs > 0.0).then(|| self.results.iter().map(|r| r.accuracy * r.secs).sum::<f64>() / secs)
+    }
+
+    pub fn best_wpm(&self) -> Option<f64> {
+        self.results.iter().map(|r| r.wpm).reduce(f64::max)
+    }
+
+    /// "3 tests, 72 wpm average, 81 best, 96% accuracy, 2m 30s typing", or None when nothing was finished
+    pub fn summary(&self) -> Option<String> {
+        let (avg, best, acc) = (self.average_wpm()?, self.best_wpm()?, self.average_accuracy()?);
+        let n = self.results.len();
+        let secs = self.results.iter().map(|r| r.secs).sum::<f64>().round() as u64;
+        let time = if secs >= 60 { format!("{}m {}s", secs / 60, secs % 60) } else { format!("{}s", secs) };
+        Some(format!(
+            "{} test{}, {:.0} wpm average, {:.0} best, {:.0}% accuracy, {} typing",
+            n, if n == 1 { "" } else { "s" }, avg, best, acc, time
+        ))
+    }
+}
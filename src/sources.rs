@@ -0,0 +1,236 @@
+//! test text from outside typa's own word lists and quotes, read from a file or fetched by
+//! running another program. whatever comes back is typed like a single quote.
+
+use crate::utils::strings;
+use anyhow::{bail, Context, Result};
+use std::collections::HashSet;
+use std::io::Read;
+use std::path::Path;
+use std::process::Command;
+
+/// the longest a fetched text gets. man pages run to thousands of words.
+const MAX_WORDS: usize = 300;
+
+/// text for a one-off test, and where it came from for the results screen
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomText {
+    pub text: String,
+    pub source: String,
+}
+
+/// a text file, or stdin for "-", with its lines run together and cut to length on a
+/// sentence end
+pub fn file_text(path: &str) -> Result<CustomText> {
+    let (raw, source) = if path == "-" {
+        let mut raw = String::new();
+        std::io::stdin().read_to_string(&mut raw).context("could not read stdin")?;
+        (raw, "stdin".to_string())
+    } else {
+        let raw = std::fs::read_to_string(path).with_context(|| format!("could not read {}", path))?;
+        let name = Path::new(path).file_name().map_or(path.into(), |n| n.to_string_lossy());
+        (raw, name.to_string())
+    };
+
+    let text = strings::clean_typography_symbols(&raw);
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if words.is_empty() {
+        bail!("{} has no text to type", source);
+    }
+    Ok(CustomText { text: cut_to_length(&words), source })
+}
+
+/// the language whose common words make up the most of `text`, out of `languages`' names and
+/// word lists. the lists are the most frequent words of each, so they work as stopwords: a handful have to turn
+/// up, and clearly more often than the runner-up's, or it's None and the caller picks.
+pub fn detect_language<'a>(text: &str, languages: &'a [(String, Vec<String>)]) -> Option<&'a str> {
+    /// fewer hits than this is too little text to go on
+    const MIN_HITS: usize = 5;
+
+    let tokens: Vec<String> = text
+        .split_whitespace()
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect();
+    let mut scores: Vec<(usize, &str)> = languages.iter().map(|(name, words)| {
+        let common: HashSet<String> = words.iter().map(|w| w.to_lowercase()).collect();
+        (tokens.iter().filter(|t| common.contains(t.as_str())).count(), name.as_str())
+    }).collect();
+    scores.sort_by_key(|s| std::cmp::Reverse(s.0));
+
+    let (best, name) = *scores.first()?;
+    let runner_up = scores.get(1).map_or(0, |s| s.0);
+    // a quarter more than the runner-up, since short words like "a" and "di" turn up everywhere
+    (best >= MIN_HITS && best * 4 > runner_up * 5).then_some(name)
+}
+
+/// one word list for each language among `names`, for `detect_language`. english_1k and
+/// english_10k are english too, and scored on their own they'd tie with it every time, so
+/// only the first of each, sorted, is kept: the plain name when there is one.
+pub fn one_per_language(mut names: Vec<String>) -> Vec<String> {
+    names.sort();
+    let base = |name: &str| name.split('_').next().unwrap_or(name).to_string();
+    names.dedup_by(|later, kept| base(later) == base(kept));
+    names
+}
+
+/// the DESCRIPTION section of an installed man page, or the whole page when there isn't one
+pub fn man_page(page: &str) -> Result<CustomText> {
+    let output = Command::new("man")
+        .arg(page)
+        // one paragraph a line, so nothing gets hyphenated across lines
+        .env("MANWIDTH", "10000")
+        .env("MANPAGER", "cat")
+        .env("GROFF_NO_SGR", "1")
+        .env_remove("MAN_KEEP_FORMATTING")
+        .output()
+        .context("could not run man")?;
+    if !output.status.success() {
+        bail!("no man page for {}", page);
+    }
+
+    let text = clean_man_text(&String::from_utf8_lossy(&output.stdout));
+    if text.is_empty() {
+        bail!("the man page for {} has no text to type", page);
+    }
+    Ok(CustomText { text, source: format!("man {}", page) })
+}
+
+/// recent commit subjects from the git repository the current directory is in, one
+/// sentence each, newest first
+pub fn git_log() -> Result<CustomText> {
+    let output = Command::new("git")
+        .args(["log", "--no-merges", "--format=%s", "-n", "200"])
+        .output()
+        .context("could not run git")?;
+    if !output.status.success() {
+        bail!("not inside a git repository with any commits");
+    }
+
+    let text = clean_commit_subjects(&String::from_utf8_lossy(&output.stdout));
+    if text.is_empty() {
+        bail!("no commit messages with anything to type");
+    }
+
+    let toplevel = Command::new("git").args(["rev-parse", "--show-toplevel"]).output().ok();
+    let repo = toplevel
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .and_then(|path| path.rsplit('/').next().map(str::to_string))
+        .filter(|name| !name.is_empty());
+    let source = match repo {
+        Some(name) => format!("git log of {}", name),
+        None => "git log".to_string(),
+    };
+    Ok(CustomText { text, source })
+}
+
+/// commit subjects strung into sentences: conventional commit types, `[tag]` prefixes and
+/// issue refs dropped, repeats and non-ascii subjects skipped, and cut to length at the end
+/// of a subject
+pub fn clean_commit_subjects(raw: &str) -> String {
+    let mut seen = HashSet::new();
+    let mut words: Vec<String> = Vec::new();
+    for subject in raw.lines() {
+        let subject = strings::clean_typography_symbols(strip_commit_prefix(subject.trim()));
+        // a subject with letters that can't be typed is skipped whole rather than left with holes
+        if !subject.chars().all(|c| c == ' ' || c.is_ascii_graphic()) { continue; }
+        let mut sentence: Vec<String> = subject
+            .split_whitespace()
+            .filter(|w| !is_issue_ref(w))
+            .map(str::to_string)
+            .collect();
+        if sentence.is_empty() || !seen.insert(sentence.join(" ").to_lowercase()) { continue; }
+        if words.len() + sentence.len() > MAX_WORDS && !words.is_empty() { break; }
+
+        strings::capitalize_word(&mut sentence[0]);
+        let last = sentence.last_mut().unwrap();
+        if !strings::ends_with_terminator(last) {
+            last.push('.');
+        }
+        words.extend(sentence);
+    }
+    words.join(" ")
+}
+
+/// conventional commit types, the ones `strip_commit_prefix` takes off
+const COMMIT_TYPES: [&str; 11] = ["build", "chore", "ci", "docs", "feat", "fix", "perf", "refactor", "revert", "style", "test"];
+
+/// "fix(parser): ", "feat!: " and "[abc#12] " style prefixes. anything else before a colon,
+/// like "Note: ", is part of the subject.
+fn strip_commit_prefix(subject: &str) -> &str {
+    let mut rest = subject;
+    if rest.starts_with('[') {
+        if let Some(end) = rest.find("] ") {
+            rest = &rest[end + 2..];
+        }
+    }
+    if let Some((kind, after)) = rest.split_once(": ") {
+        let kind = kind.strip_suffix('!').unwrap_or(kind);
+        // an optional "(scope)" after the type
+        let kind = match kind.split_once('(') {
+            Some((kind, scope)) if scope.ends_with(')') && !scope.contains(['(', ' ']) => kind,
+            Some(_) => "",
+            None => kind,
+        };
+        if COMMIT_TYPES.contains(&kind.to_ascii_lowercase().as_str()) { rest = after; }
+    }
+    rest.trim()
+}
+
+/// "#123", "(#123)" and "GH-7", which are no fun to type. "UTF-8" and the like are words.
+fn is_issue_ref(word: &str) -> bool {
+    let w = word.trim_matches(|c: char| "(),.".contains(c));
+    let number = match w.strip_prefix('#') {
+        Some(n) => n,
+        None => match w.split_once('-') {
+            Some((key, n)) if key.eq_ignore_ascii_case("gh") => n,
+            _ => return false,
+        },
+    };
+    !number.is_empty() && number.chars().all(|c| c.is_ascii_digit())
+}
+
+/// plain prose out of formatted man output: overstrikes and escape codes stripped, headings and
+/// the page's header and footer lines dropped, and cut to length on a sentence end if there's one
+pub fn clean_man_text(raw: &str) -> String {
+    let lines: Vec<String> = raw.lines().map(strip_formatting).collect();
+
+    // section headings are the only unindented lines between the header and footer
+    let is_heading = |l: &str| !l.is_empty() && !l.starts_with(char::is_whitespace);
+    let body: Vec<&String> = match lines.iter().position(|l| l.trim() == "DESCRIPTION") {
+        Some(start) => lines[start + 1..].iter().take_while(|l| !is_heading(l)).collect(),
+        None => lines.iter().filter(|l| !is_heading(l)).collect(),
+    };
+
+    let text = strings::clean_typography_symbols(&body.iter().map(|l| l.trim()).collect::<Vec<_>>().join(" "));
+    let words: Vec<&str> = text.split_whitespace().filter(|w| w.chars().all(|c| c.is_ascii_graphic())).collect();
+    cut_to_length(&words)
+}
+
+/// `words` joined, cut after the last sentence end within `MAX_WORDS`, or at `MAX_WORDS` if
+/// there isn't one
+fn cut_to_length(words: &[&str]) -> String {
+    if words.len() <= MAX_WORDS {
+        return words.join(" ");
+    }
+    let cut = words[..MAX_WORDS].iter().rposition(|w| strings::is_sentence_end(w)).map_or(MAX_WORDS, |i| i + 1);
+    words[..cut].join(" ")
+}
+
+/// drops `x\bx` bold and `_\bx` underline overstrikes, and any ansi escape codes
+fn strip_formatting(line: &str) -> String {
+    let mut out: Vec<char> = Vec::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\u{8}' => { out.pop(); }
+            '\u{1b}' => {
+                // csi: parameters up to a final letter
+                if chars.next_if_eq(&'[').is_some() {
+                    while chars.next().is_some_and(|c| !c.is_ascii_alphabetic()) {}
+                }
+            }
+            c => out.push(c),
+        }
+    }
+    out.into_iter().collect()
+}
@@ -0,0 +1,214 @@
+use crate::config::{StatusFormat, StatuslineConfig};
+use crate::history::stats::compute_streaks;
+use crate::history::TestRecord;
+use chrono::{DateTime, Local, NaiveDate};
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Instant;
+
+/// live updates during a test are written at most this often. state changes always go out.
+const THROTTLE_MS: u128 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum State {
+    Idle,
+    Running,
+    Failed,
+    Closed,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LastResult {
+    pub wpm: f64,
+    pub accuracy: f64,
+    pub test: String,
+}
+
+impl LastResult {
+    fn from_history(records: &[TestRecord]) -> Option<Self> {
+        records.iter().rev().find(|r| r.completed).and_then(|r| {
+            Some(LastResult {
+                wpm: r.wpm?,
+                accuracy: r.accuracy?,
+                test: format!("{} {}", r.mode, r.mode_value),
+            })
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct Snapshot<'a> {
+    state: State,
+    test: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    wpm: Option<f64>,
+    last: Option<&'a LastResult>,
+}
+
+/// keeps a tiny status file up to date for tmux, i3status and friends
+pub struct StatusLine {
+    path: PathBuf,
+    format: StatusFormat,
+    state: State,
+    test: String,
+    wpm: Option<f64>,
+    last: Option<LastResult>,
+    last_write: Option<Instant>,
+}
+
+impl StatusLine {
+    /// `records` seeds the last result, so the file has something to show before the first test
+    pub fn new(config: &StatuslineConfig, records: &[TestRecord]) -> Self {
+        let path = config.path.as_ref().map(PathBuf::from).unwrap_or_else(default_path);
+        let last = LastResult::from_history(records);
+        Self { path, format: config.format, state: State::Idle, test: String::new(), wpm: None, last, last_write: None }
+    }
+
+    pub fn set_idle(&mut self, test: &str) {
+        self.transition(State::Idle, test);
+    }
+
+    pub fn set_running(&mut self, test: &str, wpm: f64) {
+        self.wpm = Some(wpm);
+        if self.state == State::Running {
+            self.write(false);
+        } else {
+            self.transition(State::Running, test);
+        }
+    }
+
+    pub fn set_result(&mut self, test: &str, wpm: f64, accuracy: f64) {
+        self.last = Some(LastResult { wpm, accuracy, test: test.to_string() });
+        self.transition(State::Idle, test);
+    }
+
+    pub fn set_failed(&mut self, test: &str) {
+        self.transition(State::Failed, test);
+    }
+
+    pub fn close(&mut self) {
+        let test = std::mem::take(&mut self.test);
+        self.transition(State::Closed, &test);
+    }
+
+    fn transition(&mut self, state: State, test: &str) {
+        if state != State::Running {
+            self.wpm = None;
+        }
+        self.state = state;
+        self.test = test.to_string();
+        self.write(true);
+    }
+
+    fn write(&mut self, force: bool) {
+        if !force && self.last_write.is_some_and(|t| t.elapsed().as_millis() < THROTTLE_MS) {
+            return;
+        }
+        self.last_write = Some(Instant::now());
+
+        let contents = match self.format {
+            StatusFormat::Plain => self.plain(),
+            StatusFormat::Json => {
+                let snapshot = Snapshot { state: self.state, test: &self.test, wpm: self.wpm, last: self.last.as_ref() };
+                serde_json::to_string(&snapshot).unwrap_or_default()
+            }
+        };
+
+        // write then rename, so a status bar never reads half a file
+        let tmp = self.path.with_extension("tmp");
+        if fs::write(&tmp, contents + "\n").is_ok() {
+            let _ = fs::rename(&tmp, &self.path);
+        }
+    }
+
+    fn plain(&self) -> String {
+        let last = self.last.as_ref().map(|l| format!("last: {:.0}wpm {:.0}%", l.wpm, l.accuracy));
+        match (self.state, self.wpm) {
+            (State::Running, Some(wpm)) => format!("typing: {:.0}wpm", wpm),
+            (State::Running, None) => "typing".to_string(),
+            (State::Failed, _) => match last {
+                Some(last) => format!("failed | {}", last),
+                None => "failed".to_string(),
+            },
+            _ => last.unwrap_or_else(|| "last: -".to_string()),
+        }
+    }
+}
+
+fn default_path() -> PathBuf {
+    std::env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(std::env::temp_dir)
+        .join("typa-status")
+}
+
+/// what `typa status` prints: the last result, how many tests were finished today, and the
+/// streak. worked out from history each time, for bars that poll a command instead of
+/// reading the status file.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatusSummary {
+    pub last: Option<LastResult>,
+    pub today: usize,
+    pub streak: usize,
+}
+
+impl StatusSummary {
+    /// practice runs count towards neither today's tests nor the streak, as in the stats view
+    pub fn from_history(records: &[TestRecord], today: NaiveDate) -> Self {
+        let finished_today = records.iter()
+            .filter(|r| r.completed && !r.practice)
+            .filter(|r| DateTime::parse_from_rfc3339(&r.timestamp).is_ok_and(|t| t.with_timezone(&Local).date_naive() == today))
+            .count();
+        Self { last: LastResult::from_history(records), today: finished_today, streak: compute_streaks(records, false).0 }
+    }
+
+    /// "92wpm 97% | 3 today | 5 day streak"
+    pub fn text(&self) -> String {
+        let mut parts = vec![self.short_text()];
+        parts.push(format!("{} today", self.today));
+        if self.streak > 0 {
+            parts.push(format!("{} day streak", self.streak));
+        }
+        parts.join(" | ")
+    }
+
+    /// just the last result, for bars that run out of room
+    pub fn short_text(&self) -> String {
+        match &self.last {
+            Some(last) => format!("{:.0}wpm {:.0}%", last.wpm, last.accuracy),
+            None => "no tests yet".to_string(),
+        }
+    }
+
+    /// the i3blocks protocol: full text on the first line, short text on the second
+    pub fn i3blocks(&self) -> String {
+        format!("{}\n{}", self.text(), self.short_text())
+    }
+
+    /// a waybar custom module with `"return-type": "json"`. the class is "done" once there's
+    /// been a test today and "pending" until then, for styling. the raw numbers go along too.
+    pub fn waybar(&self) -> String {
+        #[derive(Serialize)]
+        struct Waybar<'a> {
+            text: String,
+            tooltip: String,
+            class: &'static str,
+            #[serde(flatten)]
+            summary: &'a StatusSummary,
+        }
+        let mut tooltip = match &self.last {
+            Some(last) => format!("last: {}, {:.0} wpm, {:.0}% accuracy", last.test, last.wpm, last.accuracy),
+            None => "no tests yet".to_string(),
+        };
+        tooltip.push_str(&format!("\n{} {} today\n{} day streak", self.today, if self.today == 1 { "test" } else { "tests" }, self.streak));
+        let waybar = Waybar {
+            text: self.text(),
+            tooltip,
+            class: if self.today > 0 { "done" } else { "pending" },
+            summary: self,
+        };
+        serde_json::to_string(&waybar).unwrap_or_default()
+    }
+}
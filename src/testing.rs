@@ -0,0 +1,84 @@
+//! helpers for driving an `App` from tests: a fixed word stream, scripted keystrokes,
+//! and a clock that only moves when told to. nothing here touches history, discord or
+//! anything else in the data directory.
+
+use crate::app::{App, TestOptions};
+use crate::clock::ManualClock;
+use crate::config::AppConfig;
+use crate::models::{AppState, Mode};
+use chrono::{DateTime, Local};
+use std::time::Duration;
+
+/// typed into `Harness::type_str` to mean backspace
+pub const BACKSPACE: char = '\x08';
+
+// how often the real event loop wakes up to check the timer
+const TICK: Duration = Duration::from_millis(100);
+
+pub struct Harness {
+    pub app: App,
+    clock: ManualClock,
+}
+
+impl Harness {
+    /// `words` is the whole stream. time mode keeps generating random words past the end of it.
+    pub fn new(mode: Mode, words: &[&str]) -> Self {
+        Self::with_options(TestOptions { mode, allow_repeats: true, ..TestOptions::default() }, words)
+    }
+
+    pub fn with_options(options: TestOptions, words: &[&str]) -> Self {
+        let clock = ManualClock::new();
+        let mut app = App::detached(options, AppConfig::default(), Box::new(clock.clone()))
+            .expect("embedded language data should always load");
+        app.seed_from_word_list(words.iter().map(|w| w.to_string()).collect());
+        Self { app, clock }
+    }
+
+    /// moves virtual time forward in event-loop sized ticks, so time limits and per-second
+    /// snapshots land where they would in a real run
+    pub fn advance(&mut self, by: Duration) {
+        let mut left = by;
+        while !left.is_zero() {
+            let step = left.min(TICK);
+            self.clock.advance(step);
+            self.app.check_time();
+            left -= step;
+        }
+    }
+
+    /// puts the calendar at `at`, for what goes by the date, like quotas
+    pub fn set_local(&mut self, at: DateTime<Local>) {
+        self.clock.set_local(at);
+    }
+
+    pub fn key(&mut self, c: char) {
+        if c == BACKSPACE {
+            self.app.on_backspace();
+        } else {
+            self.app.on_key(c);
+        }
+    }
+
+    /// one key every `gap`. the first key starts the test, so n keys span (n - 1) gaps.
+    pub fn type_str(&mut self, text: &str, gap: Duration) {
+        for (i, c) in text.chars().enumerate() {
+            if i > 0 {
+                self.advance(gap);
+            }
+            self.key(c);
+        }
+    }
+
+    /// types at a steady raw speed, counting five keys to a word like the stats do
+    pub fn type_at_wpm(&mut self, text: &str, wpm: f64) {
+        self.type_str(text, Duration::from_secs_f64(60.0 / (wpm * 5.0)));
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.app.test.state == AppState::Finished
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.app.elapsed().unwrap_or_default()
+    }
+}
@@ -0,0 +1,351 @@
+//! the part of the test that's on screen or waiting to be, one `Word` each. a word owns
+//! its target and what was typed into it, and extras, misses, display and stats all come
+//! from those two. a keystroke only ever touches the word it lands in, so typing costs the
+//! same whether a hundred words or a whole book are queued up behind the caret.
+//! behind it, scrolled words are kept for the results up to `KEPT_WORDS`, and past that
+//! only their score and length are, so a test that never ends doesn't grow forever.
+
+use crate::utils::strings;
+use std::collections::VecDeque;
+use std::ops::{AddAssign, Range};
+
+/// scrolled words kept whole, for the results, retries and replays. `--words` stops at this,
+/// so only a test with no end, like time mode left running, ever has its first words dropped.
+pub const KEPT_WORDS: usize = 10_000;
+
+/// one word of the test: what should be typed, what has been, and whether a space closed it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Word {
+    target: Vec<char>,
+    typed: Vec<char>,
+    closed: bool,
+}
+
+impl Word {
+    pub fn new(target: &str) -> Self {
+        Self { target: target.chars().collect(), typed: Vec::new(), closed: false }
+    }
+
+    pub fn target(&self) -> &[char] {
+        &self.target
+    }
+
+    pub fn typed(&self) -> &[char] {
+        &self.typed
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// columns on screen: the target, plus whatever was typed past its end
+    pub fn width(&self) -> usize {
+        self.target.len().max(self.typed.len())
+    }
+
+    /// letters typed past the end of the target
+    pub fn extra(&self) -> usize {
+        self.typed.len().saturating_sub(self.target.len())
+    }
+
+    /// letters skipped by closing the word early
+    pub fn missed(&self) -> usize {
+        if self.closed { self.target.len().saturating_sub(self.typed.len()) } else { 0 }
+    }
+
+    /// the char shown in column `i`, and whether it's an extra
+    pub fn shown(&self, i: usize) -> (char, bool) {
+        match self.target.get(i) {
+            Some(&c) => (c, false),
+            None => (self.typed[i], true),
+        }
+    }
+
+    /// typed exactly, or near enough that dashes and quotes don't matter
+    pub fn is_correct(&self) -> bool {
+        self.typed.len() == self.target.len()
+            && self.typed.iter().zip(&self.target).all(|(&a, &b)| strings::are_characters_visually_equal(a, b))
+    }
+
+    /// whether anything typed so far is wrong, ignoring what hasn't been typed yet
+    pub fn has_typo(&self) -> bool {
+        self.typed.iter().enumerate().any(|(i, &c)| {
+            self.target.get(i).is_none_or(|&t| !strings::are_characters_visually_equal(c, t))
+        })
+    }
+
+    /// scores every column, plus the space after the word when there is one. anything
+    /// not typed counts as missed, and a word with any error earns none of its letters.
+    pub fn stats(&self, with_space: bool) -> CharStats {
+        let mut s = CharStats::default();
+        let has_error = self.extra() > 0 || self.typed.len() < self.target.len() || self.has_typo();
+
+        for i in 0..self.width() {
+            let (shown, is_extra) = self.shown(i);
+            match self.typed.get(i) {
+                _ if is_extra => s.extra += 1,
+                None => s.missed += 1,
+                Some(&c) if !strings::are_characters_visually_equal(c, shown) => s.incorrect += 1,
+                Some(_) if !has_error => s.correct += 1,
+                Some(_) => {}
+            }
+        }
+
+        if with_space && !has_error {
+            s.correct += 1;
+        }
+        s
+    }
+}
+
+/// chars by how they were typed, the way the results screen counts them
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CharStats {
+    pub correct: usize,
+    pub incorrect: usize,
+    pub extra: usize,
+    pub missed: usize,
+}
+
+impl AddAssign for CharStats {
+    fn add_assign(&mut self, o: Self) {
+        self.correct += o.correct;
+        self.incorrect += o.incorrect;
+        self.extra += o.extra;
+        self.missed += o.missed;
+    }
+}
+
+/// the words from the top visible line to the end of what's been generated, and the
+/// caret somewhere in among them. words scrolled off the top are kept aside, done with,
+/// the latest `KEPT_WORDS` of them whole.
+#[derive(Debug, Clone, Default)]
+pub struct TypedText {
+    words: VecDeque<Word>,
+    /// the word being typed, counted from the top line. every word before it is closed.
+    current: usize,
+    scrolled_off: VecDeque<Word>,
+    /// what the scrolled off words scored, so it isn't added up again on every keystroke
+    scrolled: CharStats,
+    /// scrolled words let go of once there were more than `KEPT_WORDS`, and their letters
+    dropped: usize,
+    dropped_chars: usize,
+}
+
+impl TypedText {
+    pub fn new<'a>(words: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut text = Self::default();
+        for word in words {
+            text.push_word(word);
+        }
+        text
+    }
+
+    pub fn push_word(&mut self, target: &str) {
+        self.words.push_back(Word::new(target));
+    }
+
+    pub fn words(&self) -> &VecDeque<Word> {
+        &self.words
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    pub fn current_index(&self) -> usize {
+        self.current
+    }
+
+    pub fn current(&self) -> Option<&Word> {
+        self.words.get(self.current)
+    }
+
+    pub fn is_on_last_word(&self) -> bool {
+        self.current + 1 >= self.words.len()
+    }
+
+    /// words after the one being typed
+    pub fn pending(&self) -> usize {
+        self.words.len().saturating_sub(self.current + 1)
+    }
+
+    /// words gone off the top of the screen
+    pub fn scrolled_words(&self) -> usize {
+        self.dropped + self.scrolled_off.len()
+    }
+
+    /// words from the start of the test that are no longer kept, and how many letters they had
+    pub fn dropped(&self) -> (usize, usize) {
+        (self.dropped, self.dropped_chars)
+    }
+
+    /// every word of the test still kept, in order, scrolled ones first. the first is
+    /// word `dropped().0` of the test.
+    pub fn all_words(&self) -> impl Iterator<Item = &Word> {
+        self.scrolled_off.iter().chain(&self.words)
+    }
+
+    /// words closed with a space since the test began, scrolled ones included
+    pub fn closed_words(&self) -> usize {
+        self.scrolled_words() + self.current
+    }
+
+    /// closed words, plus the current one once it has a letter in it
+    pub fn started_words(&self) -> usize {
+        self.closed_words() + self.current().is_some_and(|w| !w.typed.is_empty()) as usize
+    }
+
+    /// the caret is at the end of the last word
+    pub fn is_fully_typed(&self) -> bool {
+        self.is_on_last_word() && self.current().is_none_or(|w| w.typed.len() >= w.target.len())
+    }
+
+    /// the last word is typed and right, near enough that dashes and quotes don't matter
+    pub fn is_complete(&self) -> bool {
+        self.is_on_last_word() && self.current().is_some_and(Word::is_correct)
+    }
+
+    pub fn type_char(&mut self, c: char) {
+        if let Some(word) = self.words.get_mut(self.current) {
+            word.typed.push(c);
+        }
+    }
+
+    /// closes the current word and moves on. false on the last word, which can't be closed.
+    pub fn type_space(&mut self) -> bool {
+        if self.current + 1 >= self.words.len() {
+            return false;
+        }
+        self.words[self.current].closed = true;
+        self.current += 1;
+        true
+    }
+
+    /// takes back the last keystroke, reopening the previous word when that was a space
+    pub fn backspace(&mut self) -> Option<char> {
+        if let Some(c) = self.words.get_mut(self.current).and_then(|w| w.typed.pop()) {
+            return Some(c);
+        }
+        if self.current == 0 {
+            return None;
+        }
+        self.current -= 1;
+        self.words[self.current].closed = false;
+        Some(' ')
+    }
+
+    /// the last char typed, if any, and the word it went into
+    pub fn last_typed(&self) -> Option<(char, &Word)> {
+        match self.current() {
+            Some(w) if !w.typed.is_empty() => Some((*w.typed.last()?, w)),
+            _ if self.current > 0 => Some((' ', &self.words[self.current - 1])),
+            _ => None,
+        }
+    }
+
+    /// the next char to type to stay on track, or None once the last word is done
+    pub fn next_char(&self) -> Option<char> {
+        let word = self.current()?;
+        match word.target.get(word.typed.len()) {
+            Some(&c) => Some(c),
+            None if !self.is_on_last_word() => Some(' '),
+            None => None,
+        }
+    }
+
+    /// where the caret is, in columns from the start of the first word. spaces count one.
+    pub fn cursor(&self) -> usize {
+        let before: usize = self.words.iter().take(self.current).map(|w| w.width() + 1).sum();
+        before + self.current().map_or(0, |w| w.typed.len())
+    }
+
+    /// scrolls up to `count` closed words off the top, keeping their score
+    pub fn scroll(&mut self, count: usize) {
+        let count = count.min(self.current);
+        for word in self.words.drain(..count) {
+            self.scrolled += word.stats(true);
+            self.scrolled_off.push_back(word);
+        }
+        while self.scrolled_off.len() > KEPT_WORDS {
+            let Some(word) = self.scrolled_off.pop_front() else { break };
+            self.dropped += 1;
+            self.dropped_chars += word.target.len();
+        }
+        self.current -= count;
+    }
+
+    /// fills in the rest of the current word, as long as what's typed of it is right
+    pub fn complete_current(&mut self) {
+        if let Some(word) = self.words.get_mut(self.current) {
+            if !word.typed.is_empty() && !word.has_typo() {
+                word.typed = word.target.clone();
+            }
+        }
+    }
+
+    /// cuts the text off at the caret, for a test that ended partway through a word
+    pub fn truncate_at_cursor(&mut self) {
+        self.words.truncate(self.current + 1);
+        if let Some(word) = self.words.get_mut(self.current) {
+            if word.typed.is_empty() {
+                self.words.pop_back();
+            } else if word.typed.len() < word.target.len() {
+                word.target.truncate(word.typed.len());
+            }
+        }
+    }
+
+    /// every word scored, scrolled ones included. words before the last have a space after them.
+    pub fn stats(&self) -> CharStats {
+        let last = self.words.len().saturating_sub(1);
+        let mut stats = self.scrolled;
+        for (i, word) in self.words.iter().enumerate() {
+            stats += word.stats(word.closed || i < last);
+        }
+        stats
+    }
+
+    /// correct chars so far: closed words that came out right, spaces included, plus the
+    /// current word if it's right up to the caret
+    pub fn live_correct(&self) -> usize {
+        let closed: usize = self.words.iter().take(self.current).map(|w| w.stats(true).correct).sum();
+        let current = self.current().filter(|w| !w.has_typo()).map_or(0, |w| w.typed.len());
+        self.scrolled.correct + closed + current
+    }
+
+    /// word ranges of each line, wrapped at `width` columns. stops once the line after the
+    /// caret's is done and at least `min_lines` are in, so the rest of the queue isn't walked.
+    pub fn wrap(&self, width: usize, min_lines: usize) -> Vec<Range<usize>> {
+        let mut lines = Vec::new();
+        let mut start = 0;
+        let mut line_width = 0;
+        for (i, word) in self.words.iter().enumerate() {
+            let w = word.width();
+            if i > start && line_width + 1 + w > width {
+                lines.push(start..i);
+                if lines.len() >= min_lines && start > self.current {
+                    return lines;
+                }
+                start = i;
+                line_width = w;
+            } else {
+                line_width += if i > start { 1 + w } else { w };
+            }
+        }
+        if start < self.words.len() {
+            lines.push(start..self.words.len());
+        }
+        lines
+    }
+
+    /// columns a line takes up, not counting the space that ends it
+    pub fn line_width(&self, line: &Range<usize>) -> usize {
+        let words = line.len();
+        self.words.range(line.clone()).map(Word::width).sum::<usize>() + words.saturating_sub(1)
+    }
+}
@@ -0,0 +1,114 @@
+use crate::app::App;
+
+/// what can be done from the results screen. the footer hints are built from whichever of
+/// these the finished test actually offers, so they never name a key that does nothing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResultsAction {
+    Next,
+    Retry,
+    Drill,
+    Tabs,
+    SaveCard,
+    Quit,
+}
+
+impl ResultsAction {
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Next => "tab",
+            Self::Retry => "r",
+            Self::Drill => "d",
+            Self::Tabs => "←/→",
+            Self::SaveCard => "s",
+            Self::Quit => "esc",
+        }
+    }
+
+    /// "next test", or what next means in a run of several tests
+    pub fn label(self, app: &App) -> &'static str {
+        match self {
+            Self::Next if app.config.kids => "go again",
+            Self::Next if app.drill.is_some() => if app.can_drill() { "next round" } else { "done" },
+            Self::Next => match &app.sequence {
+                Some(step) if step.index + 1 < step.total => "next test",
+                Some(_) => "finish",
+                None => "next test",
+            },
+            Self::Retry => "retry same words",
+            Self::Drill => "drill mistakes",
+            Self::Tabs => "tabs",
+            Self::SaveCard => "save card",
+            Self::Quit if app.config.kids => "all done",
+            Self::Quit => "quit",
+        }
+    }
+
+    /// one word, for narrow terminals
+    pub fn short_label(self, app: &App) -> &'static str {
+        match self {
+            Self::Next if app.sequence.is_some() => "next",
+            Self::Next if app.config.kids => "again",
+            Self::Next if app.drill.is_some() => if app.can_drill() { "round" } else { "done" },
+            Self::Next => "next",
+            Self::Retry => "retry",
+            Self::Drill => "drill",
+            Self::Tabs => "tabs",
+            Self::SaveCard => "save",
+            Self::Quit => "quit",
+        }
+    }
+
+    /// the ones that go first when the footer doesn't fit
+    fn is_optional(self) -> bool {
+        matches!(self, Self::Tabs | Self::SaveCard)
+    }
+}
+
+/// the actions a finished test offers, in footer order. a failed test has no tabs or card,
+/// the linear layout shows everything at once instead of in tabs, and kids only get to go
+/// again or stop. a test with mistakes can be drilled, unless it's a drill round already.
+pub fn results_actions(app: &App) -> Vec<ResultsAction> {
+    use ResultsAction::*;
+    if app.config.kids {
+        return vec![Next, Quit];
+    }
+    let mut actions = if app.test.fail_reason.is_some() {
+        vec![Next, Retry, Quit]
+    } else if app.config.accessibility.is_linear() {
+        vec![Next, Retry, SaveCard, Quit]
+    } else {
+        vec![Next, Retry, Tabs, SaveCard, Quit]
+    };
+    if app.drill.is_none() && app.can_drill() {
+        actions.insert(2, Drill);
+    }
+    actions
+}
+
+/// as many of the results actions as fit in `width`, with their full labels if there's
+/// room, then their short ones, then just the keys
+pub fn results_footer(app: &App, width: usize) -> String {
+    let mut actions = results_actions(app);
+    let fits = |s: &String| s.chars().count() <= width;
+
+    loop {
+        let full = join(&actions, "  |  ", |a| format!("{}: {}", a.key(), a.label(app)));
+        if fits(&full) { return full; }
+        match actions.iter().rposition(|a| a.is_optional()) {
+            Some(i) => { actions.remove(i); }
+            None => break,
+        }
+    }
+    let short = join(&actions, "  |  ", |a| format!("{}: {}", a.key(), a.short_label(app)));
+    if fits(&short) { return short; }
+    join(&actions, "  |  ", |a| a.key().to_string())
+}
+
+/// every action with its full label, for the linear layout, which has no width to fit
+pub fn results_hint(app: &App) -> String {
+    join(&results_actions(app), ", ", |a| format!("{}: {}", a.key(), a.label(app)))
+}
+
+fn join(actions: &[ResultsAction], sep: &str, show: impl Fn(&ResultsAction) -> String) -> String {
+    actions.iter().map(show).collect::<Vec<_>>().join(sep)
+}
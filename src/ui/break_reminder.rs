@@ -0,0 +1,68 @@
+use crate::app::App;
+use crate::ui::utils::hex_to_rgb;
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// a box over the results screen suggesting a break, with how the session has gone so far
+pub fn draw(f: &mut Frame, app: &App) {
+    let theme = &app.config.theme;
+    let bg   = hex_to_rgb(&theme.bg);
+    let main = Style::default().fg(hex_to_rgb(&theme.main));
+    let text = Style::default().fg(hex_to_rgb(&theme.text));
+    let sub  = Style::default().fg(hex_to_rgb(&theme.sub));
+    let session = &app.session;
+
+    let minutes = (session.since_break / 60.0).round().max(1.0);
+    let mut stats = vec![
+        Span::styled("tests ", sub),
+        Span::styled(session.results.len().to_string(), text),
+    ];
+    if let (Some(avg), Some(best), Some(acc)) = (session.average_wpm(), session.best_wpm(), session.average_accuracy()) {
+        stats.extend([
+            Span::styled("   avg ", sub),
+            Span::styled(format!("{:.0} wpm", avg), text),
+            Span::styled("   best ", sub),
+            Span::styled(format!("{:.0} wpm", best), text),
+            Span::styled("   acc ", sub),
+            Span::styled(format!("{:.0}%", acc), text),
+        ]);
+    }
+    let lines = vec![
+        Line::styled("time for a break", main.add_modifier(Modifier::BOLD)),
+        Line::default(),
+        Line::styled(format!("you've been typing for {:.0} minute{}", minutes, if minutes == 1.0 { "" } else { "s" }), text),
+        Line::default(),
+        Line::from(stats),
+        Line::default(),
+        Line::styled("stretch your hands, rest your eyes", sub),
+        Line::styled("any key to carry on", sub.add_modifier(Modifier::DIM)),
+    ];
+
+    let area = f.area();
+    let w = 52.min(area.width);
+    let h = (lines.len() as u16 + 4).min(area.height);
+    let modal = Rect::new(
+        area.x + area.width.saturating_sub(w) / 2,
+        area.y + area.height.saturating_sub(h) / 2,
+        w,
+        h,
+    );
+    f.render_widget(Clear, modal);
+    f.render_widget(
+        Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(main)
+                    .style(Style::default().bg(bg))
+                    .padding(ratatui::widgets::Padding::vertical(1)),
+            ),
+        modal,
+    );
+}
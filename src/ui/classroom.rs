@@ -0,0 +1,81 @@
+use crate::classroom::Roster;
+use crate::config::Theme;
+use crate::ui::utils::hex_to_rgb;
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+    Frame,
+};
+
+const COLUMNS: [(&str, usize); 5] = [("tests", 7), ("wpm", 7), ("accuracy", 10), ("passed", 8), ("best", 7)];
+
+/// the teacher's dashboard: everyone's latest result, a failed one in the error color.
+/// `scroll` is how many students are scrolled off the top.
+pub fn draw(f: &mut Frame, theme: &Theme, roster: &Roster, address: Option<&str>, scroll: usize) {
+    let bg_color   = hex_to_rgb(&theme.bg);
+    let sub_color  = hex_to_rgb(&theme.sub);
+    let main_color = hex_to_rgb(&theme.main);
+    let text_color = hex_to_rgb(&theme.text);
+    let err_color  = hex_to_rgb(&theme.error);
+
+    f.render_widget(Block::default().style(Style::default().bg(bg_color)), f.area());
+
+    let name_w = roster.students.iter().map(|s| s.name.chars().count()).max().unwrap_or(0).max(8) + 2;
+
+    let listening = match address {
+        Some(address) => format!("students set server = \"{}\" under [classroom]", address),
+        None => "waiting for results".to_string(),
+    };
+    let mut lines = vec![
+        Line::styled("classroom", Style::default().fg(main_color).add_modifier(Modifier::BOLD)).centered(),
+        Line::styled(listening, Style::default().fg(sub_color)).centered(),
+        Line::default(),
+    ];
+
+    let mut header = vec![Span::styled(format!("{:<name_w$}", "student"), Style::default().fg(sub_color))];
+    header.extend(COLUMNS.iter().map(|&(label, w)| Span::styled(format!("{:>w$}", label), Style::default().fg(sub_color))));
+    lines.push(Line::from(header));
+
+    let area = f.area();
+    // title, address, gap, header, then the footer and the gap above it
+    let room = (area.height as usize).saturating_sub(lines.len() + 2);
+    let scroll = scroll.min(roster.students.len().saturating_sub(room));
+    for s in roster.students.iter().skip(scroll).take(room) {
+        let latest = &s.latest;
+        let result = if latest.passed { Style::default().fg(text_color) } else { Style::default().fg(err_color) };
+        let values = [
+            s.tests.to_string(),
+            format!("{:.0}", latest.wpm),
+            format!("{:.1}%", latest.accuracy),
+            if latest.passed { "yes" } else { "no" }.to_string(),
+            if s.passed > 0 { format!("{:.0}", s.best_wpm) } else { "-".to_string() },
+        ];
+        let mut spans = vec![Span::styled(format!("{:<name_w$}", s.name), Style::default().fg(main_color))];
+        for (i, (value, &(_, w))) in values.into_iter().zip(&COLUMNS).enumerate() {
+            let style = if (1..=3).contains(&i) { result } else { Style::default().fg(text_color) };
+            spans.push(Span::styled(format!("{:>w$}", value), style));
+        }
+        lines.push(Line::from(spans));
+    }
+    if roster.students.is_empty() {
+        lines.push(Line::styled("no results yet", Style::default().fg(sub_color)).centered());
+    }
+
+    lines.push(Line::default());
+    lines.push(Line::styled(
+        format!("{} students, {} results  ·  ↑/↓: scroll  esc: quit", roster.students.len(), roster.results),
+        Style::default().fg(sub_color),
+    ).centered());
+
+    let w = (lines.iter().map(Line::width).max().unwrap_or(0) as u16).min(area.width);
+    let h = (lines.len() as u16).min(area.height);
+    let area = Rect::new(
+        area.x + area.width.saturating_sub(w) / 2,
+        area.y + area.height.saturating_sub(h) / 2,
+        w,
+        h,
+    );
+    f.render_widget(Paragraph::new(lines), area);
+}
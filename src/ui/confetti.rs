@@ -0,0 +1,37 @@
+use crate::config::Theme;
+use crate::ui::utils::hex_to_rgb;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use ratatui::Frame;
+
+const PARTICLES: usize = 60;
+const GLYPHS: [char; 5] = ['*', '+', '•', 'o', '·'];
+const GRAVITY: f64 = 60.0; // rows per second squared
+
+/// confetti over whatever's already drawn. `t` runs 0.0 to 1.0 over the celebration.
+/// the same seed gives the same burst, so redraws don't make it flicker.
+pub fn draw(f: &mut Frame, theme: &Theme, t: f64, seed: u64) {
+    let area = f.area();
+    if area.width == 0 || area.height == 0 { return; }
+
+    let colors = [&theme.main, &theme.caret, &theme.text, &theme.error].map(|c| hex_to_rgb(c));
+    let secs = t * 1.2;
+    let mut rng = StdRng::seed_from_u64(seed);
+    let buf = f.buffer_mut();
+
+    for _ in 0..PARTICLES {
+        // everything launches from the bottom edge and arcs back down
+        let x0 = rng.random_range(0.0..area.width as f64);
+        let vx = rng.random_range(-12.0..12.0);
+        let vy = rng.random_range(-45.0..-20.0);
+        let glyph = GLYPHS[rng.random_range(0..GLYPHS.len())];
+        let color = colors[rng.random_range(0..colors.len())];
+
+        let x = x0 + vx * secs;
+        let y = area.height as f64 + vy * secs + 0.5 * GRAVITY * secs * secs;
+        if x < 0.0 || y < 0.0 || x >= area.width as f64 || y >= area.height as f64 { continue; }
+
+        if let Some(cell) = buf.cell_mut((area.x + x as u16, area.y + y as u16)) {
+            cell.set_char(glyph).set_fg(color);
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use crate::app::StepSummary;
+use crate::config::Theme;
+use crate::ui::utils::hex_to_rgb;
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+    Frame,
+};
+
+const LABEL_W: usize = 12;
+
+/// two players' results side by side, the winner's column in the main color
+pub fn draw(f: &mut Frame, theme: &Theme, players: &[String], results: &[StepSummary]) {
+    let bg_color   = hex_to_rgb(&theme.bg);
+    let sub_color  = hex_to_rgb(&theme.sub);
+    let main_color = hex_to_rgb(&theme.main);
+    let text_color = hex_to_rgb(&theme.text);
+    let err_color  = hex_to_rgb(&theme.error);
+
+    f.render_widget(Block::default().style(Style::default().bg(bg_color)), f.area());
+
+    let winner = match results {
+        [a, b] if a.beats(b) => Some(0),
+        [a, b] if b.beats(a) => Some(1),
+        _ => None,
+    };
+    let column_w = players.iter().map(|p| p.chars().count()).max().unwrap_or(0).max(8) + 2;
+
+    let cell = |i: usize, value: String, failed: bool| {
+        let style = if failed {
+            Style::default().fg(err_color)
+        } else if winner == Some(i) {
+            Style::default().fg(main_color).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(text_color)
+        };
+        Span::styled(format!("{:>w$}", value, w = column_w), style)
+    };
+    let row = |label: &str, value: &dyn Fn(&StepSummary) -> String| {
+        let mut spans = vec![Span::styled(format!("{:<LABEL_W$}", label), Style::default().fg(sub_color))];
+        for (i, r) in results.iter().enumerate() {
+            spans.push(cell(i, value(r), false));
+        }
+        Line::from(spans)
+    };
+
+    let mut names = vec![Span::raw(" ".repeat(LABEL_W))];
+    names.extend(players.iter().enumerate().map(|(i, p)| cell(i, p.clone(), false)));
+    let mut wpm = vec![Span::styled(format!("{:<LABEL_W$}", "wpm"), Style::default().fg(sub_color))];
+    for (i, r) in results.iter().enumerate() {
+        wpm.push(if r.failed { cell(i, "failed".to_string(), true) } else { cell(i, format!("{:.0}", r.wpm), false) });
+    }
+
+    let verdict = match winner {
+        Some(i) => format!("{} wins!", players[i]),
+        None => "it's a tie".to_string(),
+    };
+    let lines = vec![
+        Line::styled("hotseat", Style::default().fg(main_color).add_modifier(Modifier::BOLD)).centered(),
+        Line::default(),
+        Line::from(names),
+        Line::from(wpm),
+        row("raw", &|r| format!("{:.0}", r.raw_wpm)),
+        row("accuracy", &|r| format!("{:.1}%", r.accuracy)),
+        row("consistency", &|r| format!("{:.0}%", r.consistency)),
+        row("time", &|r| format!("{:.1}s", r.time)),
+        Line::default(),
+        Line::styled(verdict, Style::default().fg(main_color).add_modifier(Modifier::BOLD)).centered(),
+        Line::styled("press any key to exit", Style::default().fg(sub_color)).centered(),
+    ];
+
+    let area = f.area();
+    let w = ((LABEL_W + column_w * results.len()) as u16).min(area.width);
+    let h = (lines.len() as u16).min(area.height);
+    let area = Rect::new(
+        area.x + area.width.saturating_sub(w) / 2,
+        area.y + area.height.saturating_sub(h) / 2,
+        w,
+        h,
+    );
+    f.render_widget(Paragraph::new(lines), area);
+}
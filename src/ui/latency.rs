@@ -0,0 +1,39 @@
+use crate::config::Theme;
+use crate::ui::utils::hex_to_rgb;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Paragraph},
+    Frame,
+};
+
+/// one prompt of `typa latency`: the key to press and how far along we are
+pub fn draw(f: &mut Frame, theme: &Theme, prompt: char, round: usize, rounds: usize, last_ms: Option<f64>) {
+    let sub = Style::default().fg(hex_to_rgb(&theme.sub));
+    let main = Style::default().fg(hex_to_rgb(&theme.main)).add_modifier(Modifier::BOLD);
+
+    f.render_widget(Block::default().style(Style::default().bg(hex_to_rgb(&theme.bg))), f.area());
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Fill(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+        ])
+        .split(f.area());
+
+    let center = |text: Line<'static>| Paragraph::new(text).alignment(Alignment::Center);
+    f.render_widget(center(Line::styled("press the key shown", sub)), rows[1]);
+    f.render_widget(center(Line::styled(prompt.to_string(), main)), rows[3]);
+    let progress = match last_ms {
+        Some(ms) => format!("{}/{}  last {:.1}ms  esc: stop", round, rounds, ms),
+        None => format!("{}/{}  esc: stop", round, rounds),
+    };
+    f.render_widget(center(Line::styled(progress, sub)), rows[5]);
+}
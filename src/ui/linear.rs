@@ -0,0 +1,94 @@
+use crate::app::App;
+use crate::models::AppState;
+use crate::ui::test::{live_wpm_text, status_text, text_lines};
+use crate::ui::utils::hex_to_rgb;
+use ratatui::{
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Paragraph, Wrap},
+    Frame,
+};
+
+/// the accessible layout: everything in one column, top to bottom, in reading order.
+/// no centering, overlays or charts, so a screen reader walks it like a document.
+pub fn draw(f: &mut Frame, app: &App) {
+    let sub = Style::default().fg(hex_to_rgb(&app.config.theme.sub));
+    let main = Style::default().fg(hex_to_rgb(&app.config.theme.main)).add_modifier(Modifier::BOLD);
+
+    let mut lines: Vec<Line> = vec![Line::styled(format!("typa: {}", app.test_type_label()), main)];
+    if let Some(step) = &app.sequence {
+        lines.push(Line::styled(format!("{} {} of {}", step.name, step.index + 1, step.total), sub));
+    }
+    lines.push(Line::default());
+
+    if app.test.state == AppState::Finished {
+        results(app, &mut lines, main, sub);
+    } else {
+        lines.push(Line::styled(status_text(app), main));
+        if let Some(wpm) = live_wpm_text(app) {
+            lines.push(Line::styled(wpm, main));
+        }
+        for racer in app.racers() {
+            lines.push(Line::styled(
+                format!("{}: {:.0} words, {:.0} wpm", racer.name, racer.words_done, racer.wpm),
+                sub,
+            ));
+        }
+        lines.push(Line::default());
+        lines.extend(text_lines(app));
+        lines.push(Line::default());
+        lines.push(Line::styled("tab: restart, esc: quit", sub));
+    }
+
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), f.area());
+}
+
+fn results(app: &App, lines: &mut Vec<Line>, main: Style, sub: Style) {
+    let t = &app.test;
+    if t.break_due {
+        let minutes = (app.session.since_break / 60.0).round().max(1.0);
+        lines.push(Line::styled(format!("time for a break: {:.0} minutes of typing. any key to carry on.", minutes), main));
+        if let Some(summary) = app.session.summary() {
+            lines.push(Line::styled(format!("this session: {}", summary), sub));
+        }
+        lines.push(Line::default());
+    }
+    if let Some(reason) = &t.fail_reason {
+        lines.push(Line::styled(format!("test failed: {}", reason.describe()), main));
+    } else if t.is_new_best {
+        lines.push(Line::styled("new personal best", main));
+    } else if let Some(rank) = &t.rank {
+        lines.push(Line::styled(rank.describe(), main));
+    }
+
+    let (correct, incorrect, extra, missed) = app.resolved_char_stats();
+    let stats = [
+        format!("wpm: {:.0}", t.final_wpm),
+        format!("raw wpm: {:.0}", t.final_raw_wpm),
+        app.adjusted_wpm().map(|w| format!("adjusted wpm: {:.0}", w)).unwrap_or_default(),
+        format!("accuracy: {:.1}%", t.final_accuracy),
+        t.final_settled_accuracy.map(|a| format!("settled accuracy: {:.1}%", a)).unwrap_or_default(),
+        match t.final_score {
+            Some(score) if t.is_new_best_score => format!("score: {:.0}, new best", score),
+            Some(score) => format!("score: {:.0}", score),
+            None => String::new(),
+        },
+        format!("consistency: {:.0}%", t.final_consistency),
+        t.final_difficulty.map(|d| format!("difficulty: {:.2}", d)).unwrap_or_default(),
+        format!("time: {:.1}s", t.final_time),
+        format!("characters: {} correct, {} incorrect, {} extra, {} missed", correct, incorrect, extra, missed),
+        format!("corrected errors: {}", app.error_counts().corrected),
+        format!("backspaces: {}, {:.1} per 100 keystrokes", t.backspaces, app.backspace_ratio()),
+        if app.config.strict_space { format!("stray spaces: {}", t.stray_spaces) } else { String::new() },
+    ];
+    lines.extend(stats.into_iter().filter(|s| !s.is_empty()).map(|s| Line::styled(s, main)));
+    if !t.quote_sources.is_empty() {
+        lines.push(Line::styled(format!("source: {}", t.quote_source_label()), sub));
+        if let Some(license) = t.quote_license_label() {
+            lines.push(Line::styled(format!("license: {}", license), sub));
+        }
+    }
+
+    lines.push(Line::default());
+    lines.push(Line::styled(crate::ui::actions::results_hint(app), sub));
+}
@@ -1,5 +1,16 @@
+pub mod actions;
+pub mod break_reminder;
+pub mod classroom;
+pub mod confetti;
+pub mod hotseat;
+pub mod latency;
+pub mod linear;
+pub mod racers;
 pub mod results;
+pub mod summary;
+pub mod taskbar;
 pub mod test;
+pub mod title;
 pub mod utils;
 
 use crate::app::App;
@@ -18,8 +29,16 @@ pub fn render(f: &mut Frame, app: &App) {
         f.area(),
     );
 
-    if app.test.state == AppState::Finished {
+    if app.config.accessibility.is_linear() {
+        linear::draw(f, app);
+    } else if app.test.state == AppState::Finished {
         results::draw(f, app);
+        if let Some(t) = app.celebration() {
+            confetti::draw(f, &app.config.theme, t, app.test.final_wpm.to_bits());
+        }
+        if app.test.break_due {
+            break_reminder::draw(f, app);
+        }
     } else {
         test::draw(f, app);
     }
@@ -0,0 +1,59 @@
+use crate::config::Theme;
+use crate::ui::utils::hex_to_rgb;
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+/// one row of the progress widget. the player's own row is `is_you`.
+#[derive(Debug, Clone)]
+pub struct Racer {
+    pub name: String,
+    pub words_done: f64,
+    pub wpm: f64,
+    pub is_you: bool,
+}
+
+/// rows plus a blank line underneath to keep the bars off the text
+pub fn height(racers: &[Racer]) -> u16 {
+    if racers.is_empty() { 0 } else { racers.len() as u16 + 1 }
+}
+
+/// one bar per racer. `total_words` is the finish line; without one (time mode)
+/// the leader's bar is full and everyone else is drawn relative to them.
+pub fn draw(f: &mut Frame, area: Rect, racers: &[Racer], total_words: Option<f64>, theme: &Theme) {
+    let main = hex_to_rgb(&theme.main);
+    let sub  = hex_to_rgb(&theme.sub);
+    let text = hex_to_rgb(&theme.text);
+
+    let leader = racers.iter().map(|r| r.words_done).fold(0.0_f64, f64::max);
+    let scale = total_words.unwrap_or(leader).max(1.0);
+
+    let name_w = racers.iter().map(|r| r.name.chars().count()).max().unwrap_or(0) + 2;
+    let wpm_w = 9;
+    let bar_w = (area.width as usize).saturating_sub(name_w + wpm_w);
+
+    for (row, racer) in racers.iter().enumerate().take(area.height as usize) {
+        let frac = (racer.words_done / scale).clamp(0.0, 1.0);
+        let filled = (frac * bar_w as f64).round() as usize;
+
+        let name_style = if racer.is_you {
+            Style::default().fg(text).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(sub)
+        };
+        let line = Line::from(vec![
+            Span::styled(format!("{:<w$}", racer.name, w = name_w), name_style),
+            Span::styled("━".repeat(filled), Style::default().fg(if racer.is_you { main } else { text })),
+            Span::styled("─".repeat(bar_w - filled), Style::default().fg(sub).add_modifier(Modifier::DIM)),
+            Span::styled(format!("{:>5.0} wpm", racer.wpm), Style::default().fg(sub)),
+        ]);
+        f.render_widget(
+            Paragraph::new(line),
+            Rect::new(area.x, area.y + row as u16, area.width, 1),
+        );
+    }
+}
@@ -1,9 +1,12 @@
-use crate::app::App;
-use crate::models::{Mode, QuoteSelector};
-use crate::ui::utils::{hex_to_rgb, get_quote_length_category, render_header, render_footer};
+use crate::app::{App, KeyTally, VISIBLE_LINES};
+use crate::keyboard::{self, KeyCap};
+use crate::models::{Mode, ResultsTab};
+use crate::text::Word;
+use crate::ui::test::{render_lines, typed_word_count, word_spans, TextStyles};
+use crate::ui::utils::{hex_to_rgb, render_header, render_footer};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::Style,
+    style::{Modifier, Style},
     symbols,
     text::{Line, Span},
     widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph},
@@ -40,82 +43,413 @@ pub fn draw(f: &mut Frame, app: &App) {
 
     let area = horizontal_layout[1];
 
-    let available_height = area.height;
+    if let Some(reason) = &app.test.fail_reason {
+        draw_failed(f, app, area, &reason.describe());
+        render_footer(f, app);
+        return;
+    }
+    if app.config.kids {
+        draw_kids(f, app, area);
+        render_footer(f, app);
+        return;
+    }
+
+    let bg_color    = hex_to_rgb(&app.config.theme.bg);
+    let sub_color   = hex_to_rgb(&app.config.theme.sub);
+    let main_color  = hex_to_rgb(&app.config.theme.main);
+    let error_color = hex_to_rgb(&app.config.theme.error);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ])
+        .split(area);
 
-    let layout_mode = if available_height >= 25 {
+    draw_test_type_header(f, app, rows[0], sub_color, main_color);
+    draw_tab_bar(f, app, rows[1], sub_color, main_color);
+    let sending = app.uploads.is_pending().then(|| "sending the result…".to_string());
+    if let Some(notice) = app.test.card_notice.as_ref().or(app.classroom_notice.as_ref()).or(sending.as_ref()) {
+        f.render_widget(
+            Paragraph::new(Line::styled(notice.clone(), Style::default().fg(sub_color))).alignment(Alignment::Center),
+            rows[2],
+        );
+    }
+
+    let body = rows[3];
+    match app.test.results_tab {
+        ResultsTab::Summary => draw_summary(f, app, body, sub_color, main_color),
+        ResultsTab::Chart => draw_chart_tab(f, app, body, bg_color, sub_color, main_color, error_color),
+        ResultsTab::Words => draw_words(f, app, body, sub_color, main_color),
+        ResultsTab::Keys => draw_keys(f, app, body, sub_color, main_color, error_color),
+        ResultsTab::Hands => draw_hands(f, app, body, sub_color, main_color),
+        ResultsTab::Replay => draw_replay(f, app, body, sub_color, main_color),
+    }
+
+    render_footer(f, app);
+}
+
+/// the kids' results: stars for accuracy, a word of praise, and the numbers in plain words
+fn draw_kids(f: &mut Frame, app: &App, area: Rect) {
+    let sub = Style::default().fg(hex_to_rgb(&app.config.theme.sub));
+    let main = Style::default().fg(hex_to_rgb(&app.config.theme.main));
+
+    let stars = app.stars();
+    let mut star_line: Vec<Span> = Vec::new();
+    for i in 0..3 {
+        if i > 0 { star_line.push(Span::raw("   ")); }
+        star_line.push(if i < stars { Span::styled("★", main.add_modifier(Modifier::BOLD)) } else { Span::styled("☆", sub) });
+    }
+    let praise = ["keep practicing!", "good try!", "great job!", "amazing!"][stars];
+    let words = typed_word_count(app, true);
+
+    let lines = vec![
+        Line::from(star_line),
+        Line::default(),
+        Line::styled(praise, main.add_modifier(Modifier::BOLD)),
+        Line::default(),
+        Line::from(vec![
+            Span::styled("you typed ", sub),
+            Span::styled(words.to_string(), main),
+            Span::styled(if words == 1 { " word" } else { " words" }, sub),
+        ]),
+        Line::from(vec![Span::styled(format!("{:.0}%", app.test.final_accuracy), main), Span::styled(" of your keys were right", sub)]),
+        Line::from(vec![Span::styled(format!("{:.0}", app.test.final_wpm), main), Span::styled(" words a minute", sub)]),
+    ];
+    let height = (lines.len() as u16).min(area.height);
+    let top = area.y + area.height.saturating_sub(height) / 2;
+    f.render_widget(Paragraph::new(lines).alignment(Alignment::Center), Rect::new(area.x, top, area.width, height));
+}
+
+fn draw_tab_bar(
+    f: &mut Frame,
+    app: &App,
+    area: Rect,
+    sub_color: ratatui::style::Color,
+    main_color: ratatui::style::Color,
+) {
+    let mut spans = Vec::new();
+    for (i, tab) in ResultsTab::ALL.into_iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::styled("   ", Style::default().fg(sub_color)));
+        }
+        let style = if tab == app.test.results_tab {
+            Style::default().fg(main_color).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(sub_color)
+        };
+        spans.push(Span::styled(format!("{} {}", i + 1, tab.name()), style));
+    }
+    f.render_widget(Paragraph::new(Line::from(spans)).alignment(Alignment::Center), area);
+}
+
+fn draw_summary(
+    f: &mut Frame,
+    app: &App,
+    area: Rect,
+    sub_color: ratatui::style::Color,
+    main_color: ratatui::style::Color,
+) {
+    let layout_mode = if area.height >= 11 {
         LayoutMode::Full
-    } else if available_height >= 18 {
+    } else if area.height >= 8 {
         LayoutMode::Compact
     } else {
         LayoutMode::UltraCompact
     };
 
-    let content_layout = match layout_mode {
-        LayoutMode::Full => {
-            Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([
-                    Constraint::Length(1),
-                    Constraint::Length(1),
-                    Constraint::Length(7),
-                    Constraint::Length(1),
-                    Constraint::Min(12),
-                    Constraint::Length(1),
-                    Constraint::Length(3),
-                ])
-                .split(area)
-        },
-        LayoutMode::Compact => {
-            Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([
-                    Constraint::Length(1),
-                    Constraint::Length(5),
-                    Constraint::Min(8),
-                    Constraint::Length(3),
-                ])
-                .split(area)
-        },
-        LayoutMode::UltraCompact => {
-            Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([
-                    Constraint::Length(1),
-                    Constraint::Length(3),
-                    Constraint::Min(6),
-                    Constraint::Length(2),
-                ])
-                .split(area)
-        }
+    let rows = match layout_mode {
+        LayoutMode::Full => Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(7), Constraint::Length(1), Constraint::Length(3)])
+            .split(area),
+        LayoutMode::Compact => Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(5), Constraint::Length(0), Constraint::Length(3)])
+            .split(area),
+        LayoutMode::UltraCompact => Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Length(0), Constraint::Length(2)])
+            .split(area),
     };
 
-    let bg_color    = hex_to_rgb(&app.config.theme.bg);
-    let sub_color   = hex_to_rgb(&app.config.theme.sub);
-    let main_color  = hex_to_rgb(&app.config.theme.main);
-    let error_color = hex_to_rgb(&app.config.theme.error);
-
     match layout_mode {
         LayoutMode::Full => {
-            draw_test_type_header(f, app, content_layout[0], sub_color, main_color);
-            draw_full_stats_card(f, app, content_layout[2], sub_color, main_color);
-            draw_chart(f, app, content_layout[4], bg_color, sub_color, main_color, error_color, true);
-            draw_full_footer(f, app, content_layout[6], sub_color, main_color);
+            draw_full_stats_card(f, app, rows[0], sub_color, main_color);
+            draw_full_footer(f, app, rows[2], sub_color, main_color);
         },
         LayoutMode::Compact => {
-            draw_test_type_header(f, app, content_layout[0], sub_color, main_color);
-            draw_compact_stats_card(f, app, content_layout[1], sub_color, main_color);
-            draw_chart(f, app, content_layout[2], bg_color, sub_color, main_color, error_color, true);
-            draw_compact_footer(f, app, content_layout[3], sub_color, main_color);
+            draw_compact_stats_card(f, app, rows[0], sub_color, main_color);
+            draw_compact_footer(f, app, rows[2], sub_color, main_color);
         },
         LayoutMode::UltraCompact => {
-            draw_test_type_header(f, app, content_layout[0], sub_color, main_color);
-            draw_ultra_compact_stats(f, app, content_layout[1], sub_color, main_color);
-            draw_chart(f, app, content_layout[2], bg_color, sub_color, main_color, error_color, false);
-            draw_ultra_compact_footer(f, app, content_layout[3], sub_color, main_color);
+            draw_ultra_compact_stats(f, app, rows[0], sub_color, main_color);
+            draw_ultra_compact_footer(f, app, rows[2], sub_color, main_color);
         }
     }
+}
 
-    render_footer(f, app);
+fn draw_chart_tab(
+    f: &mut Frame,
+    app: &App,
+    area: Rect,
+    bg_color: ratatui::style::Color,
+    sub_color: ratatui::style::Color,
+    main_color: ratatui::style::Color,
+    error_color: ratatui::style::Color,
+) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(6), Constraint::Length(1)])
+        .split(area);
+    draw_chart(f, app, rows[0], bg_color, sub_color, main_color, error_color, area.height >= 12);
+    let hint = Line::from(vec![
+        Span::styled("h", Style::default().fg(main_color)),
+        Span::styled(" / ", Style::default().fg(sub_color)),
+        Span::styled("l", Style::default().fg(main_color)),
+        Span::styled(" to read a second off the chart", Style::default().fg(sub_color)),
+    ]);
+    f.render_widget(Paragraph::new(hint).alignment(Alignment::Center), rows[1]);
+}
+
+/// every word of the test as it was left, wrapped to the screen and scrolled with up and down
+fn draw_words(
+    f: &mut Frame,
+    app: &App,
+    area: Rect,
+    sub_color: ratatui::style::Color,
+    main_color: ratatui::style::Color,
+) {
+    let text = &app.test.text;
+    let words: Vec<&Word> = text.all_words().take(text.started_words() - text.dropped().0).collect();
+    let wrong = words.iter().filter(|w| !w.is_correct()).count();
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Length(1), Constraint::Min(0)])
+        .split(area);
+
+    let summary = Line::from(vec![
+        Span::styled(format!("{}", words.len()), Style::default().fg(main_color)),
+        Span::styled(" words  │  ", Style::default().fg(sub_color)),
+        Span::styled(format!("{}", wrong), Style::default().fg(main_color)),
+        Span::styled(" with mistakes", Style::default().fg(sub_color)),
+    ]);
+    f.render_widget(Paragraph::new(summary).alignment(Alignment::Center), rows[0]);
+
+    let styles = TextStyles::new(app, false);
+    let width = rows[2].width as usize;
+    let mut lines: Vec<Line> = Vec::new();
+    let mut spans: Vec<Span> = Vec::new();
+    let mut line_width = 0;
+    for word in words {
+        if line_width > 0 && line_width + 1 + word.width() > width {
+            lines.push(Line::from(std::mem::take(&mut spans)));
+            line_width = 0;
+        }
+        if line_width > 0 {
+            spans.push(Span::styled(" ", styles.future()));
+            line_width += 1;
+        }
+        word_spans(word, &styles, None, false, &mut spans);
+        line_width += word.width();
+    }
+    if !spans.is_empty() {
+        lines.push(Line::from(spans));
+    }
+
+    let scroll = scroll_offset(app, lines.len(), rows[2].height);
+    f.render_widget(Paragraph::new(lines).scroll((scroll, 0)), rows[2]);
+}
+
+/// how every key did, worst first
+fn draw_keys(
+    f: &mut Frame,
+    app: &App,
+    area: Rect,
+    sub_color: ratatui::style::Color,
+    main_color: ratatui::style::Color,
+    error_color: ratatui::style::Color,
+) {
+    let mut keys: Vec<(char, KeyTally)> = app.test.key_tallies.iter().map(|(&k, &t)| (k, t)).collect();
+    keys.sort_by(|(_, a), (_, b)| a.accuracy().total_cmp(&b.accuracy()).then(b.presses.cmp(&a.presses)));
+
+    // the board goes above the list when there's room for it and a few rows of the list too
+    let board = keyboard::keymap(app.config.keyboard_layout, app.config.keyboard_geometry);
+    let area = if area.height >= 14 && area.width >= keyboard::keymap_width(&board) {
+        let parts = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(6), Constraint::Min(0)])
+            .split(area);
+        draw_keymap(f, app, &board, parts[0], sub_color, main_color, error_color);
+        parts[1]
+    } else {
+        area
+    };
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Length(1), Constraint::Min(0)])
+        .split(area);
+
+    let header = format!("{:<7}{:>9}{:>9}{:>10}", "key", "presses", "missed", "accuracy");
+    f.render_widget(
+        Paragraph::new(Line::styled(header, Style::default().fg(sub_color))).alignment(Alignment::Center),
+        rows[0],
+    );
+
+    let lines: Vec<Line> = keys.iter().map(|&(key, tally)| {
+        let name = if key == ' ' { "space".to_string() } else { key.to_string() };
+        let missed = if tally.misses > 0 { error_color } else { main_color };
+        Line::from(vec![
+            Span::styled(format!("{:<7}", name), Style::default().fg(main_color)),
+            Span::styled(format!("{:>9}", tally.presses), Style::default().fg(sub_color)),
+            Span::styled(format!("{:>9}", tally.misses), Style::default().fg(missed)),
+            Span::styled(format!("{:>9.1}%", tally.accuracy()), Style::default().fg(main_color)),
+        ])
+    }).collect();
+
+    let scroll = scroll_offset(app, lines.len(), rows[2].height);
+    f.render_widget(Paragraph::new(lines).alignment(Alignment::Center).scroll((scroll, 0)), rows[2]);
+}
+
+/// the keyboard, each key in the error color if it was ever missed, the main one if it was
+/// only hit, and dimmed if it went unused. a key counts its shifted character too.
+fn draw_keymap(
+    f: &mut Frame,
+    app: &App,
+    board: &[KeyCap],
+    area: Rect,
+    sub_color: ratatui::style::Color,
+    main_color: ratatui::style::Color,
+    error_color: ratatui::style::Color,
+) {
+    let tally = |c: char| app.test.key_tallies.get(&c).copied().unwrap_or_default();
+    let edge = Style::default().fg(sub_color);
+
+    let mut rows: Vec<Vec<Span>> = vec![Vec::new(); 4];
+    let mut ends = [0u16; 4];
+    for key in board {
+        let (plain, shifted) = (tally(key.label), tally(key.shifted));
+        let color = if plain.misses + shifted.misses > 0 {
+            error_color
+        } else if plain.presses + shifted.presses > 0 {
+            main_color
+        } else {
+            sub_color
+        };
+        let row = key.row as usize;
+        rows[row].push(Span::raw(" ".repeat((key.x - ends[row]) as usize)));
+        rows[row].push(Span::styled("[", edge));
+        rows[row].push(Span::styled(key.label.to_string(), Style::default().fg(color).add_modifier(Modifier::BOLD)));
+        rows[row].push(Span::styled("]", edge));
+        ends[row] = key.x + keyboard::KEY_PITCH - 1;
+    }
+
+    // rows are drawn from a shared left edge, so the stagger survives centering
+    let width = keyboard::keymap_width(board);
+    let board_area = Rect { x: area.x + (area.width - width) / 2, width, height: 4, ..area };
+    f.render_widget(Paragraph::new(rows.into_iter().map(Line::from).collect::<Vec<_>>()), board_area);
+
+    let caption = format!("{}, {} board", app.config.keyboard_layout.name(), app.config.keyboard_geometry.name());
+    let caption_area = Rect { y: area.y + 4, height: 1, ..area };
+    f.render_widget(Paragraph::new(Line::styled(caption, edge)).alignment(Alignment::Center), caption_area);
+}
+
+/// how the typing moved between hands and fingers, and how fast each kind of key pair went
+fn draw_hands(
+    f: &mut Frame,
+    app: &App,
+    area: Rect,
+    sub_color: ratatui::style::Color,
+    main_color: ratatui::style::Color,
+) {
+    let layout = app.config.keyboard_layout;
+    let stats = keyboard::hand_stats(layout, &app.test.keystrokes);
+    let sub = Style::default().fg(sub_color);
+    let main = Style::default().fg(main_color);
+
+    if stats.total() == 0 {
+        f.render_widget(Paragraph::new(Line::styled("no key pairs to go on", sub)).alignment(Alignment::Center), area);
+        return;
+    }
+
+    let mut lines = vec![
+        Line::styled(format!("{:<14}{:>7}{:>9}{:>10}", "pair", "count", "share", "pace"), sub),
+        Line::default(),
+    ];
+    let kinds = [
+        ("alternating", &stats.alternating),
+        ("same hand", &stats.same_hand),
+        ("same finger", &stats.same_finger),
+    ];
+    for (name, tally) in kinds {
+        let pace = tally.wpm().map_or("-".to_string(), |w| format!("{:.0} wpm", w));
+        lines.push(Line::from(vec![
+            Span::styled(format!("{:<14}", name), main),
+            Span::styled(format!("{:>7}", tally.count), sub),
+            Span::styled(format!("{:>8.1}%", stats.share(tally)), main),
+            Span::styled(format!("{:>10}", pace), main),
+        ]));
+    }
+    lines.push(Line::default());
+    if let (Some(fast), Some(slow)) = (stats.alternating.wpm(), stats.same_finger.wpm()) {
+        let slower = (1.0 - slow / fast) * 100.0;
+        let note = if slower > 0.0 {
+            format!("same finger pairs went {:.0}% slower than alternating ones", slower)
+        } else {
+            "same finger pairs kept up with alternating ones".to_string()
+        };
+        lines.push(Line::styled(note, sub));
+    }
+    lines.push(Line::styled(format!("{} key pairs on {}, space left out", stats.total(), layout.name()), sub));
+
+    f.render_widget(Paragraph::new(lines).alignment(Alignment::Center), area);
+}
+
+/// the test typed out again at the speed it was typed
+fn draw_replay(
+    f: &mut Frame,
+    app: &App,
+    area: Rect,
+    sub_color: ratatui::style::Color,
+    main_color: ratatui::style::Color,
+) {
+    let Some(secs) = app.replay_position() else { return };
+    let text = app.replay_text(secs);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(VISIBLE_LINES as u16),
+        ])
+        .split(area);
+
+    let length = app.test.keystrokes.last().map_or(0.0, |&(at, _)| at);
+    let progress = Line::from(vec![
+        Span::styled(format!("{:.1}s", secs), Style::default().fg(main_color)),
+        Span::styled(format!(" of {:.1}s", length), Style::default().fg(sub_color)),
+    ]);
+    f.render_widget(Paragraph::new(progress).alignment(Alignment::Center), rows[0]);
+
+    // the window keeps the caret on its second line, like the test does
+    let lines = text.wrap(rows[2].width as usize, VISIBLE_LINES);
+    let current = text.current_index();
+    let caret_line = lines.iter().position(|l| l.contains(&current)).unwrap_or(0);
+    let top = caret_line.saturating_sub(1);
+    let shown = &lines[top..lines.len().min(top + VISIBLE_LINES)];
+    f.render_widget(Paragraph::new(render_lines(&text, shown, &TextStyles::new(app, true), None)), rows[2]);
+}
+
+/// how far down a scrolled tab is, kept to where there's still something to show
+fn scroll_offset(app: &App, lines: usize, height: u16) -> u16 {
+    let max = lines.saturating_sub(height as usize) as u16;
+    app.test.results_scroll.min(max)
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -127,7 +461,82 @@ enum LayoutMode {
 
 fn get_content_height(terminal_height: u16) -> u16 {
     let available = terminal_height.saturating_sub(5);
-    available.max(12).min(50)
+    available.clamp(12, 50)
+}
+
+// failed tests get their own card. partial numbers are shown but nothing here should look like a result
+fn draw_failed(f: &mut Frame, app: &App, area: Rect, reason: &str) {
+    let bg_color    = hex_to_rgb(&app.config.theme.bg);
+    let sub_color   = hex_to_rgb(&app.config.theme.sub);
+    let main_color  = hex_to_rgb(&app.config.theme.main);
+    let error_color = hex_to_rgb(&app.config.theme.error);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ])
+        .split(area);
+
+    draw_test_type_header(f, app, rows[0], sub_color, main_color);
+
+    let title = Line::from(Span::styled(
+        "test failed",
+        Style::default().fg(error_color).add_modifier(ratatui::style::Modifier::BOLD),
+    ));
+    f.render_widget(Paragraph::new(title).alignment(Alignment::Center), rows[2]);
+
+    let reason_line = Line::from(Span::styled(reason, Style::default().fg(sub_color)));
+    f.render_widget(Paragraph::new(reason_line).alignment(Alignment::Center), rows[3]);
+
+    let partial = Line::from(vec![
+        Span::styled("wpm ", Style::default().fg(sub_color)),
+        Span::styled(format!("{:.0}", app.test.final_wpm), Style::default().fg(main_color)),
+        Span::styled(" │ ", Style::default().fg(sub_color)),
+        Span::styled("acc ", Style::default().fg(sub_color)),
+        Span::styled(format!("{:.1}%", app.test.final_accuracy), Style::default().fg(main_color)),
+        Span::styled(" │ ", Style::default().fg(sub_color)),
+        Span::styled("raw ", Style::default().fg(sub_color)),
+        Span::styled(format!("{:.0}", app.test.final_raw_wpm), Style::default().fg(main_color)),
+        Span::styled(" │ ", Style::default().fg(sub_color)),
+        Span::styled(format!("{:.1}s", app.test.final_time), Style::default().fg(main_color)),
+    ]);
+    f.render_widget(Paragraph::new(partial).alignment(Alignment::Center), rows[5]);
+
+    let typed_words = app.test.text.started_words();
+    let progress = match &app.config.mode {
+        Mode::Time(t)  => format!("{:.1}s of {}s", app.test.final_time, t),
+        Mode::Words(w) => format!("{}/{} words", typed_words, w),
+        Mode::Quote(_) => format!("{}/{} words", typed_words, app.test.total_quote_words),
+    };
+    let progress_line = Line::from(vec![
+        Span::styled("got through ", Style::default().fg(sub_color)),
+        Span::styled(progress, Style::default().fg(main_color)),
+    ]);
+    f.render_widget(Paragraph::new(progress_line).alignment(Alignment::Center), rows[6]);
+
+    let hint = Line::from(vec![
+        Span::styled("r", Style::default().fg(main_color)),
+        Span::styled(" to retry the same words  │  ", Style::default().fg(sub_color)),
+        Span::styled("tab", Style::default().fg(main_color)),
+        Span::styled(" for a new test", Style::default().fg(sub_color)),
+    ]);
+    f.render_widget(Paragraph::new(hint).alignment(Alignment::Center), rows[8]);
+
+    // the chart is a nice-to-have here. skip it rather than squash it
+    if rows[10].height >= 8 {
+        draw_chart(f, app, rows[10], bg_color, sub_color, main_color, error_color, false);
+    }
 }
 
 fn draw_test_type_header(
@@ -137,25 +546,12 @@ fn draw_test_type_header(
     sub_color: ratatui::style::Color,
     _main_color: ratatui::style::Color,
 ) {
-    let mode_str = match &app.config.mode {
-        Mode::Time(t) => format!("time {}", t),
-        Mode::Words(w) => format!("word {}", w),
-        Mode::Quote(q) => match q {
-            QuoteSelector::Id(_) => format!("quote {}", get_quote_length_category(app.test.original_quote_length)),
-            QuoteSelector::Category(len) => {
-                let s = format!("{:?}", len).to_lowercase();
-                format!("quote {}", if s == "all" { get_quote_length_category(app.test.original_quote_length) } else { &s })
-            }
-        },
-    };
-
-    let mut type_parts = vec![mode_str, app.config.word_data.name.clone()];
-    if app.config.use_punctuation { type_parts.push("punctuation".to_string()); }
-    if app.config.use_numbers     { type_parts.push("number".to_string()); }
-
-    let header = Line::from(vec![
-        Span::styled(type_parts.join(" "), Style::default().fg(sub_color)),
+    let mut header = Line::from(vec![
+        Span::styled(app.test_type_label(), Style::default().fg(sub_color)),
     ]);
+    if let Some(d) = app.test.final_difficulty {
+        header.spans.push(Span::styled(format!("  difficulty {:.2}", d), Style::default().fg(sub_color)));
+    }
     f.render_widget(Paragraph::new(header).alignment(Alignment::Center), area);
 }
 
@@ -196,10 +592,12 @@ fn draw_full_stats_card(
     ]);
     if app.test.is_new_best {
         wpm_line.spans.push(Span::styled("  ↑ new best!", Style::default().fg(main_color)));
+    } else if let Some(rank) = &app.test.rank {
+        wpm_line.spans.push(Span::styled(format!("  {}", rank.describe()), Style::default().fg(sub_color)));
     }
     f.render_widget(Paragraph::new(wpm_line).alignment(Alignment::Center), rows[1]);
 
-    let acc_line = Line::from(vec![
+    let mut acc_line = Line::from(vec![
         Span::styled("  Accuracy: ", Style::default().fg(sub_color)),
         Span::styled(
             format!("{:.2}%", app.test.final_accuracy),
@@ -208,11 +606,40 @@ fn draw_full_stats_card(
                 .add_modifier(ratatui::style::Modifier::BOLD),
         ),
     ]);
+    if let Some(settled) = app.test.final_settled_accuracy {
+        acc_line.spans.extend([
+            Span::styled("  settled ", Style::default().fg(sub_color)),
+            Span::styled(format!("{:.2}%", settled), Style::default().fg(main_color)),
+        ]);
+    }
     f.render_widget(Paragraph::new(acc_line).alignment(Alignment::Center), rows[2]);
 
-    let secondary = Line::from(vec![
+    if let Some(score) = app.test.final_score {
+        let mut score_line = Line::from(vec![
+            Span::styled("  Score: ", Style::default().fg(sub_color)),
+            Span::styled(
+                format!("{:.0}", score),
+                Style::default().fg(main_color).add_modifier(ratatui::style::Modifier::BOLD),
+            ),
+        ]);
+        if app.test.is_new_best_score {
+            score_line.spans.push(Span::styled("  ↑ new best!", Style::default().fg(main_color)));
+        }
+        f.render_widget(Paragraph::new(score_line).alignment(Alignment::Center), rows[3]);
+    }
+
+    let mut secondary = Line::from(vec![
         Span::styled("raw ", Style::default().fg(sub_color)),
         Span::styled(format!("{:.0}", app.test.final_raw_wpm), Style::default().fg(main_color)),
+    ]);
+    if let Some(adjusted) = app.adjusted_wpm() {
+        secondary.spans.extend([
+            Span::styled("  │  ", Style::default().fg(sub_color)),
+            Span::styled("adj ", Style::default().fg(sub_color)),
+            Span::styled(format!("{:.0}", adjusted), Style::default().fg(main_color)),
+        ]);
+    }
+    secondary.spans.extend([
         Span::styled("  │  ", Style::default().fg(sub_color)),
         Span::styled("time ", Style::default().fg(sub_color)),
         Span::styled(format!("{:.1}s", app.test.final_time), Style::default().fg(main_color)),
@@ -222,23 +649,24 @@ fn draw_full_stats_card(
     ]);
     f.render_widget(Paragraph::new(secondary).alignment(Alignment::Center), rows[4]);
 
-    let (_, _, vis_raw_cor, vis_raw_inc, vis_raw_ext, vis_raw_mis) =
-        app.calculate_custom_stats_for_slice(&app.test.aligned_input, &app.test.display_string, &app.test.display_mask);
+    let (chars_cor, chars_inc, chars_ext, chars_mis) = app.resolved_char_stats();
 
-    let total_chars = app.test.st_correct + vis_raw_cor + app.test.st_incorrect + vis_raw_inc +
-                      app.test.st_extra + vis_raw_ext + app.test.st_missed + vis_raw_mis;
+    let total_chars = chars_cor + chars_inc + chars_ext + chars_mis;
 
     let acc_breakdown = Line::from(vec![
         Span::styled("correct ", Style::default().fg(sub_color)),
-        Span::styled(format!("{}", app.test.st_correct + vis_raw_cor), Style::default().fg(main_color)),
+        Span::styled(format!("{}", chars_cor), Style::default().fg(main_color)),
         Span::styled(" / ", Style::default().fg(sub_color)),
         Span::styled(format!("{}", total_chars), Style::default().fg(main_color)),
         Span::styled("  │  ", Style::default().fg(sub_color)),
         Span::styled("errors ", Style::default().fg(sub_color)),
         Span::styled(
-            format!("{}", app.test.st_incorrect + vis_raw_inc + app.test.st_extra + vis_raw_ext + app.test.st_missed + vis_raw_mis),
+            format!("{}", chars_inc + chars_ext + chars_mis),
             Style::default().fg(main_color)
         ),
+        Span::styled("  │  ", Style::default().fg(sub_color)),
+        Span::styled("corrected ", Style::default().fg(sub_color)),
+        Span::styled(format!("{}", app.error_counts().corrected), Style::default().fg(main_color)),
     ]);
     f.render_widget(Paragraph::new(acc_breakdown).alignment(Alignment::Center), rows[5]);
 
@@ -277,19 +705,46 @@ fn draw_compact_stats_card(
         ];
         if app.test.is_new_best {
             spans.push(Span::styled("  ↑ new best!", Style::default().fg(main_color)));
+        } else if let Some(rank) = &app.test.rank {
+            spans.push(Span::styled(format!("  top {:.0}%", rank.top_percent), Style::default().fg(sub_color)));
         }
         spans.extend([
             Span::styled("  │  ", Style::default().fg(sub_color)),
             Span::styled("Acc ", Style::default().fg(sub_color)),
             Span::styled(format!("{:.2}%", app.test.final_accuracy), Style::default().fg(main_color)),
         ]);
+        if let Some(settled) = app.test.final_settled_accuracy {
+            spans.extend([
+                Span::styled(" settled ", Style::default().fg(sub_color)),
+                Span::styled(format!("{:.2}%", settled), Style::default().fg(main_color)),
+            ]);
+        }
+        if let Some(score) = app.test.final_score {
+            spans.extend([
+                Span::styled("  │  ", Style::default().fg(sub_color)),
+                Span::styled("Score ", Style::default().fg(sub_color)),
+                Span::styled(format!("{:.0}", score), Style::default().fg(main_color)),
+            ]);
+            if app.test.is_new_best_score {
+                spans.push(Span::styled(" ↑", Style::default().fg(main_color)));
+            }
+        }
         spans
     });
     f.render_widget(Paragraph::new(primary).alignment(Alignment::Center), rows[0]);
 
-    let secondary = Line::from(vec![
+    let mut secondary = Line::from(vec![
         Span::styled("raw ", Style::default().fg(sub_color)),
         Span::styled(format!("{:.0}", app.test.final_raw_wpm), Style::default().fg(main_color)),
+    ]);
+    if let Some(adjusted) = app.adjusted_wpm() {
+        secondary.spans.extend([
+            Span::styled("  │  ", Style::default().fg(sub_color)),
+            Span::styled("adj ", Style::default().fg(sub_color)),
+            Span::styled(format!("{:.0}", adjusted), Style::default().fg(main_color)),
+        ]);
+    }
+    secondary.spans.extend([
         Span::styled("  │  ", Style::default().fg(sub_color)),
         Span::styled("time ", Style::default().fg(sub_color)),
         Span::styled(format!("{:.1}s", app.test.final_time), Style::default().fg(main_color)),
@@ -299,30 +754,30 @@ fn draw_compact_stats_card(
     ]);
     f.render_widget(Paragraph::new(secondary).alignment(Alignment::Center), rows[1]);
 
-    let (_, _, vis_raw_cor, vis_raw_inc, vis_raw_ext, vis_raw_mis) =
-        app.calculate_custom_stats_for_slice(&app.test.aligned_input, &app.test.display_string, &app.test.display_mask);
+    let (chars_cor, chars_inc, chars_ext, chars_mis) = app.resolved_char_stats();
 
-    let total_chars = app.test.st_correct + vis_raw_cor + app.test.st_incorrect + vis_raw_inc +
-                      app.test.st_extra + vis_raw_ext + app.test.st_missed + vis_raw_mis;
-    let errors = app.test.st_incorrect + vis_raw_inc + app.test.st_extra + vis_raw_ext + app.test.st_missed + vis_raw_mis;
+    let total_chars = chars_cor + chars_inc + chars_ext + chars_mis;
+    let errors = chars_inc + chars_ext + chars_mis;
 
     let breakdown = Line::from(vec![
-        Span::styled(format!("{}/{}", app.test.st_correct + vis_raw_cor, total_chars), Style::default().fg(main_color)),
+        Span::styled(format!("{}/{}", chars_cor, total_chars), Style::default().fg(main_color)),
         Span::styled(" correct  │  ", Style::default().fg(sub_color)),
         Span::styled(format!("{}", errors), Style::default().fg(main_color)),
-        Span::styled(" errors", Style::default().fg(sub_color)),
+        Span::styled(" errors  │  ", Style::default().fg(sub_color)),
+        Span::styled(format!("{}", app.error_counts().corrected), Style::default().fg(main_color)),
+        Span::styled(" corrected", Style::default().fg(sub_color)),
     ]);
     f.render_widget(Paragraph::new(breakdown).alignment(Alignment::Center), rows[2]);
 
     let char_detail = Line::from(vec![
         Span::styled("cor ", Style::default().fg(sub_color)),
-        Span::styled(format!("{}", app.test.st_correct + vis_raw_cor), Style::default().fg(main_color)),
+        Span::styled(format!("{}", chars_cor), Style::default().fg(main_color)),
         Span::styled(" │ inc ", Style::default().fg(sub_color)),
-        Span::styled(format!("{}", app.test.st_incorrect + vis_raw_inc), Style::default().fg(main_color)),
+        Span::styled(format!("{}", chars_inc), Style::default().fg(main_color)),
         Span::styled(" │ ext ", Style::default().fg(sub_color)),
-        Span::styled(format!("{}", app.test.st_extra + vis_raw_ext), Style::default().fg(main_color)),
+        Span::styled(format!("{}", chars_ext), Style::default().fg(main_color)),
         Span::styled(" │ mis ", Style::default().fg(sub_color)),
-        Span::styled(format!("{}", app.test.st_missed + vis_raw_mis), Style::default().fg(main_color)),
+        Span::styled(format!("{}", chars_mis), Style::default().fg(main_color)),
     ]);
     f.render_widget(Paragraph::new(char_detail).alignment(Alignment::Center), rows[3]);
 
@@ -350,11 +805,9 @@ fn draw_ultra_compact_stats(
         ])
         .split(area);
 
-    let (_, _, vis_raw_cor, vis_raw_inc, vis_raw_ext, vis_raw_mis) =
-        app.calculate_custom_stats_for_slice(&app.test.aligned_input, &app.test.display_string, &app.test.display_mask);
+    let (chars_cor, chars_inc, chars_ext, chars_mis) = app.resolved_char_stats();
 
-    let total_chars = app.test.st_correct + vis_raw_cor + app.test.st_incorrect + vis_raw_inc +
-                      app.test.st_extra + vis_raw_ext + app.test.st_missed + vis_raw_mis;
+    let total_chars = chars_cor + chars_inc + chars_ext + chars_mis;
 
     let primary = Line::from({
         let mut spans = vec![
@@ -368,6 +821,12 @@ fn draw_ultra_compact_stats(
             Span::styled(" │ ", Style::default().fg(sub_color)),
             Span::styled("acc ", Style::default().fg(sub_color)),
             Span::styled(format!("{:.1}%", app.test.final_accuracy), Style::default().fg(main_color)),
+        ]);
+        if let Some(settled) = app.test.final_settled_accuracy {
+            // settled accuracy rides along after a slash to save room
+            spans.push(Span::styled(format!("/{:.1}%", settled), Style::default().fg(main_color)));
+        }
+        spans.extend([
             Span::styled(" │ ", Style::default().fg(sub_color)),
             Span::styled("raw ", Style::default().fg(sub_color)),
             Span::styled(format!("{:.0}", app.test.final_raw_wpm), Style::default().fg(main_color)),
@@ -377,18 +836,25 @@ fn draw_ultra_compact_stats(
             Span::styled(" │ ", Style::default().fg(sub_color)),
             Span::styled(format!("{:.1}s", app.test.final_time), Style::default().fg(main_color)),
         ]);
+        if let Some(score) = app.test.final_score {
+            spans.extend([
+                Span::styled(" │ ", Style::default().fg(sub_color)),
+                Span::styled("score ", Style::default().fg(sub_color)),
+                Span::styled(format!("{:.0}", score), Style::default().fg(main_color)),
+            ]);
+        }
         spans
     });
     f.render_widget(Paragraph::new(primary).alignment(Alignment::Center), rows[0]);
 
     let char_line = Line::from(vec![
-        Span::styled(format!("{}/{}", app.test.st_correct + vis_raw_cor, total_chars), Style::default().fg(main_color)),
+        Span::styled(format!("{}/{}", chars_cor, total_chars), Style::default().fg(main_color)),
         Span::styled(" cor │ ", Style::default().fg(sub_color)),
-        Span::styled(format!("{}", app.test.st_incorrect + vis_raw_inc), Style::default().fg(main_color)),
+        Span::styled(format!("{}", chars_inc), Style::default().fg(main_color)),
         Span::styled(" inc │ ", Style::default().fg(sub_color)),
-        Span::styled(format!("{}", app.test.st_extra + vis_raw_ext), Style::default().fg(main_color)),
+        Span::styled(format!("{}", chars_ext), Style::default().fg(main_color)),
         Span::styled(" ext │ ", Style::default().fg(sub_color)),
-        Span::styled(format!("{}", app.test.st_missed + vis_raw_mis), Style::default().fg(main_color)),
+        Span::styled(format!("{}", chars_mis), Style::default().fg(main_color)),
         Span::styled(" mis", Style::default().fg(sub_color)),
     ]);
     f.render_widget(Paragraph::new(char_line).alignment(Alignment::Center), rows[1]);
@@ -397,6 +863,10 @@ fn draw_ultra_compact_stats(
     let ks_line = Line::from(vec![
         Span::styled("keystroke ", Style::default().fg(sub_color)),
         Span::styled(format!("{}/{}", app.test.live_correct_keystrokes, total_ks), Style::default().fg(main_color)),
+        Span::styled(" │ corrected ", Style::default().fg(sub_color)),
+        Span::styled(format!("{}", app.error_counts().corrected), Style::default().fg(main_color)),
+        Span::styled(" │ bksp ", Style::default().fg(sub_color)),
+        Span::styled(format!("{}", app.test.backspaces), Style::default().fg(main_color)),
     ]);
     f.render_widget(Paragraph::new(ks_line).alignment(Alignment::Center), rows[2]);
 }
@@ -417,19 +887,18 @@ fn draw_full_footer(
         ])
         .split(area);
 
-    let (_, _, vis_raw_cor, vis_raw_inc, vis_raw_ext, vis_raw_mis) =
-        app.calculate_custom_stats_for_slice(&app.test.aligned_input, &app.test.display_string, &app.test.display_mask);
+    let (chars_cor, chars_inc, chars_ext, chars_mis) = app.resolved_char_stats();
 
     let char_detail = Line::from(vec![
         Span::styled("chars: ", Style::default().fg(sub_color)),
         Span::styled("cor ", Style::default().fg(sub_color)),
-        Span::styled(format!("{}", app.test.st_correct + vis_raw_cor), Style::default().fg(main_color)),
+        Span::styled(format!("{}", chars_cor), Style::default().fg(main_color)),
         Span::styled(" │ inc ", Style::default().fg(sub_color)),
-        Span::styled(format!("{}", app.test.st_incorrect + vis_raw_inc), Style::default().fg(main_color)),
+        Span::styled(format!("{}", chars_inc), Style::default().fg(main_color)),
         Span::styled(" │ ext ", Style::default().fg(sub_color)),
-        Span::styled(format!("{}", app.test.st_extra + vis_raw_ext), Style::default().fg(main_color)),
+        Span::styled(format!("{}", chars_ext), Style::default().fg(main_color)),
         Span::styled(" │ mis ", Style::default().fg(sub_color)),
-        Span::styled(format!("{}", app.test.st_missed + vis_raw_mis), Style::default().fg(main_color)),
+        Span::styled(format!("{}", chars_mis), Style::default().fg(main_color)),
     ]);
     f.render_widget(Paragraph::new(char_detail).alignment(Alignment::Center), rows[0]);
 
@@ -440,20 +909,30 @@ fn draw_full_footer(
         100.0
     };
 
-    let keystroke_detail = Line::from(vec![
+    let mut keystroke_detail = Line::from(vec![
         Span::styled("keystrokes: ", Style::default().fg(sub_color)),
         Span::styled(format!("{}", app.test.live_correct_keystrokes), Style::default().fg(main_color)),
         Span::styled(" / ", Style::default().fg(sub_color)),
         Span::styled(format!("{}", total_ks), Style::default().fg(main_color)),
         Span::styled(format!(" ({:.1}%)", ks_acc), Style::default().fg(sub_color)),
+        Span::styled("  │  backspaces: ", Style::default().fg(sub_color)),
+        Span::styled(format!("{}", app.test.backspaces), Style::default().fg(main_color)),
+        Span::styled(format!(" ({:.1} per 100 keys)", app.backspace_ratio()), Style::default().fg(sub_color)),
     ]);
+    if app.config.strict_space {
+        keystroke_detail.push_span(Span::styled("  │  stray spaces: ", Style::default().fg(sub_color)));
+        keystroke_detail.push_span(Span::styled(format!("{}", app.test.stray_spaces), Style::default().fg(main_color)));
+    }
     f.render_widget(Paragraph::new(keystroke_detail).alignment(Alignment::Center), rows[1]);
 
-    if !app.test.current_quote_source.is_empty() {
-        let source = Line::from(vec![
-            Span::styled("source: ", Style::default().fg(sub_color)),
-            Span::styled(&app.test.current_quote_source, Style::default().fg(main_color)),
+    if !app.test.quote_sources.is_empty() {
+        let mut source = Line::from(vec![
+            Span::styled(source_heading(app), Style::default().fg(sub_color)),
+            Span::styled(app.test.quote_source_label(), Style::default().fg(main_color)),
         ]);
+        if let Some(license) = app.test.quote_license_label() {
+            source.push_span(Span::styled(format!("  ({})", license), Style::default().fg(sub_color)));
+        }
         f.render_widget(Paragraph::new(source).alignment(Alignment::Center), rows[2]);
     }
 }
@@ -485,13 +964,16 @@ fn draw_compact_footer(
         Span::styled("keystrokes ", Style::default().fg(sub_color)),
         Span::styled(format!("{}/{}", app.test.live_correct_keystrokes, total_ks), Style::default().fg(main_color)),
         Span::styled(format!(" ({:.1}%)", ks_acc), Style::default().fg(sub_color)),
+        Span::styled("  │  backspaces ", Style::default().fg(sub_color)),
+        Span::styled(format!("{}", app.test.backspaces), Style::default().fg(main_color)),
+        Span::styled(format!(" ({:.1}/100)", app.backspace_ratio()), Style::default().fg(sub_color)),
     ]);
     f.render_widget(Paragraph::new(ks_line).alignment(Alignment::Center), rows[0]);
 
-    if !app.test.current_quote_source.is_empty() {
+    if !app.test.quote_sources.is_empty() {
         let source = Line::from(vec![
             Span::styled("― ", Style::default().fg(sub_color)),
-            Span::styled(&app.test.current_quote_source, Style::default().fg(main_color)),
+            Span::styled(app.test.quote_source_label(), Style::default().fg(main_color)),
         ]);
         f.render_widget(Paragraph::new(source).alignment(Alignment::Center), rows[1]);
     }
@@ -512,15 +994,20 @@ fn draw_ultra_compact_footer(
         ])
         .split(area);
 
-    if !app.test.current_quote_source.is_empty() {
+    if !app.test.quote_sources.is_empty() {
         let source = Line::from(vec![
-            Span::styled("source: ", Style::default().fg(sub_color)),
-            Span::styled(&app.test.current_quote_source, Style::default().fg(main_color)),
+            Span::styled(source_heading(app), Style::default().fg(sub_color)),
+            Span::styled(app.test.quote_source_label(), Style::default().fg(main_color)),
         ]);
         f.render_widget(Paragraph::new(source).alignment(Alignment::Center), rows[0]);
     }
 }
 
+fn source_heading(app: &App) -> &'static str {
+    if app.test.quote_sources.len() > 1 { "sources: " } else { "source: " }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn draw_chart(
     f: &mut Frame,
     app: &App,
@@ -598,10 +1085,20 @@ fn draw_chart(
     let max_errors = app.test.errors_history.iter().map(|(_, e)| *e).fold(0.0_f64, f64::max);
     let y_max_err  = max_errors.max(1.0);
 
-    let scaled_errors: Vec<(f64, f64)> = app.test.errors_history.iter()
-        .filter(|(t, e)| *t >= 1.0 && *e > 0.0)
-        .map(|(t, e)| (*t, (e / y_max_err) * y_max_wpm))
-        .collect();
+    // stacked: corrected errors at their own height, the ones left standing on top of them
+    let scale = |t: f64, e: f64| (t, (e / y_max_err) * y_max_wpm);
+    let (mut corrected_errors, mut uncorrected_errors) = (Vec::new(), Vec::new());
+    for (i, &(t, e)) in app.test.errors_history.iter().enumerate() {
+        if t < 1.0 { continue; }
+        let corrected = app.test.corrected_history.get(i).map_or(0.0, |&(_, c)| c.min(e));
+        if corrected > 0.0 { corrected_errors.push(scale(t, corrected)); }
+        if e > corrected { uncorrected_errors.push(scale(t, e)); }
+    }
+
+    let cursor = app.test.chart_cursor
+        .and_then(|i| Some((i, app.test.wpm_history.get(i)?.0)))
+        .filter(|&(_, t)| t >= 1.0);
+    let cursor_line: Vec<(f64, f64)> = cursor.map(|(_, t)| vec![(t, 0.0), (t, y_max_wpm)]).unwrap_or_default();
 
     const LEFT_W:  u16 = 5;
     const RIGHT_W: u16 = 6;
@@ -620,6 +1117,11 @@ fn draw_chart(
     let right_area = cols[2];
 
     let datasets = vec![
+        Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(sub_color))
+            .data(&cursor_line),
         Dataset::default()
             .marker(symbols::Marker::Braille)
             .graph_type(GraphType::Line)
@@ -630,11 +1132,16 @@ fn draw_chart(
             .graph_type(GraphType::Line)
             .style(Style::default().fg(main_color).add_modifier(ratatui::style::Modifier::BOLD))
             .data(&filtered_wpm_history),
+        Dataset::default()
+            .marker(symbols::Marker::Dot)
+            .graph_type(GraphType::Scatter)
+            .style(Style::default().fg(sub_color))
+            .data(&corrected_errors),
         Dataset::default()
             .marker(symbols::Marker::Dot)
             .graph_type(GraphType::Scatter)
             .style(Style::default().fg(error_color))
-            .data(&scaled_errors),
+            .data(&uncorrected_errors),
     ];
 
     let chart_width = body_area.width.max(4) as f64;
@@ -735,16 +1242,53 @@ fn draw_chart(
         }
     }
 
-    let legend = Line::from(vec![
+    let legend = match cursor {
+        Some((i, t)) => cursor_readout(app, i, t, sub_color, main_color, error_color),
+        None => chart_legend(sub_color, main_color, error_color),
+    };
+    f.render_widget(
+        Paragraph::new(legend).alignment(Alignment::Center),
+        legend_area,
+    );
+}
+
+fn chart_legend(
+    sub_color: ratatui::style::Color,
+    main_color: ratatui::style::Color,
+    error_color: ratatui::style::Color,
+) -> Line<'static> {
+    Line::from(vec![
         Span::styled("━━ ", Style::default().fg(main_color).add_modifier(ratatui::style::Modifier::BOLD)),
         Span::styled("wpm  ", Style::default().fg(sub_color)),
         Span::styled("── ", Style::default().fg(sub_color)),
         Span::styled("raw  ", Style::default().fg(sub_color)),
         Span::styled("· ", Style::default().fg(error_color)),
-        Span::styled("errors/s", Style::default().fg(sub_color)),
-    ]);
-    f.render_widget(
-        Paragraph::new(legend).alignment(Alignment::Center),
-        legend_area,
-    );
+        Span::styled("errors/s  ", Style::default().fg(sub_color)),
+        Span::styled("· ", Style::default().fg(sub_color)),
+        Span::styled("corrected", Style::default().fg(sub_color)),
+    ])
+}
+
+/// exact values for the second under the chart cursor
+fn cursor_readout(
+    app: &App,
+    i: usize,
+    t: f64,
+    sub_color: ratatui::style::Color,
+    main_color: ratatui::style::Color,
+    error_color: ratatui::style::Color,
+) -> Line<'static> {
+    let value = |history: &[(f64, f64)]| history.get(i).map_or(0.0, |&(_, v)| v);
+    let errors = value(&app.test.errors_history);
+    let corrected = value(&app.test.corrected_history);
+    Line::from(vec![
+        // only the last snapshot can fall between whole seconds
+        Span::styled(format!("{:.*}s  ", (t.fract() > 0.0) as usize, t), Style::default().fg(sub_color)),
+        Span::styled(format!("{:.0}", value(&app.test.wpm_history)), Style::default().fg(main_color)),
+        Span::styled(" wpm  ", Style::default().fg(sub_color)),
+        Span::styled(format!("{:.0}", value(&app.test.raw_wpm_history)), Style::default().fg(main_color)),
+        Span::styled(" raw  ", Style::default().fg(sub_color)),
+        Span::styled(format!("{:.0}", errors), Style::default().fg(error_color)),
+        Span::styled(format!(" errors ({:.0} corrected)", corrected), Style::default().fg(sub_color)),
+    ])
 }
@@ -0,0 +1,101 @@
+use crate::app::StepSummary;
+use crate::config::Theme;
+use crate::ui::utils::hex_to_rgb;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+    Frame,
+};
+
+/// combined results for a multi-test run. `planned` is how many tests the run had,
+/// so an early quit shows up as "2/3 done".
+pub fn draw(f: &mut Frame, theme: &Theme, title: &str, steps: &[StepSummary], planned: usize) {
+    let bg_color   = hex_to_rgb(&theme.bg);
+    let sub_color  = hex_to_rgb(&theme.sub);
+    let main_color = hex_to_rgb(&theme.main);
+    let err_color  = hex_to_rgb(&theme.error);
+
+    f.render_widget(Block::default().style(Style::default().bg(bg_color)), f.area());
+
+    let label_w = steps.iter().map(|s| s.label.chars().count()).max().unwrap_or(0).max(7);
+    let table_w = (4 + label_w + 2 + 6 + 9 + 8) as u16;
+    let table_h = (steps.len() + 6) as u16;
+
+    let area = f.area();
+    let w = table_w.min(area.width);
+    let h = table_h.min(area.height);
+    let area = Rect::new(
+        area.x + area.width.saturating_sub(w) / 2,
+        area.y + area.height.saturating_sub(h) / 2,
+        w,
+        h,
+    );
+
+    let mut constraints = vec![Constraint::Length(1), Constraint::Length(1), Constraint::Length(1)];
+    constraints.extend(steps.iter().map(|_| Constraint::Length(1)));
+    constraints.extend([Constraint::Length(1), Constraint::Length(1), Constraint::Length(1)]);
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .split(area);
+
+    let heading = Line::from(vec![
+        Span::styled(title, Style::default().fg(main_color).add_modifier(Modifier::BOLD)),
+        Span::styled(format!("  {}/{} done", steps.len(), planned), Style::default().fg(sub_color)),
+    ]);
+    f.render_widget(Paragraph::new(heading).alignment(Alignment::Center), rows[0]);
+
+    let header = format!("{:<4}{:<lw$}  {:>6}{:>9}{:>8}", "#", "test", "wpm", "acc", "time", lw = label_w);
+    f.render_widget(
+        Paragraph::new(header).style(Style::default().fg(sub_color).add_modifier(Modifier::DIM)),
+        rows[2],
+    );
+
+    for (i, step) in steps.iter().enumerate() {
+        let wpm = if step.failed { "failed".to_string() } else { format!("{:.0}", step.wpm) };
+        let line = Line::from(vec![
+            Span::styled(format!("{:<4}", i + 1), Style::default().fg(sub_color)),
+            Span::styled(format!("{:<lw$}  ", step.label, lw = label_w), Style::default().fg(sub_color)),
+            Span::styled(
+                format!("{:>6}", wpm),
+                Style::default().fg(if step.failed { err_color } else { main_color }),
+            ),
+            Span::styled(format!("{:>8.1}%", step.accuracy), Style::default().fg(main_color)),
+            Span::styled(format!("{:>7.1}s", step.time), Style::default().fg(main_color)),
+        ]);
+        f.render_widget(Paragraph::new(line), rows[3 + i]);
+    }
+
+    // time-weighted so a 15s sprint doesn't count as much as a 60s test. failed tests stay out of it
+    let counted: Vec<&StepSummary> = steps.iter().filter(|s| !s.failed && s.time > 0.0).collect();
+    let total_time: f64 = steps.iter().map(|s| s.time).sum();
+    let counted_time: f64 = counted.iter().map(|s| s.time).sum();
+    let (avg_wpm, avg_acc) = if counted_time > 0.0 {
+        (
+            counted.iter().map(|s| s.wpm * s.time).sum::<f64>() / counted_time,
+            counted.iter().map(|s| s.accuracy * s.time).sum::<f64>() / counted_time,
+        )
+    } else {
+        (0.0, 0.0)
+    };
+
+    let overall = Line::from(vec![
+        Span::styled(format!("{:<4}{:<lw$}  ", "", "overall", lw = label_w), Style::default().fg(sub_color)),
+        Span::styled(
+            format!("{:>6.0}", avg_wpm),
+            Style::default().fg(main_color).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(format!("{:>8.1}%", avg_acc), Style::default().fg(main_color)),
+        Span::styled(format!("{:>7.1}s", total_time), Style::default().fg(main_color)),
+    ]);
+    f.render_widget(Paragraph::new(overall), rows[4 + steps.len()]);
+
+    f.render_widget(
+        Paragraph::new("press any key to exit")
+            .style(Style::default().fg(sub_color))
+            .alignment(Alignment::Center),
+        rows[5 + steps.len()],
+    );
+}
@@ -0,0 +1,53 @@
+use crate::config::ProgressMode;
+use std::io::{self, Write};
+
+/// test progress as an OSC 9;4 sequence, which Windows Terminal, ConEmu and a few
+/// others turn into a taskbar/tab progress indicator. terminals that don't know it
+/// tend to print it, so it's only sent where it's known to work unless forced on.
+pub struct TaskbarProgress {
+    enabled: bool,
+    last: Option<u8>,
+}
+
+impl TaskbarProgress {
+    pub fn new(mode: ProgressMode) -> Self {
+        let enabled = match mode {
+            ProgressMode::Always => true,
+            ProgressMode::Never => false,
+            ProgressMode::Auto => supported(),
+        };
+        Self { enabled, last: None }
+    }
+
+    /// `progress` runs 0.0 to 1.0. None clears the indicator.
+    pub fn update(&mut self, progress: Option<f64>) -> io::Result<()> {
+        if !self.enabled { return Ok(()); }
+        let percent = progress.map(|p| (p.clamp(0.0, 1.0) * 100.0).round() as u8);
+        if percent == self.last { return Ok(()); }
+        self.last = percent;
+
+        let mut out = io::stdout();
+        match percent {
+            Some(p) => write!(out, "\x1b]9;4;1;{}\x07", p)?,
+            None => write!(out, "\x1b]9;4;0;\x07")?,
+        }
+        out.flush()
+    }
+}
+
+impl Drop for TaskbarProgress {
+    fn drop(&mut self) {
+        let _ = self.update(None);
+    }
+}
+
+fn supported() -> bool {
+    use std::env::var;
+    // tmux and screen swallow or mangle it without passthrough
+    if var("TMUX").is_ok() || var("STY").is_ok() {
+        return false;
+    }
+    var("WT_SESSION").is_ok()
+        || var("ConEmuANSI").is_ok_and(|v| v == "ON")
+        || var("TERM_PROGRAM").is_ok_and(|v| v == "WezTerm" || v == "ghostty")
+}
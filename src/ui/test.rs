@@ -1,55 +1,69 @@
-use crate::app::App;
+use crate::app::{App, VISIBLE_LINES};
 use crate::models::Mode;
 use crate::models::AppState;
-use crate::ui::utils::{format_timer, hex_to_rgb, render_header, render_footer};
+use crate::text::{TypedText, Word};
+use crate::ui::racers;
+use crate::ui::utils::{format_timer, hex_to_rgb, live_color, render_header, render_footer};
 use crate::utils::strings;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout},
-    style::Style,
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::Paragraph,
     Frame,
 };
+use std::ops::Range;
 
-pub fn draw(f: &mut Frame, app: &App) {
-    let status_text = match app.config.mode {
+/// time left, or words typed out of the total
+pub(crate) fn status_text(app: &App) -> String {
+    match app.config.mode {
         Mode::Time(limit) => {
-            let seconds = if let Some(start) = app.test.start_time {
-                let elapsed = start.elapsed().as_secs();
-                limit.saturating_sub(elapsed)
+            let seconds = if let Some(elapsed) = app.elapsed() {
+                limit.saturating_sub(elapsed.as_secs())
             } else {
                 limit
             };
             format_timer(seconds)
         }
         Mode::Words(total) => {
-            let visible_words = app.test.input.split_whitespace().count();
-            let mut total_typed = app.test.scrolled_word_count + visible_words;
-            let is_finished = app.test.aligned_input.len() >= app.test.word_stream_string.chars().count();
-            if !app.test.input.ends_with(' ') && !is_finished && visible_words > 0 {
-                total_typed = total_typed.saturating_sub(1);
-            }
-            format!("{}/{}", total_typed, total)
+            format!("{}/{}", typed_word_count(app, true), total)
         }
         Mode::Quote(_) => {
-            let visible_words = app.test.input.split_whitespace().count();
-            let mut typed_words = app.test.scrolled_word_count + visible_words;
-            let is_finished =
-                app.test.aligned_input.len() >= app.test.word_stream_string.chars().count() && app.test.quote_pool.is_empty();
-            if !app.test.input.ends_with(' ') && !is_finished && visible_words > 0 {
-                typed_words = typed_words.saturating_sub(1);
-            }
-            format!("{}/{}", typed_words, app.test.total_quote_words)
+            let is_finished = app.test.quote_pool.is_empty();
+            format!("{}/{}", typed_word_count(app, is_finished), app.test.total_quote_words)
         }
-    };
+    }
+}
+
+/// words closed with a space, plus the last one once it's typed to the end
+pub(crate) fn typed_word_count(app: &App, stream_ends_here: bool) -> usize {
+    let text = &app.test.text;
+    let last_done = stream_ends_here && text.is_fully_typed();
+    text.closed_words() + last_done as usize
+}
+
+/// "54 wpm" when the live readout is turned on
+pub(crate) fn live_wpm_text(app: &App) -> Option<String> {
+    if !app.config.live.wpm || app.test.state != AppState::Running { return None; }
+    Some(match app.live_wpm() {
+        Some(wpm) => format!("{:.0} wpm", wpm),
+        None => "- wpm".to_string(),
+    })
+}
+
+pub fn draw(f: &mut Frame, app: &App) {
+    let status_text = status_text(app);
 
     render_header(f, app);
 
+    let racers = app.racers();
+
     let vertical_layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Fill(1),
-            Constraint::Length(6),
+            Constraint::Length(racers::height(&racers)),
+            Constraint::Length(if app.config.accessibility.large_text { 8 } else { 6 }),
             Constraint::Fill(1),
         ])
         .split(f.area());
@@ -61,122 +75,199 @@ pub fn draw(f: &mut Frame, app: &App) {
             Constraint::Percentage(80),
             Constraint::Fill(1),
         ])
-        .split(vertical_layout[1]);
+        .split(vertical_layout[2]);
+
+    if !racers.is_empty() {
+        let racer_area = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Fill(1),
+                Constraint::Percentage(80),
+                Constraint::Fill(1),
+            ])
+            .split(vertical_layout[1])[1];
+        racers::draw(f, racer_area, &racers, app.race_length(), &app.config.theme);
+    }
 
     let active_area = horizontal_layout[1];
     let inner_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(1),
-            Constraint::Length(0),
+            Constraint::Length(app.config.accessibility.large_text as u16),
             Constraint::Min(1),
         ])
         .split(active_area);
 
+    let status_style = Style::default().fg(live_color(app)).add_modifier(Modifier::BOLD);
+    let mut status = vec![Span::styled(status_text, status_style)];
+    if let Some(wpm) = live_wpm_text(app) {
+        status.push(Span::styled(format!("  {}", wpm), status_style.remove_modifier(Modifier::BOLD)));
+    }
+    if app.is_paused() {
+        status.push(Span::styled("  paused", status_style.remove_modifier(Modifier::BOLD)));
+    }
+    if app.test.state == AppState::Waiting {
+        let sub = Style::default().fg(hex_to_rgb(&app.config.theme.sub));
+        if let Some(totals) = app.lifetime {
+            status.push(Span::styled(format!("  ·  {}", totals.label()), sub));
+        }
+        for quota in &app.quotas {
+            let met = if quota.is_met() { " ✓" } else { "" };
+            status.push(Span::styled(format!("  ·  {}{}", quota.summary(), met), sub));
+        }
+    }
+    if let Some(cheer) = app.cheer() {
+        status.push(Span::styled(format!("  ★ {}", cheer), status_style));
+    }
     f.render_widget(
-        Paragraph::new(status_text)
-            .alignment(Alignment::Left)
-            .style(
-                Style::default()
-                    .fg(hex_to_rgb(&app.config.theme.main))
-                    .add_modifier(ratatui::style::Modifier::BOLD),
-            ),
+        Paragraph::new(Line::from(status)).alignment(Alignment::Left),
         inner_chunks[0],
     );
 
-    let elapsed_ms = app.test.caret_epoch.elapsed().as_millis();
-    const BLINK_PERIOD_MS: u128 = 530;
-
-    let caret_visible = app.test.state == AppState::Running
-        || (elapsed_ms / BLINK_PERIOD_MS) % 2 == 0;
-
-    let mut visible_lines: Vec<Line> = Vec::new();
-    let lines_to_show = app.test.visual_lines.iter().take(3);
-
-    let mut global_char_idx = 0;
-    let input_chars = &app.test.aligned_input;
     let text_area = inner_chunks[2];
+    let visible_lines = text_lines(app);
 
-    let color_correct = hex_to_rgb(&app.config.theme.text);
-    let color_incorrect = hex_to_rgb(&app.config.theme.error);
-    let color_future = hex_to_rgb(&app.config.theme.sub);
+    f.render_widget(
+        Paragraph::new(visible_lines).alignment(Alignment::Left),
+        text_area,
+    );
+
+    render_footer(f, app);
+}
 
-    // caret block is 'caret', text inside is 'sub' (for contrast)
-    let color_cursor_bg = hex_to_rgb(&app.config.theme.caret);
-    let color_cursor_fg = hex_to_rgb(&app.config.theme.sub);
+/// the next few lines of text, styled by what's been typed so far
+pub(crate) fn text_lines(app: &App) -> Vec<Line<'static>> {
+    let elapsed_ms = app.caret_elapsed().as_millis();
+    const BLINK_PERIOD_MS: u128 = 530;
 
-    for line_str in lines_to_show {
-        let mut spans: Vec<Span> = Vec::new();
-        for (char_idx, c) in line_str.chars().enumerate() {
-            let current_idx = global_char_idx + char_idx;
+    let caret_visible = !app.config.accessibility.blinks_caret()
+        || app.test.state == AppState::Running
+        || (elapsed_ms / BLINK_PERIOD_MS).is_multiple_of(2);
 
-            let is_extra_char = if current_idx < app.test.display_mask.len() {
-                app.test.display_mask[current_idx]
-            } else {
-                false
-            };
+    let lines = &app.test.visual_lines[..app.test.visual_lines.len().min(VISIBLE_LINES)];
+    let styles = TextStyles::new(app, caret_visible);
+    let rendered = render_lines(&app.test.text, lines, &styles, app.hidden_from());
+    if app.config.accessibility.large_text { enlarge(rendered, &styles) } else { rendered }
+}
 
-            if current_idx < input_chars.len() {
-                if is_extra_char {
-                    spans.push(Span::styled(
-                        c.to_string(),
-                        Style::default()
-                            .fg(color_incorrect)
-                            .add_modifier(ratatui::style::Modifier::BOLD),
-                    ));
-                } else {
-                    let input_char = input_chars[current_idx];
-
-                    if input_char == '\0' {
-                        // missed == future/sub color
-                        spans.push(Span::styled(c.to_string(), Style::default().fg(color_future)));
-                    } else if strings::are_characters_visually_equal(input_char, c) {
-                        spans.push(Span::styled(
-                            c.to_string(),
-                            Style::default()
-                                .fg(color_correct)
-                                .add_modifier(ratatui::style::Modifier::BOLD),
-                        ));
-                    } else {
-                        spans.push(Span::styled(
-                            c.to_string(),
-                            Style::default()
-                                .fg(color_incorrect)
-                                .add_modifier(ratatui::style::Modifier::BOLD),
-                        ));
-                    }
+/// large text: every cell doubled across, with a blank row under each line. the caret
+/// gets both its cells, and a half block under them so it stands out at a glance.
+fn enlarge(lines: Vec<Line<'static>>, styles: &TextStyles) -> Vec<Line<'static>> {
+    let caret_bg = styles.caret_end.and_then(|s| s.bg);
+    let mut large = Vec::with_capacity(lines.len() * 2);
+    for line in lines {
+        let mut spans = Vec::with_capacity(line.spans.len() * 2);
+        let mut under = Vec::new();
+        let mut col = 0;
+        for span in line.spans {
+            let is_caret = caret_bg.is_some() && span.style.bg == caret_bg;
+            for c in span.content.chars() {
+                spans.push(Span::styled(format!("{} ", c), span.style));
+                if let (true, Some(color)) = (is_caret, caret_bg) {
+                    under.push(Span::raw(" ".repeat(col)));
+                    under.push(Span::styled("▀▀", Style::default().fg(color)));
                 }
-            } else if current_idx == input_chars.len() {
-
-                spans.push(Span::styled(
-                    c.to_string(),
-                    if caret_visible {
-                        Style::default().bg(color_cursor_bg).fg(color_cursor_fg)
-                    } else {
-                        Style::default().fg(color_future)
-                    },
-                ));
-            } else {
-                spans.push(Span::styled(c.to_string(), Style::default().fg(color_future)));
+                col += 2;
             }
         }
-        let line_end_idx = global_char_idx + line_str.chars().count();
-        if input_chars.len() == line_end_idx && caret_visible {
-            spans.push(Span::styled(
-                " ",
-                Style::default().bg(color_cursor_bg),
-            ));
+        large.push(Line::from(spans));
+        large.push(Line::from(under));
+    }
+    large
+}
+
+/// how typed, untyped and mistyped text is drawn
+pub(crate) struct TextStyles {
+    correct: Style,
+    mistake: Style,
+    extra: Style,
+    future: Style,
+    on_caret: Style,
+    /// the caret past the end of a line, or None while it's blinked off
+    caret_end: Option<Style>,
+}
+
+impl TextStyles {
+    pub(crate) fn new(app: &App, caret_visible: bool) -> Self {
+        // mistakes are marked with more than color in accessible mode
+        let accessible = app.config.accessibility.enabled;
+        let mistake = if accessible {
+            Modifier::BOLD | Modifier::UNDERLINED
+        } else {
+            Modifier::BOLD
+        };
+        let extra = if accessible { mistake | Modifier::CROSSED_OUT } else { mistake };
+        let caret = if accessible { Modifier::REVERSED } else { Modifier::empty() };
+
+        let color_incorrect = hex_to_rgb(&app.config.theme.error);
+        // caret block is 'caret', text inside is 'sub' (for contrast)
+        let color_cursor_bg = hex_to_rgb(&app.config.theme.caret);
+        let future = Style::default().fg(hex_to_rgb(&app.config.theme.sub));
+
+        Self {
+            correct: Style::default().fg(hex_to_rgb(&app.config.theme.text)).add_modifier(Modifier::BOLD),
+            mistake: Style::default().fg(color_incorrect).add_modifier(mistake),
+            extra: Style::default().fg(color_incorrect).add_modifier(extra),
+            future,
+            on_caret: if caret_visible {
+                Style::default().bg(color_cursor_bg).fg(hex_to_rgb(&app.config.theme.sub)).add_modifier(caret)
+            } else {
+                future
+            },
+            caret_end: caret_visible.then(|| Style::default().bg(color_cursor_bg).add_modifier(caret)),
         }
+    }
 
+    pub(crate) fn future(&self) -> Style {
+        self.future
+    }
+}
+
+/// `lines` of `text`, with the caret in the current word. words from `hidden_from` on show
+/// only what's been typed of them.
+pub(crate) fn render_lines(text: &TypedText, lines: &[Range<usize>], styles: &TextStyles, hidden_from: Option<usize>) -> Vec<Line<'static>> {
+    let current = text.current_index();
+    let mut visible_lines: Vec<Line> = Vec::new();
 
-        global_char_idx += line_str.chars().count() + 1;
+    for line in lines {
+        let mut spans: Vec<Span> = Vec::new();
+        for i in line.clone() {
+            let word = &text.words()[i];
+            let caret = (i == current).then_some(word.typed().len());
+            let hidden = hidden_from.is_some_and(|from| i >= from);
+            word_spans(word, styles, caret, hidden, &mut spans);
+
+            // then the space after the word, or the end of the line
+            let caret_after = caret.is_some_and(|at| at >= word.width());
+            if i + 1 < line.end {
+                let style = if caret_after { styles.on_caret } else if word.is_closed() { styles.correct } else { styles.future };
+                spans.push(Span::styled(" ", style));
+            } else if let Some(style) = styles.caret_end.filter(|_| caret_after) {
+                spans.push(Span::styled(" ", style));
+            }
+        }
         visible_lines.push(Line::from(spans));
     }
 
-    f.render_widget(
-        Paragraph::new(visible_lines).alignment(Alignment::Left),
-        text_area,
-    );
+    visible_lines
+}
 
-    render_footer(f, app);
+/// one word, letter by letter. `caret` is the column it sits in, if it's in this word.
+/// a hidden word keeps its length, but its untyped letters are dots.
+pub(crate) fn word_spans(word: &Word, styles: &TextStyles, caret: Option<usize>, hidden: bool, spans: &mut Vec<Span<'static>>) {
+    let typed = word.typed();
+    for col in 0..word.width() {
+        let (c, is_extra) = word.shown(col);
+        let style = match typed.get(col) {
+            Some(_) if is_extra => styles.extra,
+            Some(&t) if strings::are_characters_visually_equal(t, c) => styles.correct,
+            Some(_) => styles.mistake,
+            // missed letters of a closed word read as not typed
+            None if caret == Some(col) => styles.on_caret,
+            None => styles.future,
+        };
+        let c = if hidden && col >= typed.len() { '·' } else { c };
+        spans.push(Span::styled(c.to_string(), style));
+    }
 }
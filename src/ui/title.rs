@@ -0,0 +1,64 @@
+use crate::app::App;
+use crate::models::{AppState, Mode};
+use std::io::{self, Write};
+
+/// live status in the terminal or tmux pane title, through OSC 2. the title that was there
+/// before is pushed onto the xterm title stack first and popped back off on drop.
+pub struct TerminalTitle {
+    enabled: bool,
+    last: Option<String>,
+}
+
+impl TerminalTitle {
+    pub fn new(enabled: bool) -> io::Result<Self> {
+        if enabled {
+            let mut out = io::stdout();
+            write!(out, "\x1b[22;0t")?;
+            out.flush()?;
+        }
+        Ok(Self { enabled, last: None })
+    }
+
+    /// only writes when the title changed, which is at most once a second while typing
+    pub fn update(&mut self, app: &App) -> io::Result<()> {
+        if !self.enabled { return Ok(()); }
+        let title = title_text(app);
+        if self.last.as_deref() == Some(title.as_str()) { return Ok(()); }
+
+        let mut out = io::stdout();
+        write!(out, "\x1b]2;{}\x07", title)?;
+        self.last = Some(title);
+        out.flush()
+    }
+}
+
+impl Drop for TerminalTitle {
+    fn drop(&mut self) {
+        if !self.enabled { return; }
+        let mut out = io::stdout();
+        let _ = write!(out, "\x1b[23;0t");
+        let _ = out.flush();
+    }
+}
+
+/// "typa 00:42 87wpm" while typing: time left in time mode, time taken otherwise
+pub fn title_text(app: &App) -> String {
+    match app.test.state {
+        AppState::Waiting => "typa".to_string(),
+        AppState::Running if app.is_paused() => "typa paused".to_string(),
+        AppState::Running => {
+            let elapsed = app.elapsed().unwrap_or_default().as_secs();
+            let secs = match app.config.mode {
+                Mode::Time(limit) => limit.saturating_sub(elapsed),
+                _ => elapsed,
+            };
+            let clock = format!("{:02}:{:02}", secs / 60, secs % 60);
+            match app.live_wpm() {
+                Some(wpm) => format!("typa {} {:.0}wpm", clock, wpm),
+                None => format!("typa {}", clock),
+            }
+        }
+        AppState::Finished if app.test.fail_reason.is_some() => "typa failed".to_string(),
+        AppState::Finished => format!("typa {:.0}wpm {:.0}%", app.test.final_wpm, app.test.final_accuracy),
+    }
+}
@@ -7,6 +7,7 @@ use ratatui::{
     Frame,
 };
 use crate::app::App;
+use crate::models::QuoteThresholds;
 
 pub fn hex_to_rgb(hex: &str) -> Color {
     let hex = hex.trim_start_matches('#');
@@ -20,6 +21,32 @@ pub fn hex_to_rgb(hex: &str) -> Color {
     }
 }
 
+/// blends `a` into `b`; t = 0.0 is all `a`, 1.0 is all `b`. non-rgb colors snap at the midpoint.
+pub fn lerp_color(a: Color, b: Color, t: f64) -> Color {
+    let t = t.clamp(0.0, 1.0);
+    match (a, b) {
+        (Color::Rgb(r1, g1, b1), Color::Rgb(r2, g2, b2)) => {
+            let mix = |x: u8, y: u8| (x as f64 + (y as f64 - x as f64) * t).round() as u8;
+            Color::Rgb(mix(r1, r2), mix(g1, g2), mix(b1, b2))
+        }
+        _ => if t < 0.5 { a } else { b },
+    }
+}
+
+/// the live readout color: `main` normally, or somewhere between the configured `below`
+/// and `above` colors when there's a goal. the gradient runs from 80% of goal up to goal.
+pub fn live_color(app: &App) -> Color {
+    let theme = &app.config.theme;
+    let live = &app.config.live;
+    let main = hex_to_rgb(&theme.main);
+    let (Some(goal), Some(wpm)) = (live.goal, app.live_wpm()) else { return main };
+    let below = theme.named(&live.below).map(hex_to_rgb).unwrap_or(main);
+    let above = theme.named(&live.above).map(hex_to_rgb).unwrap_or(main);
+    const FLOOR: f64 = 0.8;
+    let t = (wpm / goal.max(1.0) - FLOOR) / (1.0 - FLOOR);
+    lerp_color(below, above, t)
+}
+
 pub fn format_timer(seconds: u64) -> String {
     if seconds >= 60 {
         let minutes = seconds / 60;
@@ -30,19 +57,23 @@ pub fn format_timer(seconds: u64) -> String {
     }
 }
 
-pub fn get_quote_length_category(char_count: usize) -> &'static str {
-    if char_count <= 100 {
+pub fn get_quote_length_category(char_count: usize, thresholds: &QuoteThresholds) -> &'static str {
+    if char_count <= thresholds.short {
         "short"
-    } else if char_count <= 300 {
+    } else if char_count <= thresholds.medium {
         "medium"
-    } else if char_count <= 600 {
+    } else if char_count <= thresholds.long {
         "long"
     } else {
         "very long"
     }
 }
 
-pub fn quote_idle_label(selector: &crate::models::QuoteSelector, original_length: usize) -> &'static str {
+pub fn quote_idle_label(
+    selector: &crate::models::QuoteSelector,
+    original_length: usize,
+    thresholds: &QuoteThresholds,
+) -> &'static str {
     use crate::models::{QuoteSelector, QuoteLength::*};
     match selector {
         QuoteSelector::Category(len) => match len {
@@ -52,7 +83,7 @@ pub fn quote_idle_label(selector: &crate::models::QuoteSelector, original_length
             Long     => "long",
             VeryLong => "very long",
         },
-        QuoteSelector::Id(_) => get_quote_length_category(original_length),
+        QuoteSelector::Id(_) => get_quote_length_category(original_length, thresholds),
     }
 }
 
@@ -77,6 +108,12 @@ pub fn render_header(f: &mut Frame, app: &App) {
             format!(" | mode: {:?}", app.config.mode),
             Style::default().fg(hex_to_rgb(&app.config.theme.sub)),
         ));
+        if let Some(step) = &app.sequence {
+            header_spans.push(Span::styled(
+                format!(" | {} {}/{}", step.name, step.index + 1, step.total),
+                Style::default().fg(hex_to_rgb(&app.config.theme.main)),
+            ));
+        }
     }
 
     let header_row_area = Rect::new(0, 1, f.area().width, 1);
@@ -99,28 +136,30 @@ pub fn render_footer(f: &mut Frame, app: &App) {
         let width = f.area().width as usize;
 
         let text = if app.test.state == AppState::Finished {
-            if width >= 52 {
-                "tab: next test  |  r: retry same words  |  esc: quit"
-            } else if width >= 36 {
-                "tab: next  |  r: retry  |  esc: quit"
-            } else if width >= 24 {
-                "tab  |  r: retry  |  esc"
-            } else {
-                "tab  |  r  |  esc"
-            }
+            crate::ui::actions::results_footer(app, width)
+        } else if width >= 28 {
+            "tab: restart  |  esc: quit".to_string()
+        } else if width >= 18 {
+            "tab: restart  |  esc".to_string()
         } else {
-            if width >= 28 {
-                "tab: restart  |  esc: quit"
-            } else if width >= 18 {
-                "tab: restart  |  esc"
-            } else {
-                "tab  esc"
-            }
+            "tab  esc".to_string()
         };
 
-        let footer = Paragraph::new(text)
-            .style(Style::default().fg(hex_to_rgb(&app.config.theme.sub)))
-            .alignment(Alignment::Center);
+        let mut line = Line::styled(text, Style::default().fg(hex_to_rgb(&app.config.theme.sub)));
+        // only where there's room to spare, so it never pushes a key hint out
+        if let Some(version) = &app.newer_version {
+            let hint = format!("  ·  {} available", version);
+            if line.width() + hint.chars().count() + 4 <= width {
+                line.push_span(Span::styled(hint, Style::default().fg(hex_to_rgb(&app.config.theme.sub_alt))));
+            }
+        }
+        let footer = Paragraph::new(line).alignment(Alignment::Center);
         f.render_widget(footer, Rect::new(0, f.area().height - 1, f.area().width, 1));
+
+        if let Some(notice) = app.notice.as_ref().filter(|_| app.test.state == AppState::Waiting && f.area().height >= 3) {
+            let notice = Paragraph::new(Line::styled(notice.as_str(), Style::default().fg(hex_to_rgb(&app.config.theme.sub_alt))))
+                .alignment(Alignment::Center);
+            f.render_widget(notice, Rect::new(0, f.area().height - 3, f.area().width, 1));
+        }
     }
 }
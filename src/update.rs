@@ -0,0 +1,124 @@
+//! the opt-in check for a newer release. at most once a day, and never in the way: the
+//! answer is cached in the data directory, and fetching a new one happens on a thread that
+//! the test doesn't wait for. only built in with the `update-check` feature; without it
+//! `UpdateCheck::start` always comes back empty.
+
+use crate::utils::paths;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+/// when the feed was last asked, and what it said
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Cache {
+    pub checked_at: Option<String>,
+    pub latest: Option<String>,
+}
+
+impl Cache {
+    pub fn load() -> Self {
+        paths::data_file("update_check.json")
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    #[cfg_attr(not(feature = "update-check"), allow(dead_code))]
+    fn save(&self) {
+        let Some(path) = paths::data_file("update_check.json") else { return };
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        if let Ok(raw) = serde_json::to_string(self) {
+            let _ = fs::write(path, raw);
+        }
+    }
+
+    /// asked within the last day, so there's no need to ask again
+    pub fn is_fresh(&self, now: DateTime<Utc>) -> bool {
+        self.checked_at.as_deref()
+            .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+            .is_some_and(|at| now.signed_duration_since(at) < Duration::days(1))
+    }
+}
+
+/// the release tag in a github style release feed, `{"tag_name": "v0.5.0", ...}`
+pub fn parse_feed(raw: &[u8]) -> Option<String> {
+    #[derive(Deserialize)]
+    struct Release {
+        tag_name: String,
+    }
+    serde_json::from_slice::<Release>(raw).ok().map(|r| r.tag_name)
+}
+
+/// `latest` is a later version than `current`. a leading "v" and anything after a "-" are
+/// ignored, and missing parts count as 0.
+pub fn is_newer(latest: &str, current: &str) -> bool {
+    let parts = |v: &str| -> Option<Vec<u64>> {
+        let v = v.trim().trim_start_matches('v');
+        let v = v.split('-').next().unwrap_or(v);
+        v.split('.').map(|p| p.parse().ok()).collect()
+    };
+    let (Some(mut latest), Some(mut current)) = (parts(latest), parts(current)) else { return false };
+    let len = latest.len().max(current.len());
+    latest.resize(len, 0);
+    current.resize(len, 0);
+    latest > current
+}
+
+pub use imp::UpdateCheck;
+
+#[cfg(feature = "update-check")]
+mod imp {
+    use super::{is_newer, parse_feed, Cache};
+    use crate::utils::net;
+    use std::sync::mpsc::{self, Receiver};
+    use std::thread;
+
+    /// the answer, when there is one. poll it from the event loop.
+    pub struct UpdateCheck {
+        answer: Receiver<Option<String>>,
+    }
+
+    impl UpdateCheck {
+        pub fn start(feed: &str) -> Option<Self> {
+            let (tx, answer) = mpsc::channel();
+            let current = env!("CARGO_PKG_VERSION");
+            let cache = Cache::load();
+            let now = chrono::Utc::now();
+            if cache.is_fresh(now) {
+                let _ = tx.send(cache.latest.filter(|l| is_newer(l, current)));
+                return Some(Self { answer });
+            }
+
+            let feed = feed.to_string();
+            thread::spawn(move || {
+                // a failed check still counts as the day's check, the previous answer stands
+                let latest = net::download(&feed, 10).ok().and_then(|raw| parse_feed(&raw)).or(cache.latest);
+                Cache { checked_at: Some(now.to_rfc3339()), latest: latest.clone() }.save();
+                let _ = tx.send(latest.filter(|l| is_newer(l, current)));
+            });
+            Some(Self { answer })
+        }
+
+        /// the newer version, once, as soon as it's known
+        pub fn poll(&self) -> Option<String> {
+            self.answer.try_recv().ok().flatten()
+        }
+    }
+}
+
+#[cfg(not(feature = "update-check"))]
+mod imp {
+    pub struct UpdateCheck;
+
+    impl UpdateCheck {
+        pub fn start(_feed: &str) -> Option<Self> {
+            None
+        }
+
+        pub fn poll(&self) -> Option<String> {
+            None
+        }
+    }
+}
@@ -1 +1,4 @@
+pub mod paths;
 pub mod strings;
+#[cfg(any(feature = "fetch", feature = "update-check"))]
+pub mod net;
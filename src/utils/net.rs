@@ -0,0 +1,17 @@
+//! the one way typa reaches the network: `curl`, so there's no http stack to build in.
+//! only there with the `fetch` or `update-check` feature.
+
+use anyhow::{bail, Context, Result};
+use std::process::Command;
+
+/// the body at `url`, or an error saying why not. gives up after `timeout_secs`.
+pub fn download(url: &str, timeout_secs: u64) -> Result<Vec<u8>> {
+    let output = Command::new("curl")
+        .args(["--fail", "--silent", "--show-error", "--location", "--max-time", &timeout_secs.to_string(), "--", url])
+        .output()
+        .context("could not run curl")?;
+    if !output.status.success() {
+        bail!("couldn't download {}: {}", url, String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(output.stdout)
+}
@@ -0,0 +1,17 @@
+use directories::ProjectDirs;
+use std::path::PathBuf;
+
+/// every file typa writes lives next to history.json in the local data dir.
+pub fn data_file(name: &str) -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join(name))
+}
+
+pub fn data_dir() -> Option<PathBuf> {
+    ProjectDirs::from("", "", "typa").map(|dirs| dirs.data_local_dir().to_path_buf())
+}
+
+/// config.toml, and anything else the user edits by hand, lives in the config dir.
+/// on some platforms that's the same directory as the data.
+pub fn config_file(name: &str) -> Option<PathBuf> {
+    ProjectDirs::from("", "", "typa").map(|dirs| dirs.config_dir().join(name))
+}
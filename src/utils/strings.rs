@@ -22,42 +22,43 @@ pub fn clean_typography_symbols(text: &str) -> String {
     output
 }
 
+/// uppercases the first letter, past any opening quotes or brackets: `"(well` becomes `"(Well`
 pub fn capitalize_word(w: &mut String) {
-    if let Some(idx) = w.find(|c: char| c.is_alphabetic()) {
-        let mut chars: Vec<char> = w.chars().collect();
-        if let Some(c) = chars.get_mut(idx) {
-            *c = c.to_uppercase().next().unwrap_or(*c);
-        }
-        *w = chars.into_iter().collect();
+    if let Some((idx, c)) = w.char_indices().find(|(_, c)| c.is_alphabetic()) {
+        let upper: String = c.to_uppercase().collect();
+        w.replace_range(idx..idx + c.len_utf8(), &upper);
     }
 }
 
+/// the word without the closing quotes and brackets that can follow its last punctuation mark
+fn strip_closers(w: &str) -> &str {
+    w.trim_end_matches(|c: char| is_quote(c) || matches!(c, ')' | ']' | '}' | '\u{00BB}'))
+}
+
+/// ends in . ! or ?, inside closing quotes and brackets or not: `"stop!"` and `(see above.)` do
 pub fn ends_with_terminator(w: &str) -> bool {
+    let w = strip_closers(w);
     w.ends_with('.') || w.ends_with('!') || w.ends_with('?')
 }
 
+/// a terminator that ends the sentence, so the next word starts a new one. an ellipsis
+/// only trails off.
 pub fn is_sentence_end(w: &str) -> bool {
-    !w.ends_with("...") && (w.ends_with('.') || w.ends_with('!') || w.ends_with('?'))
+    !strip_closers(w).ends_with("...") && ends_with_terminator(w)
 }
 
 fn is_quote(c: char) -> bool {
-    match c {
+    matches!(
+        c,
         '"' | '\u{201C}' | '\u{201D}' | '\u{201E}'
-        | '\'' | '\u{2019}' | '\u{2018}' | '\u{02BC}' | '\u{1FBD}' => true,
-        _ => false,
-    }
+        | '\'' | '\u{2019}' | '\u{2018}' | '\u{02BC}' | '\u{1FBD}'
+    )
 }
 
 fn is_dash(c: char) -> bool {
-    match c {
-        '-' | '\u{2013}' | '\u{2014}' | '\u{2010}' => true,
-        _ => false,
-    }
+    matches!(c, '-' | '\u{2013}' | '\u{2014}' | '\u{2010}')
 }
 
 fn is_comma_like(c: char) -> bool {
-    match c {
-        ',' | '\u{201A}' => true,
-        _ => false,
-    }
+    matches!(c, ',' | '\u{201A}')
 }
@@ -0,0 +1,46 @@
+//! a short token to share a result with, worked out from the test's text, the keystrokes
+//! that typed it and the numbers they came to. there's no server and no secret behind it, so
+//! anyone with the same inputs can work it out again: it's a checksum, not a proof. what it
+//! does catch is a result edited after the fact, by checking it against the record it came from.
+
+use crate::history::TestRecord;
+
+/// fnv-1a, which unlike std's hasher gives the same answer from build to build
+fn fnv(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |h, &b| (h ^ b as u64).wrapping_mul(0x100_0000_01b3))
+}
+
+const FNV_START: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// the text the test gave, standing in for a seed
+pub fn text_digest(words: &[String]) -> String {
+    let hash = words.iter().fold(FNV_START, |h, w| fnv(fnv(h, w.as_bytes()), b" "));
+    format!("{:016x}", hash)
+}
+
+/// every keystroke and when it came, to the millisecond. a backspace counts as a key of its own.
+pub fn keystroke_digest(keystrokes: &[(f64, Option<char>)]) -> String {
+    let hash = keystrokes.iter().fold(FNV_START, |h, &(at, key)| {
+        let ms = (at * 1000.0).round() as u64;
+        let key = key.map_or(0, u32::from);
+        fnv(fnv(h, &ms.to_le_bytes()), &key.to_le_bytes())
+    });
+    format!("{:016x}", hash)
+}
+
+/// eight letters and digits in two groups, like "K3F9-QX2M"
+pub fn token(text: &str, keys: &str, wpm: f64, accuracy: f64, secs: f64) -> String {
+    const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+    let result = format!("{:.2} {:.2} {:.2}", wpm, accuracy, secs);
+    let hash = [text, keys, &result].iter().fold(FNV_START, |h, part| fnv(fnv(h, part.as_bytes()), b"|"));
+    let chars: String = (0..8).map(|i| ALPHABET[(hash >> (i * 5)) as usize & 31] as char).collect();
+    format!("{}-{}", &chars[..4], &chars[4..])
+}
+
+/// whether the record's token still matches its numbers. None if it never had one.
+pub fn check(record: &TestRecord) -> Option<bool> {
+    let (Some(text), Some(keys), Some(expected)) = (&record.text_digest, &record.keys_digest, &record.token) else { return None };
+    let wpm = record.wpm?;
+    let accuracy = record.accuracy?;
+    Some(token(text, keys, wpm, accuracy, record.duration_secs) == *expected)
+}
@@ -0,0 +1,118 @@
+//! typa as a pane in someone else's ratatui app. the host keeps a `TypaState`, forwards it
+//! the keys meant for the pane, and renders `TypaWidget` with it every frame, which also
+//! keeps the test's clock going. nothing is saved to history or announced anywhere.
+//!
+//! ```no_run
+//! use typa::app::TestOptions;
+//! use typa::config::AppConfig;
+//! use typa::models::Mode;
+//! use typa::widget::{TypaState, TypaWidget};
+//!
+//! let options = TestOptions { mode: Mode::Words(10), ..TestOptions::default() };
+//! let mut typa = TypaState::new(options, AppConfig::default()).unwrap();
+//! # let (mut frame, area, key): (ratatui::Frame, ratatui::layout::Rect, crossterm::event::KeyEvent) = todo!();
+//! frame.render_stateful_widget(TypaWidget, area, &mut typa);
+//! typa.handle_key(key);
+//! ```
+
+use crate::app::{App, TestOptions};
+use crate::clock::SystemClock;
+use crate::config::AppConfig;
+use crate::models::AppState;
+use crate::ui::test::{live_wpm_text, status_text, text_lines};
+use crate::ui::utils::hex_to_rgb;
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Paragraph, StatefulWidget, Widget},
+};
+
+/// a test for the pane to show
+pub struct TypaState {
+    app: App,
+}
+
+impl TypaState {
+    pub fn new(options: TestOptions, app_config: AppConfig) -> Result<Self> {
+        Ok(Self { app: App::detached(options, app_config, Box::new(SystemClock))? })
+    }
+
+    /// an app made some other way, e.g. on a clock of the host's own
+    pub fn from_app(app: App) -> Self {
+        Self { app }
+    }
+
+    pub fn app(&self) -> &App {
+        &self.app
+    }
+
+    pub fn app_mut(&mut self) -> &mut App {
+        &mut self.app
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.app.test.state == AppState::Finished
+    }
+
+    /// types `key` into the test. tab starts a new one. anything else, esc included, is left
+    /// to the host, and comes back false.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if key.kind != KeyEventKind::Press || key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) {
+            return false;
+        }
+        match key.code {
+            KeyCode::Tab => self.app.restart_test(),
+            KeyCode::Backspace => self.app.on_backspace(),
+            KeyCode::Char(c) if !self.is_finished() => self.app.on_key(c),
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// the status line and the text, or the result once it's done. wants four rows, more with
+/// large text, and as many columns as it can get.
+pub struct TypaWidget;
+
+impl StatefulWidget for TypaWidget {
+    type State = TypaState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut TypaState) {
+        let app = &mut state.app;
+        app.check_time();
+        if app.text_width != Some(area.width) {
+            app.set_text_width(area.width);
+        }
+
+        let theme = &app.config.theme;
+        let main = Style::default().fg(hex_to_rgb(&theme.main)).add_modifier(Modifier::BOLD);
+        let sub = Style::default().fg(hex_to_rgb(&theme.sub));
+
+        let lines = if app.test.state == AppState::Finished {
+            let t = &app.test;
+            let headline = match &t.fail_reason {
+                Some(reason) => format!("failed: {}", reason.describe()),
+                None => format!("{:.0} wpm  {:.1}% accuracy", t.final_wpm, t.final_accuracy),
+            };
+            vec![
+                Line::styled(headline, main),
+                Line::styled(format!("{:.0} raw  {:.0}% consistency  {:.1}s", t.final_raw_wpm, t.final_consistency, t.final_time), sub),
+                Line::default(),
+                Line::styled("tab: again", sub),
+            ]
+        } else {
+            let mut status = status_text(app);
+            if let Some(wpm) = live_wpm_text(app) {
+                status = format!("{}  {}", status, wpm);
+            }
+            let mut lines = vec![Line::styled(status, main)];
+            lines.extend(text_lines(app));
+            lines
+        };
+        Paragraph::new(lines).render(area, buf);
+    }
+}
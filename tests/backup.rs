@@ -0,0 +1,62 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use typa::backup::{read_tar, write_tar, Entry};
+
+#[test]
+fn tar_round_trips_files_of_any_length() {
+    let entries = vec![
+        Entry { name: "config/config.toml".into(), data: b"[theme]\nbg = \"#000000\"\n".to_vec() },
+        Entry { name: "data/history.json".into(), data: vec![b'x'; 1300] },
+        Entry { name: "data/empty.json".into(), data: Vec::new() },
+        Entry { name: format!("data/cards/{}.svg", "a".repeat(95)), data: b"<svg/>".to_vec() },
+    ];
+    let tar = write_tar(&entries).unwrap();
+    assert_eq!(tar.len() % 512, 0);
+    assert_eq!(read_tar(&tar).unwrap(), entries);
+}
+
+#[test]
+fn tar_reader_rejects_what_isnt_a_tar() {
+    let mut tar = write_tar(&[Entry { name: "data/history.json".into(), data: b"[]".to_vec() }]).unwrap();
+    tar[0] = b'X';
+    assert!(read_tar(&tar).is_err());
+    assert!(read_tar(&[7u8; 512]).is_err());
+}
+
+/// `typa restore` run against a scratch home, kept away from the real directories
+fn restore_into(home: &Path, tar: &[u8]) -> std::process::Output {
+    fs::create_dir_all(home).unwrap();
+    let archive = home.join("backup.tar");
+    fs::write(&archive, tar).unwrap();
+    Command::new(env!("CARGO_BIN_EXE_typa"))
+        .arg("restore")
+        .arg(&archive)
+        .env("XDG_CONFIG_HOME", home.join("config"))
+        .env("XDG_DATA_HOME", home.join("data"))
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn restore_refuses_paths_that_climb_out_of_the_data_dir() {
+    for (i, name) in ["data/../escape", "data//escape", "config/./escape", "data/"].into_iter().enumerate() {
+        let home = std::env::temp_dir().join(format!("typa-restore-{}-{}", std::process::id(), i));
+        let _ = fs::remove_dir_all(&home);
+        let tar = write_tar(&[
+            Entry { name: "data/history.json".into(), data: b"[]".to_vec() },
+            Entry { name: name.into(), data: b"gotcha".to_vec() },
+        ]).unwrap();
+
+        let out = restore_into(&home, &tar);
+        assert!(!out.status.success(), "{} was restored", name);
+        assert!(String::from_utf8_lossy(&out.stderr).contains("unsafe path"), "{}: {}", name, String::from_utf8_lossy(&out.stderr));
+
+        // nothing lands anywhere, not even the harmless file ahead of it
+        assert!(!home.join("data/escape").exists());
+        assert!(!home.join("escape").exists());
+        assert!(!home.join("data/typa").exists());
+        let _ = fs::remove_dir_all(&home);
+    }
+}
@@ -0,0 +1,51 @@
+use typa::card::render_svg;
+use typa::config::Theme;
+use typa::history::build_record;
+use typa::models::Mode;
+use typa::testing::Harness;
+use typa::verify::check;
+
+#[test]
+fn card_shows_the_result_and_chart() {
+    let mut h = Harness::new(Mode::Words(4), &["one", "two", "three", "four"]);
+    h.type_at_wpm("one twx three four", 60.0);
+    let mut record = build_record(&h.app, true);
+    record.quote_source = Some("Tom & Jerry <1940>".to_string());
+
+    let theme = Theme::default();
+    let svg = render_svg(&record, &theme);
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.trim_end().ends_with("</svg>"));
+    assert!(svg.contains(&format!(">{:.0}</text>", record.wpm.unwrap())));
+    assert!(svg.contains("words 4 english"));
+    assert!(svg.contains(&format!(r#"fill="{}""#, theme.bg)));
+    // one line each for wpm and raw
+    assert_eq!(svg.matches("<polyline").count(), 2);
+    assert!(svg.contains("Tom &amp; Jerry &lt;1940&gt;"));
+}
+
+#[test]
+fn token_catches_an_edited_result() {
+    let mut h = Harness::new(Mode::Words(4), &["one", "two", "three", "four"]);
+    h.type_at_wpm("one two three four", 60.0);
+    let mut record = build_record(&h.app, true);
+    let token = record.token.clone().unwrap();
+    assert_eq!(token.len(), 9);
+    assert_eq!(check(&record), Some(true));
+    // the card carries what the token was made of, so it can be worked out again from the card alone
+    let svg = render_svg(&record, &Theme::default());
+    assert!(svg.contains(&token));
+    let (text, keys) = (record.text_digest.clone().unwrap(), record.keys_digest.clone().unwrap());
+    let (wpm, accuracy) = (record.wpm.unwrap(), record.accuracy.unwrap());
+    assert!(svg.contains(&format!(r#"data-text="{}" data-keys="{}" data-wpm="{:.2}" data-accuracy="{:.2}" data-secs="{:.2}""#, text, keys, wpm, accuracy, record.duration_secs)));
+    assert_eq!(typa::verify::token(&text, &keys, wpm, accuracy, record.duration_secs), token);
+
+    // the same test typed the same way comes to the same token
+    let mut again = Harness::new(Mode::Words(4), &["one", "two", "three", "four"]);
+    again.type_at_wpm("one two three four", 60.0);
+    assert_eq!(build_record(&again.app, true).token, Some(token));
+
+    record.wpm = record.wpm.map(|w| w + 10.0);
+    assert_eq!(check(&record), Some(false));
+    assert_eq!(check(&build_record(&h.app, false)), None);
+}
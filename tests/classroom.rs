@@ -0,0 +1,89 @@
+use chrono::TimeZone;
+use typa::app::TestOptions;
+use std::time::Duration;
+use typa::classroom::{field, parse_submission, serve, server_address, Roster, Submission, Uploads};
+use typa::models::{FailReason, FailRules, Mode};
+use typa::testing::{Harness, BACKSPACE};
+
+#[test]
+fn accuracy_below_the_minimum_fails_the_test() {
+    let options = TestOptions {
+        mode: Mode::Words(2),
+        fail_rules: FailRules { min_accuracy: Some(95.0), ..FailRules::default() },
+        ..TestOptions::default()
+    };
+    let mut h = Harness::with_options(options.clone(), &["hello", "world"]);
+    // one wrong key in twelve
+    h.type_at_wpm(&format!("hellp{BACKSPACE}o world"), 60.0);
+    assert!(h.is_finished());
+    assert!(matches!(h.app.test.fail_reason, Some(FailReason::BelowMinAccuracy { .. })));
+
+    let at = chrono::Local.with_ymd_and_hms(2026, 3, 9, 14, 5, 0).unwrap();
+    assert_eq!(
+        Submission::from_app("Ana, B.", &h.app, at).row(),
+        "\"Ana, B.\",2026-03-09,14:05,word 2 english,55,91.7,no,min accuracy: finished at 91.7% (needed 95%)"
+    );
+
+    let mut h = Harness::with_options(options, &["hello", "world"]);
+    h.type_at_wpm("hello world", 60.0);
+    assert_eq!(h.app.test.fail_reason, None);
+    assert!(Submission::from_app("Ana", &h.app, at).row().ends_with(",yes,"));
+}
+
+#[test]
+fn fields_are_quoted_only_when_they_need_it() {
+    assert_eq!(field("Ana"), "Ana");
+    assert_eq!(field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    assert_eq!(field("=SUM(A1)"), "\"'=SUM(A1)\"");
+}
+
+#[test]
+fn the_default_port_goes_on_any_server_without_one() {
+    assert_eq!(server_address("192.168.1.20"), "192.168.1.20:7878");
+    assert_eq!(server_address("teacher.local"), "teacher.local:7878");
+    assert_eq!(server_address("teacher.local:9000"), "teacher.local:9000");
+    assert_eq!(server_address("fe80::1"), "[fe80::1]:7878");
+    assert_eq!(server_address("[fe80::1]"), "[fe80::1]:7878");
+    assert_eq!(server_address("[fe80::1]:9000"), "[fe80::1]:9000");
+}
+
+#[test]
+fn the_server_keeps_each_students_latest_and_best() {
+    let line = |name: &str, wpm: f64, passed: bool| format!(
+        r#"{{"student":"{}","date":"2026-03-09","time":"14:05","test":"time 60 english","wpm":{},"accuracy":96.5,"passed":{}}}"#,
+        name, wpm, passed,
+    );
+    let mut roster = Roster::default();
+    for (name, wpm, passed) in [("Ana", 40.0, true), ("Ben\\u001b[2J", 55.0, false), ("Ana", 35.0, true)] {
+        roster.add(parse_submission(&line(name, wpm, passed)).unwrap());
+    }
+    assert_eq!(roster.results, 3);
+    let ana = &roster.students[0];
+    assert_eq!((ana.tests, ana.passed, ana.best_wpm, ana.latest.wpm), (2, 2, 40.0, 35.0));
+    // control characters never reach the screen, and a failed test isn't anyone's best
+    assert_eq!(roster.students[1].name, "Ben[2J");
+    assert_eq!(roster.students[1].best_wpm, 0.0);
+
+    assert!(parse_submission("not json").is_none());
+    assert!(parse_submission(&line("  ", 40.0, true)).is_none());
+    assert!(parse_submission(&line("Ana", 40.0, true).replace("96.5", "250")).is_none());
+}
+
+#[test]
+fn uploads_say_how_they_went() {
+    let submission = parse_submission(r#"{"student":"Ana","date":"2026-03-09","time":"14:05","test":"time 60 english","wpm":40,"accuracy":96.5,"passed":true}"#).unwrap();
+    let free_port = || std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+
+    let port = free_port();
+    let received = serve(&format!("127.0.0.1:{}", port)).unwrap();
+    let mut uploads = Uploads::default();
+    uploads.send(format!("127.0.0.1:{}", port), submission.clone());
+    assert!(uploads.is_pending());
+    assert_eq!(uploads.wait(Duration::from_secs(5)).unwrap().unwrap(), format!("127.0.0.1:{}", port));
+    assert_eq!(received.recv_timeout(Duration::from_secs(1)).unwrap(), submission);
+
+    // nobody listening is an error to show, not one to drop
+    uploads.send(format!("127.0.0.1:{}", free_port()), submission);
+    assert!(uploads.wait(Duration::from_secs(5)).unwrap().is_err());
+    assert!(!uploads.is_pending());
+}
@@ -0,0 +1,29 @@
+use std::process::Command;
+
+/// typa with `args`, kept away from the real config and data directories
+fn typa(args: &[&str]) -> std::process::Output {
+    let home = std::env::temp_dir().join(format!("typa-cli-{}", std::process::id()));
+    Command::new(env!("CARGO_BIN_EXE_typa"))
+        .args(args)
+        .env("XDG_CONFIG_HOME", home.join("config"))
+        .env("XDG_DATA_HOME", home.join("data"))
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn speed_flags_take_a_value_either_way_and_leave_the_command_alone() {
+    for args in [
+        ["-w", "10", "--pace", "80", "generate", "-c", "3"].as_slice(),
+        &["-w", "10", "--pace=80", "--min-wpm", "40", "generate", "-c", "3"],
+        &["--min-wpm=40", "generate", "-c", "3"],
+        &["--pace-calibrated", "--min-wpm-calibrated", "generate", "-c", "3"],
+    ] {
+        let out = typa(args);
+        assert!(out.status.success(), "{:?}: {}", args, String::from_utf8_lossy(&out.stderr));
+        assert_eq!(String::from_utf8_lossy(&out.stdout).split_whitespace().count(), 3, "{:?}", args);
+    }
+
+    // a speed and the saved one can't both be meant
+    assert!(!typa(&["--pace", "80", "--pace-calibrated", "generate"]).status.success());
+}
@@ -0,0 +1,32 @@
+use typa::app::{App, TestOptions};
+use typa::clock::ManualClock;
+use typa::config::{set_toml_keys, AccessibilityConfig, AppConfig};
+
+#[test]
+fn setting_keys_keeps_the_rest_of_the_file() {
+    let text = "# my theme\n[theme]\nbg = \"#000000\"\n\n[pace]\nwpm = 50 # old\n\n[words]\nrepeat = 2\n";
+    let out = set_toml_keys(text, "pace", &[("wpm", "72".into()), ("min_wpm", "58".into())]);
+    assert_eq!(out, "# my theme\n[theme]\nbg = \"#000000\"\n\n[pace]\nwpm = 72\nmin_wpm = 58\n\n[words]\nrepeat = 2\n");
+}
+
+#[test]
+fn a_missing_section_goes_at_the_end() {
+    let out = set_toml_keys("[theme]\nbg = \"#000000\"\n", "pace", &[("wpm", "72".into())]);
+    assert_eq!(out, "[theme]\nbg = \"#000000\"\n\n[pace]\nwpm = 72\n");
+    assert_eq!(set_toml_keys("", "pace", &[("wpm", "72".into())]), "[pace]\nwpm = 72\n");
+}
+
+#[test]
+fn reduced_motion_stills_everything_at_once() {
+    let mut accessibility = AccessibilityConfig::default();
+    assert!(accessibility.animates() && accessibility.blinks_caret());
+    // accessible mode keeps the caret still but leaves the rest
+    accessibility.enabled = true;
+    assert!(accessibility.animates() && !accessibility.blinks_caret());
+
+    let accessibility = AccessibilityConfig { reduced_motion: true, ..AccessibilityConfig::default() };
+    assert!(!accessibility.animates() && !accessibility.blinks_caret());
+    let config = AppConfig { accessibility, ..AppConfig::default() };
+    let app = App::detached(TestOptions::default(), config, Box::new(ManualClock::new())).unwrap();
+    assert!(!app.config.confetti);
+}
@@ -0,0 +1,75 @@
+use std::time::Duration;
+use typa::demo::DemoTyper;
+use typa::models::{AppState, Mode};
+use typa::testing::Harness;
+
+#[test]
+fn demo_types_the_test_to_the_end_and_starts_another() {
+    let mut h = Harness::new(Mode::Words(3), &["the", "quick", "fox"]);
+    h.app.start_demo();
+    let mut demo = DemoTyper::new(100.0, h.app.now());
+
+    let mut finished = false;
+    for _ in 0..400 {
+        h.advance(Duration::from_millis(50));
+        demo.step(&mut h.app);
+        if h.is_finished() {
+            finished = true;
+            break;
+        }
+    }
+    assert!(finished, "demo never finished the test");
+    // typos get fixed on the spot, so nothing uncorrected is left behind
+    assert_eq!(h.app.resolved_char_stats().1, 0);
+
+    h.advance(Duration::from_secs(3));
+    demo.step(&mut h.app);
+    assert_eq!(h.app.test.state, AppState::Waiting);
+}
+
+#[test]
+fn repeating_after_the_demo_repeats_the_players_test() {
+    let mut h = Harness::new(Mode::Words(3), &["the", "quick", "fox"]);
+    let mine: Vec<String> = ["lazy", "brown", "dog"].iter().map(|w| w.to_string()).collect();
+    h.app.last_test_words = Some(mine.clone());
+    h.app.start_demo();
+    let mut demo = DemoTyper::new(100.0, h.app.now());
+    for _ in 0..400 {
+        h.advance(Duration::from_millis(50));
+        demo.step(&mut h.app);
+        if h.is_finished() { break; }
+    }
+    assert!(h.is_finished(), "demo never finished the test");
+
+    h.app.stop_demo();
+    assert_eq!(h.app.last_test_words, Some(mine.clone()));
+    h.app.retry_last_test();
+    assert_eq!(h.app.test.words(), mine);
+}
+
+#[test]
+fn scripts_turn_into_timed_keys() {
+    use typa::demo::{parse_script, ScriptKey, ScriptPlayer};
+
+    let script = parse_script("# a typo, fixed\nwords the fox\nwpm 60\ntype th\nwait 1.5s\npress backspace 2\n\npress tab\n").unwrap();
+    assert_eq!(script.words, Some(vec!["the".to_string(), "fox".to_string()]));
+    // 60 wpm is five keys a second
+    let keys: Vec<(u128, ScriptKey)> = script.keys.iter().map(|(at, k)| (at.as_millis(), *k)).collect();
+    assert_eq!(keys, vec![
+        (0, ScriptKey::Char('t')),
+        (200, ScriptKey::Char('h')),
+        (1900, ScriptKey::Backspace),
+        (2100, ScriptKey::Backspace),
+        (2300, ScriptKey::Tab),
+    ]);
+
+    let start = std::time::Instant::now();
+    let mut player = ScriptPlayer::new(&script, start);
+    assert_eq!(player.next_due(start), Some(ScriptKey::Char('t')));
+    assert_eq!(player.next_due(start), None);
+    assert_eq!(player.until_next(start), Some(Duration::from_millis(200)));
+
+    let err = parse_script("wpm 60\ntpye oops\n").unwrap_err();
+    assert!(err.to_string().starts_with("line 2:"), "{}", err);
+    assert!(parse_script("type a\nwords late\n").is_err());
+}
@@ -0,0 +1,26 @@
+use typa::doctor::{check_history, split_history, Level, Repair};
+
+const RECORD: &str = r#"{"timestamp":"2026-01-01T10:00:00Z","completed":true,"mode":"time","mode_value":"30","language":"english","use_punctuation":false,"use_numbers":false,"duration_secs":30.0,"wpm":50.0,"raw_wpm":null,"accuracy":null,"consistency":null,"correct_chars":null,"incorrect_chars":null,"extra_chars":null,"missed_chars":null,"correct_keystrokes":null,"incorrect_keystrokes":null,"total_keystrokes":null,"quote_source":null,"wpm_history":null,"raw_wpm_history":null,"errors_history":null}"#;
+
+#[test]
+fn a_broken_history_line_is_found_and_set_aside() {
+    let raw = format!("{}\n{{\"timestamp\": \"cut off\n{}\n", RECORD, RECORD);
+    let findings = check_history(&raw);
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].level, Level::Problem);
+    assert!(findings[0].message.starts_with("line 2 "), "{}", findings[0].message);
+    assert_eq!(findings[0].repair, Some(Repair::SetAsideHistoryLines));
+
+    let (good, bad) = split_history(&raw);
+    assert_eq!(good, vec![RECORD, RECORD]);
+    assert_eq!(bad, vec!["{\"timestamp\": \"cut off"]);
+}
+
+#[test]
+fn a_readable_history_only_gets_warnings() {
+    let undated = RECORD.replace("2026-01-01T10:00:00Z", "yesterday");
+    let findings = check_history(&format!("{}\n{}\n", RECORD, undated));
+    assert_eq!(findings[0].level, Level::Ok);
+    assert!(findings[1..].iter().all(|f| f.level == Level::Warning && f.repair.is_none()));
+    assert!(findings[1].message.contains("timestamp"));
+}
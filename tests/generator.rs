@@ -0,0 +1,178 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use typa::app::load_word_data;
+use typa::generator::{RepeatRules, StreamTail, WordGenerator};
+use typa::generator::difficulty::difficulty;
+use typa::generator::sourcing::{bucket_quotes, kids_words, TextSource};
+use typa::generator::formatting::{apply_contextual_capitalization, finalize_stream_punctuation};
+use typa::generator::punctuation::{generate_number, to_roman, NumberStyles, PunctuationRules};
+use typa::generator::word_controller::generate_next_word;
+use typa::config::QuoteConfig;
+use typa::models::{NumberFormat, QuoteCredit, QuoteData, QuoteEntry, QuoteThresholds, WordData};
+use typa::utils::strings::{capitalize_word, is_sentence_end};
+
+#[test]
+fn long_streams_hit_the_exact_count_and_follow_the_seed() {
+    let generator = WordGenerator::new(load_word_data("english").unwrap(), true, true);
+    let a = generator.generate_stream(10_000, &mut StdRng::seed_from_u64(7));
+    let b = generator.generate_stream(10_000, &mut StdRng::seed_from_u64(7));
+    assert_eq!(a.len(), 10_000);
+    assert_eq!(a, b);
+    // sentence state carries across the whole stream, not just the first batch
+    let lowercase_after_stop = a.windows(2)
+        .filter(|w| is_sentence_end(&w[0]) && w[1].chars().next().is_some_and(char::is_lowercase))
+        .count();
+    assert_eq!(lowercase_after_stop, 0);
+}
+
+#[test]
+fn harder_text_scores_higher() {
+    let easy = difficulty("the and of to in is it".split(' ')).unwrap();
+    let prose = difficulty("people often wonder about their place within the world".split(' ')).unwrap();
+    let hard = difficulty("Quixotic, jazzy (vexing) objections: 42 of them!".split(' ')).unwrap();
+    assert!(easy < 1.0, "{easy}");
+    assert!((prose - 1.0).abs() < 0.15, "{prose}");
+    assert!(hard > prose + 0.5, "{hard}");
+    assert_eq!(difficulty([]), None);
+}
+
+fn small_list() -> WordData {
+    WordData { name: "small".into(), words: (0..30).map(|i| format!("w{i}")).collect(), sentences: None, number_format: Default::default() }
+}
+
+#[test]
+fn repeats_stay_out_of_the_window() {
+    let repeats = RepeatRules { window: 8, retries: 50, forbid: false };
+    let generator = WordGenerator::new(small_list(), false, false).with_repeats(repeats);
+    let stream = generator.generate_stream(500, &mut StdRng::seed_from_u64(3));
+    for (i, word) in stream.iter().enumerate().skip(30) {
+        assert!(!stream[i - 8..i].contains(word), "{word} repeated at {i}");
+    }
+}
+
+#[test]
+fn forbidden_repeats_go_round_the_whole_list() {
+    let repeats = RepeatRules { forbid: true, ..Default::default() };
+    let generator = WordGenerator::new(small_list(), false, false).with_repeats(repeats);
+    let stream = generator.generate_stream(90, &mut StdRng::seed_from_u64(3));
+    for word in &small_list().words {
+        assert_eq!(stream.iter().filter(|w| *w == word).count(), 3, "{word}");
+    }
+}
+
+#[test]
+fn a_capital_or_a_comma_doesnt_make_a_new_word() {
+    let list = WordData { name: "tiny".into(), words: vec!["one".into(), "two".into(), "three".into()], sentences: None, number_format: Default::default() };
+    let source = TextSource::new(list);
+    let repeats = RepeatRules { forbid: true, ..Default::default() };
+    let rules = PunctuationRules { use_punctuation: false, use_numbers: false, number_format: Default::default(), number_styles: Default::default() };
+    let mut rng = StdRng::seed_from_u64(1);
+    for _ in 0..20 {
+        let mut tail = StreamTail::from_words(&["One.".into(), "(two),".into()], &repeats);
+        assert_eq!(generate_next_word(&source, &rules, &repeats, &mut tail, &mut rng), ["three"]);
+    }
+}
+
+#[test]
+fn sentences_fill_the_templates() {
+    let generator = WordGenerator::new(load_word_data("english").unwrap(), false, true).with_sentences().unwrap();
+    let stream = generator.generate_stream(200, &mut StdRng::seed_from_u64(5));
+    assert_eq!(stream.len(), 200);
+    // each sentence opens with a capital, and has at least a subject, verb and object
+    let text = stream.join(" ");
+    for sentence in text.split_terminator(". ").take(20) {
+        assert!(sentence.starts_with(char::is_uppercase), "{sentence}");
+        assert!(sentence.split(' ').count() >= 4, "{sentence}");
+    }
+
+    assert!(WordGenerator::new(small_list(), false, false).with_sentences().is_err());
+}
+
+fn stream(words: &str) -> Vec<String> {
+    words.split(' ').map(str::to_string).collect()
+}
+
+#[test]
+fn sentences_end_inside_quotes_and_brackets() {
+    let mut words = stream("\"stop.\" then (see above!) \"fine\" done... why \"now?\"");
+    finalize_stream_punctuation(&mut words);
+    assert_eq!(words.join(" "), "\"Stop.\" Then (see above!) \"Fine\" done... why \"now?\"");
+
+    let mut words = stream("\"quoted\"");
+    finalize_stream_punctuation(&mut words);
+    assert_eq!(words, ["\"Quoted\"."]);
+
+    let mut next = stream("(well");
+    apply_contextual_capitalization(&mut next, Some("it.)"), true);
+    assert_eq!(next, ["(Well"]);
+}
+
+#[test]
+fn capitalization_skips_leading_marks() {
+    for (word, capitalized) in [("\u{201C}hello", "\u{201C}Hello"), ("\u{00BF}qu\u{00E9}", "\u{00BF}Qu\u{00E9}"), ("\u{00E9}t\u{00E9}", "\u{00C9}t\u{00E9}"), ("42", "42")] {
+        let mut w = word.to_string();
+        capitalize_word(&mut w);
+        assert_eq!(w, capitalized);
+    }
+}
+
+#[test]
+fn numbers_use_the_language_separators() {
+    let mut rng = StdRng::seed_from_u64(11);
+    let indonesian = NumberFormat { decimal: ',', thousands: Some('.') };
+    let numbers: Vec<String> = (0..400).map(|_| generate_number(&mut rng, &indonesian, &NumberStyles::default())).collect();
+    assert!(numbers.iter().any(|n| n.len() == 5 && n.chars().nth(1) == Some('.')), "no grouped thousands");
+    assert!(numbers.iter().any(|n| n.len() <= 4 && n.contains(',')), "no decimals");
+    assert!(numbers.iter().all(|n| !n.ends_with('.') && !n.contains(",,")));
+
+    let numbers: Vec<String> = (0..400).map(|_| generate_number(&mut rng, &NumberFormat::default(), &NumberStyles::default())).collect();
+    assert!(numbers.iter().all(|n| !n.contains(',')));
+    assert!(numbers.iter().any(|n| n.len() == 4 && n.chars().all(|c| c.is_ascii_digit())));
+}
+
+#[test]
+fn extra_number_styles_show_up_when_on() {
+    assert_eq!(to_roman(14), "XIV");
+    assert_eq!(to_roman(1994), "MCMXCIV");
+
+    let mut rng = StdRng::seed_from_u64(5);
+    let styles = NumberStyles { roman: true, units: true, dimensions: true, times: true };
+    let numbers: Vec<String> = (0..400).map(|_| generate_number(&mut rng, &NumberFormat::default(), &styles)).collect();
+    assert!(numbers.iter().any(|n| !n.is_empty() && n.chars().all(|c| "IVXLCDM".contains(c))));
+    assert!(numbers.iter().any(|n| n.ends_with("kg") || n.ends_with("GB") || n.ends_with("km")));
+    assert!(numbers.iter().any(|n| n.split_once('x').is_some_and(|(w, h)| w.parse::<u32>().is_ok() && h.parse::<u32>().is_ok())));
+    assert!(numbers.iter().any(|n| n.split_once(':').is_some_and(|(h, m)| h.parse::<u32>().is_ok_and(|h| h < 24) && m.len() == 2)));
+}
+
+#[test]
+fn kids_words_are_short_and_common() {
+    let english = load_word_data("english").unwrap();
+    let words = kids_words(&english.words);
+    assert!(words.len() >= 50);
+    assert!(words.iter().all(|w| (2..=4).contains(&w.len()) && w.chars().all(|c| c.is_ascii_lowercase())));
+    assert!(words.contains(&"the".to_string()));
+}
+
+#[test]
+fn quote_credits_are_optional() {
+    let plain: QuoteEntry = serde_json::from_str(r#"{"id": 1, "text": "hi", "source": "Anon", "length": 2}"#).unwrap();
+    assert_eq!(plain.credit(), QuoteCredit { source: "Anon".into(), attribution_url: None, license: None });
+
+    let credited: QuoteEntry = serde_json::from_str(
+        r#"{"id": 2, "text": "hi", "source": "Ada", "license": "CC BY 4.0", "attribution_url": "https://example.org/2"}"#,
+    ).unwrap();
+    assert_eq!(credited.credit().license.as_deref(), Some("CC BY 4.0"));
+    assert_eq!(credited.credit().attribution_url.as_deref(), Some("https://example.org/2"));
+}
+
+#[test]
+fn quote_thresholds_as_big_as_they_go_just_make_everything_short() {
+    let mut quotes: QuoteData = serde_json::from_str(
+        r#"{"language": "english", "quotes": [{"id": 1, "text": "hi there", "source": "Anon"}]}"#,
+    ).unwrap();
+    let config = QuoteConfig { rebucket: true, short: usize::MAX, medium: 0, long: usize::MAX - 1, ..QuoteConfig::default() };
+    let thresholds = bucket_quotes(&mut quotes, &config);
+    assert_eq!(thresholds, QuoteThresholds { short: usize::MAX, medium: usize::MAX, long: usize::MAX });
+    assert_eq!(quotes.groups[0], [0, usize::MAX]);
+    assert_eq!(quotes.quotes[0].length, 8);
+}
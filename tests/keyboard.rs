@@ -0,0 +1,57 @@
+use typa::config::{KeyboardGeometry, KeyboardLayout};
+use typa::keyboard::{finger, hand_stats, keymap, Finger};
+
+fn log(keys: &str, gap: f64) -> Vec<(f64, Option<char>)> {
+    keys.chars().enumerate().map(|(i, c)| (i as f64 * gap, (c != '\x08').then_some(c))).collect()
+}
+
+#[test]
+fn fingers_follow_the_layout() {
+    assert_eq!(finger(KeyboardLayout::Qwerty, 'e'), Some(Finger::LeftMiddle));
+    assert_eq!(finger(KeyboardLayout::Qwerty, 'E'), Some(Finger::LeftMiddle));
+    assert_eq!(finger(KeyboardLayout::Qwerty, '('), Some(Finger::RightRing));
+    assert_eq!(finger(KeyboardLayout::Dvorak, 'e'), Some(Finger::LeftMiddle));
+    assert_eq!(finger(KeyboardLayout::Colemak, 'e'), Some(Finger::RightMiddle));
+    assert_eq!(finger(KeyboardLayout::Qwerty, 'é'), None);
+}
+
+#[test]
+fn pairs_are_sorted_by_how_the_hands_moved() {
+    // "ed" same finger, "de" again, "ek" alternates, "kl" same hand, and the space and the
+    // doubled "ll" don't count
+    let stats = hand_stats(KeyboardLayout::Qwerty, &log("edekll e", 0.1));
+    assert_eq!(stats.same_finger.count, 2);
+    assert_eq!(stats.alternating.count, 1);
+    assert_eq!(stats.same_hand.count, 1);
+    assert_eq!(stats.share(&stats.same_finger), 50.0);
+    // a key every tenth of a second is 120 wpm
+    assert!((stats.alternating.wpm().unwrap() - 120.0).abs() < 1e-9);
+
+    // a backspace breaks the pair
+    let stats = hand_stats(KeyboardLayout::Qwerty, &log("e\x08d", 0.1));
+    assert_eq!(stats.total(), 0);
+}
+
+#[test]
+fn boards_place_keys_by_their_shape() {
+    let place = |geometry, c| {
+        let keys = keymap(KeyboardLayout::Qwerty, geometry);
+        keys.iter().find(|k| k.label == c).map(|k| (k.row, k.x))
+    };
+    // ansi rows step right, ortho ones line up in columns
+    assert_eq!(place(KeyboardGeometry::Ansi, 'q'), Some((1, 6)));
+    assert_eq!(place(KeyboardGeometry::Ansi, 'z'), Some((3, 9)));
+    assert_eq!(place(KeyboardGeometry::Ortho, 'q'), place(KeyboardGeometry::Ortho, '1').map(|(_, x)| (1, x)));
+    assert_eq!(place(KeyboardGeometry::Ortho, 'z'), Some((3, 4)));
+
+    // iso's extra key beside left shift
+    assert_eq!(place(KeyboardGeometry::Iso, '\\'), Some((3, 5)));
+    assert_eq!(place(KeyboardGeometry::Iso, 'z'), Some((3, 9)));
+
+    // a split board parts between the hands
+    let gap = |a, b| place(KeyboardGeometry::Split, b).unwrap().1 - place(KeyboardGeometry::Split, a).unwrap().1;
+    assert_eq!(gap('t', 'y'), 8);
+    assert_eq!(gap('r', 't'), 4);
+    assert_eq!(gap('5', '6'), 8);
+    assert_eq!(keymap(KeyboardLayout::Dvorak, KeyboardGeometry::Split).len(), 47);
+}
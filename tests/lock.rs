@@ -0,0 +1,44 @@
+use typa::lock::{reply_object_path, session_path, SignalParser};
+
+#[test]
+fn screensaver_signals_carry_the_state_on_the_next_line() {
+    let mut p = SignalParser::default();
+    let header = "signal time=1700000000.1 sender=:1.42 -> destination=(null destination) serial=9 path=/org/gnome/ScreenSaver; interface=org.gnome.ScreenSaver; member=ActiveChanged";
+    assert_eq!(p.feed(header), None);
+    assert_eq!(p.feed("   boolean true"), Some(true));
+    // a stray bool with no header in front of it means nothing
+    assert_eq!(p.feed("   boolean false"), None);
+    p.feed(header);
+    assert_eq!(p.feed("   boolean false"), Some(false));
+}
+
+#[test]
+fn xfce_locking_is_heard_too() {
+    let mut p = SignalParser::default();
+    p.feed("signal time=1.0 sender=:1.9 -> destination=(null destination) serial=4 path=/org/xfce/ScreenSaver; interface=org.xfce.ScreenSaver; member=ActiveChanged");
+    assert_eq!(p.feed("   boolean true"), Some(true));
+}
+
+#[test]
+fn logind_lock_and_unlock_count_only_for_our_session() {
+    let signal = |session: &str, member: &str| format!("signal time=1.0 sender=:1.3 -> destination=(null destination) serial=5 path=/org/freedesktop/login1/session/{session}; interface=org.freedesktop.login1.Session; member={member}");
+    let mut p = SignalParser::for_session(&session_path("2"));
+    assert_eq!(p.feed(&signal("_32", "Lock")), Some(true));
+    assert_eq!(p.feed(&signal("_32", "Unlock")), Some(false));
+    assert_eq!(p.feed(&signal("_32", "PropertiesChanged")), None);
+    // someone else's session locking is no reason to pause
+    assert_eq!(p.feed(&signal("_37", "Lock")), None);
+    assert_eq!(p.feed(&signal("c2", "Lock")), None);
+    // and without knowing our own, no session's count
+    assert_eq!(SignalParser::default().feed(&signal("_32", "Lock")), None);
+}
+
+#[test]
+fn session_paths_are_escaped_like_logind_does() {
+    assert_eq!(session_path("2"), "/org/freedesktop/login1/session/_32");
+    assert_eq!(session_path("c12"), "/org/freedesktop/login1/session/c12");
+    assert_eq!(session_path("a-b"), "/org/freedesktop/login1/session/a_2db");
+    let reply = "method return time=1.0 sender=:1.2 -> destination=:1.99 serial=7 reply_serial=2\n   object path \"/org/freedesktop/login1/session/_33\"\n";
+    assert_eq!(reply_object_path(reply).as_deref(), Some("/org/freedesktop/login1/session/_33"));
+    assert_eq!(reply_object_path("Error org.freedesktop.login1.NoSessionForPID"), None);
+}
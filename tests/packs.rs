@@ -0,0 +1,18 @@
+use typa::packs::{resolve_url, valid_name};
+
+#[test]
+fn pack_urls_are_taken_from_next_to_the_index() {
+    let index = "https://example.org/typa-packs/index.json";
+    assert_eq!(resolve_url(index, "german.json"), "https://example.org/typa-packs/german.json");
+    assert_eq!(resolve_url(index, "./quotes/german.json"), "https://example.org/typa-packs/quotes/german.json");
+    assert_eq!(resolve_url(index, "https://cdn.example.org/g.json"), "https://cdn.example.org/g.json");
+}
+
+#[test]
+fn pack_names_cant_leave_the_pack_directory() {
+    assert!(valid_name("german"));
+    assert!(valid_name("english_1k"));
+    assert!(!valid_name("../english"));
+    assert!(!valid_name("a/b"));
+    assert!(!valid_name(""));
+}
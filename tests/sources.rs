@@ -0,0 +1,108 @@
+use typa::app::{load_quote_data, load_word_data, quotes_for, App, TestOptions};
+use typa::clock::ManualClock;
+use typa::config::AppConfig;
+use typa::models::{Mode, QuoteLength, QuoteSelector};
+use typa::sources::{clean_commit_subjects, clean_man_text, detect_language, one_per_language, CustomText};
+
+#[test]
+fn man_text_keeps_only_the_description() {
+    let raw = [
+        "LS(1)                User Commands                LS(1)",
+        "",
+        "N\u{8}NA\u{8}AM\u{8}ME\u{8}E",
+        "       ls - list directory contents",
+        "",
+        "DESCRIPTION",
+        "       List  information  about  the  FILEs (the current directory by default).",
+        "       Sort entries \u{1b}[1malphabetically\u{1b}[0m if none of _\u{8}-_\u{8}c is specified.",
+        "",
+        "AUTHOR",
+        "       Written by Richard M. Stallman.",
+        "",
+        "GNU coreutils 9.1              2023              LS(1)",
+    ].join("\n");
+    assert_eq!(
+        clean_man_text(&raw),
+        "List information about the FILEs (the current directory by default). Sort entries alphabetically if none of -c is specified."
+    );
+}
+
+#[test]
+fn long_pages_are_cut_on_a_sentence_end() {
+    let sentence = "one two three four five six seven eight nine.";
+    let raw = format!("DESCRIPTION\n{}\n", vec![format!("       {}", sentence); 40].join("\n"));
+    let text = clean_man_text(&raw);
+    assert_eq!(text.split(' ').count(), 297);
+    assert!(text.ends_with("nine."));
+}
+
+#[test]
+fn custom_text_is_typed_as_a_single_quote() {
+    let custom = CustomText { text: "tar stores and extracts files".to_string(), source: "man tar".to_string() };
+    let options = TestOptions { mode: Mode::Time(30), custom_text: Some(custom), ..TestOptions::default() };
+    let app = App::detached(options, AppConfig::default(), Box::new(ManualClock::new())).unwrap();
+
+    assert!(matches!(app.config.mode, Mode::Quote(_)));
+    let words: Vec<String> = app.test.text.words().iter().map(|w| w.target().iter().collect()).collect();
+    assert_eq!(words, ["tar", "stores", "and", "extracts", "files"]);
+    assert_eq!(app.test.quote_source_label(), "man tar");
+}
+
+#[test]
+fn commit_subjects_become_sentences() {
+    let raw = [
+        "fix(parser): handle empty input (#123)",
+        "[uint82/typa#synth-5021] Practice on installed man pages with --man",
+        "Bump version to 0.4.0",
+        "fix(parser): Handle empty input",
+        "Résumé support",
+        "Fix crash on resize (GH-42)",
+        "Why does this break?",
+        "Note: read dates as ISO-8601 in UTF-8",
+        "feat(config)!: Drop the old keys",
+    ].join("\n");
+    assert_eq!(
+        clean_commit_subjects(&raw),
+        "Handle empty input. Practice on installed man pages with --man. Bump version to 0.4.0. Fix crash on resize. Why does this break? \
+         Note: read dates as ISO-8601 in UTF-8. Drop the old keys."
+    );
+}
+
+#[test]
+fn quotes_are_optional_and_can_be_borrowed() {
+    let quote = Mode::Quote(QuoteSelector::Category(QuoteLength::All));
+    assert!(load_quote_data("klingon").unwrap().quotes.is_empty());
+
+    let (quotes, notice) = quotes_for(&quote, "klingon", None).unwrap();
+    assert!(quotes.quotes.is_empty());
+    assert!(notice.unwrap().contains("fallback"));
+
+    let (quotes, notice) = quotes_for(&quote, "klingon", Some("english")).unwrap();
+    assert!(!quotes.quotes.is_empty());
+    assert_eq!(notice.as_deref(), Some("no klingon quotes yet, these are english"));
+
+    // a language with quotes of its own keeps them, and other modes load none at all
+    assert_eq!(quotes_for(&quote, "english", Some("indonesian")).unwrap().1, None);
+    assert!(quotes_for(&Mode::Time(30), "english", None).unwrap().0.quotes.is_empty());
+}
+
+#[test]
+fn custom_text_language_is_told_by_its_common_words() {
+    let languages: Vec<(String, Vec<String>)> = ["english", "indonesian"]
+        .iter()
+        .map(|name| (name.to_string(), load_word_data(name).unwrap().words))
+        .collect();
+
+    let english = "The cat sat on the mat, and it was happy to have found a place that felt like home.";
+    assert_eq!(detect_language(english, &languages), Some("english"));
+    let indonesian = "Aku akan pergi ke pasar karena ada banyak buah yang segar, dan ibu ingin membuat kue untuk kami.";
+    assert_eq!(detect_language(indonesian, &languages), Some("indonesian"));
+
+    // too little to go on, or nothing either list knows
+    assert_eq!(detect_language("the cat", &languages), None);
+    assert_eq!(detect_language("fn main() { println!(); }", &languages), None);
+
+    // sizes of one language's list don't compete with each other
+    let names = ["english_1k", "indonesian", "english", "german_1k", "english_10k", "german_10k"].map(String::from).to_vec();
+    assert_eq!(one_per_language(names), ["english", "german_10k", "indonesian"]);
+}
@@ -0,0 +1,188 @@
+use typa::history::metrics::prometheus;
+use typa::history::TestRecord;
+use typa::statusline::StatusSummary;
+use typa::app::{calibrated_speeds, StepSummary};
+use typa::history::stats::top_percent;
+use typa::history::compare::{compare_rows, index_of_test};
+use typa::history::counters::{compact, Counters};
+use typa::history::quotas::{progress, window_start};
+use typa::config::{Period, Quota};
+use typa::history::build_record;
+use typa::models::Mode;
+use typa::testing::Harness;
+
+#[test]
+fn rank_counts_the_past_results_above() {
+    let past = [40.0, 50.0, 60.0, 70.0, 80.0, 90.0, 100.0, 110.0, 120.0];
+    assert_eq!(top_percent(&past, 125.0), Some(10.0));
+    assert_eq!(top_percent(&past, 105.0), Some(30.0));
+    assert_eq!(top_percent(&past, 30.0), Some(100.0));
+    // a tie isn't beaten
+    assert_eq!(top_percent(&past, 120.0), Some(10.0));
+    assert_eq!(top_percent(&past[..4], 125.0), None);
+}
+
+#[test]
+fn hotseat_goes_to_the_faster_player() {
+    let a = StepSummary { label: "words 10".into(), wpm: 80.2, raw_wpm: 84.0, accuracy: 97.0, consistency: 80.0, time: 9.0, failed: false };
+    assert!(StepSummary { wpm: 85.0, ..a.clone() }.beats(&a));
+    assert!(!a.beats(&a));
+    // the same rounded wpm goes to the more accurate one
+    assert!(StepSummary { wpm: 79.9, accuracy: 99.0, ..a.clone() }.beats(&a));
+    assert!(!StepSummary { wpm: 120.0, failed: true, ..a.clone() }.beats(&a));
+}
+
+fn record(timestamp: &str, wpm: f64, practice: bool) -> TestRecord {
+    serde_json::from_value(serde_json::json!({
+        "timestamp": timestamp, "completed": true, "mode": "time", "mode_value": "30", "language": "english",
+        "use_punctuation": false, "use_numbers": false, "practice": practice, "wpm": wpm, "accuracy": 96.4, "duration_secs": 30.0,
+    })).unwrap()
+}
+
+#[test]
+fn status_counts_todays_tests() {
+    let records = [
+        record("2026-03-01T09:00:00+00:00", 70.0, false),
+        record("2026-03-02T09:00:00+00:00", 75.0, false),
+        record("2026-03-02T10:00:00+00:00", 90.0, true),
+    ];
+    let today = chrono::DateTime::parse_from_rfc3339("2026-03-02T09:00:00+00:00").unwrap().with_timezone(&chrono::Local).date_naive();
+    let status = StatusSummary::from_history(&records, today);
+    assert_eq!(status.today, 1);
+    assert_eq!(status.short_text(), "90wpm 96%");
+    assert!(status.i3blocks().starts_with("90wpm 96% | 1 today"));
+
+    let waybar: serde_json::Value = serde_json::from_str(&status.waybar()).unwrap();
+    assert_eq!(waybar["class"], "done");
+    assert_eq!(waybar["today"], 1);
+    assert_eq!(waybar["last"]["test"], "time 30");
+}
+
+#[test]
+fn metrics_are_valid_prometheus_text() {
+    let records = [
+        record("2026-03-01T09:00:00+00:00", 70.0, false),
+        record("2026-03-02T09:00:00+00:00", 75.0, false),
+        record("2026-03-02T10:00:00+00:00", 90.0, true),
+    ];
+    let text = prometheus(&records);
+    assert!(text.contains("# TYPE typa_tests_total counter\ntypa_tests_total{completed=\"true\"} 3\n"));
+    assert!(text.contains("typa_seconds_typed_total 90\n"));
+    // practice runs don't set bests
+    assert!(text.contains("typa_best_wpm{mode=\"time\",mode_value=\"30\"} 75\n"));
+    assert!(text.contains("typa_last_wpm 90\n"));
+    assert!(prometheus(&[]).contains("typa_seconds_typed_total 0\n"));
+    for line in text.lines().filter(|l| !l.starts_with('#')) {
+        let (_, value) = line.rsplit_once(' ').unwrap();
+        assert!(value.parse::<f64>().is_ok(), "{line}");
+    }
+}
+
+#[test]
+fn calibration_takes_the_middle_speed() {
+    let step = |wpm: f64, failed: bool| StepSummary { label: "time 15".into(), wpm, raw_wpm: wpm, accuracy: 96.0, consistency: 80.0, time: 15.0, failed };
+    assert_eq!(calibrated_speeds(&[step(70.4, false), step(90.0, false), step(64.0, false)]), Some((70, 56)));
+    // a failed test doesn't count, and two are averaged
+    assert_eq!(calibrated_speeds(&[step(70.0, false), step(20.0, true), step(81.0, false)]), Some((76, 61)));
+    assert_eq!(calibrated_speeds(&[step(70.0, false), step(20.0, true)]), None);
+}
+
+#[test]
+fn lifetime_counters_start_from_the_history() {
+    let mut records = vec![record("2026-03-01T09:00:00+00:00", 70.0, false), record("2026-03-01T10:00:00+00:00", 80.0, false)];
+    records[0].total_keystrokes = Some(1_000);
+    records[1].total_keystrokes = Some(1_500);
+    let totals = Counters::from_history(&records);
+    assert_eq!(totals, Counters { chars: 2_500, words: 500, seconds: 60.0 });
+
+    let mut totals = Counters { chars: 1_234_567, ..totals };
+    totals += Counters { chars: 1, words: 1, seconds: 1.0 };
+    assert_eq!(totals.label(), "1.2M chars typed");
+    assert_eq!(compact(950), "950");
+    assert_eq!(compact(45_300), "45.3k");
+}
+
+#[test]
+fn diff_lines_up_two_tests() {
+    // newest first, as the history browser holds them
+    let mut records = vec![record("2026-03-02T09:00:00+00:00", 80.5, false), record("2026-03-01T09:00:00+00:00", 72.0, false)];
+    records[0].backspaces = Some(4);
+    assert_eq!(index_of_test(&records, 1), Some(1));
+    assert_eq!(index_of_test(&records, 2), Some(0));
+    assert_eq!(index_of_test(&records, 3), None);
+    assert_eq!(index_of_test(&records, 0), None);
+
+    let rows = compare_rows(&records[1], &records[0]);
+    let row = |label| rows.iter().find(|r| r.label == label).unwrap();
+    assert_eq!((row("wpm").a.as_str(), row("wpm").b.as_str(), row("wpm").diff.as_str()), ("72.0", "80.5", "+8.5"));
+    assert_eq!(row("accuracy").diff, "+0.0");
+    assert_eq!(row("test").a, "time 30");
+    // nothing to take from a missing number
+    assert_eq!((row("backspaces").a.as_str(), row("backspaces").diff.as_str()), ("-", ""));
+}
+
+#[test]
+fn quotas_count_the_current_window() {
+    use chrono::{Local, TimeZone};
+    // a wednesday
+    let now = Local.with_ymd_and_hms(2026, 3, 18, 12, 0, 0).unwrap();
+    assert_eq!(window_start(Period::Week, now), Local.with_ymd_and_hms(2026, 3, 16, 0, 0, 0).unwrap());
+    assert_eq!(window_start(Period::Month, now), Local.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap());
+
+    let at = |d, h| Local.with_ymd_and_hms(2026, 3, d, h, 0, 0).unwrap().to_rfc3339();
+    let mut records = vec![
+        record(&at(18, 9), 70.0, false),
+        record(&at(16, 9), 70.0, true),
+        record(&at(10, 9), 70.0, false),
+        record(&at(18, 10), 70.0, false),
+    ];
+    records[3].completed = false;
+
+    let quotas = [
+        Quota { tests: Some(5), per: Period::Week, ..Quota::default() },
+        Quota { tests: Some(200), hours: Some(5.0), per: Period::Month },
+        Quota::default(),
+    ];
+    let p = progress(&quotas, &records, now);
+    assert_eq!(p.len(), 3);
+    // an unfinished test counts for its time, but not as a test
+    assert_eq!((p[0].done, p[0].label()), (2.0, "tests / week".to_string()));
+    assert_eq!(p[1].summary(), "3/200 tests this month");
+    assert_eq!(p[2].count(), "0.0/5h");
+    assert_eq!(p[0].bar(5), "██░░░");
+
+    let mut week = p[0].clone();
+    for h in 13..16 { week.add(&record(&at(18, h), 70.0, false)); }
+    assert!(week.is_met());
+
+    // the next monday starts the week over, whether a test or the clock gets there first
+    let mut next = week.clone();
+    next.add(&record(&at(23, 9), 70.0, false));
+    assert_eq!((next.done, next.start), (1.0, window_start(Period::Week, Local.with_ymd_and_hms(2026, 3, 23, 9, 0, 0).unwrap())));
+    assert!(!week.roll(now));
+    assert!(week.roll(Local.with_ymd_and_hms(2026, 3, 23, 0, 30, 0).unwrap()));
+    assert_eq!(week.done, 0.0);
+}
+
+#[test]
+fn quotas_start_over_when_the_app_clock_crosses_midnight() {
+    use chrono::{Local, TimeZone};
+    let mut h = Harness::new(Mode::Words(2), &["hello", "world"]);
+    h.set_local(Local.with_ymd_and_hms(2026, 3, 18, 23, 59, 0).unwrap());
+    let earlier = record(&Local.with_ymd_and_hms(2026, 3, 18, 9, 0, 0).unwrap().to_rfc3339(), 70.0, false);
+    h.app.quotas = progress(&[Quota { tests: Some(3), per: Period::Day, ..Quota::default() }], &[earlier], h.app.local_now());
+    assert_eq!(h.app.quotas[0].done, 1.0);
+    assert!(!h.app.roll_quotas());
+
+    h.advance(std::time::Duration::from_secs(90));
+    assert!(h.app.roll_quotas());
+    assert_eq!(h.app.quotas[0].done, 0.0);
+
+    // and a test taken now is saved under the new day, and counts toward it
+    h.type_at_wpm("hello world", 60.0);
+    let saved = build_record(&h.app, true);
+    let saved_at = chrono::DateTime::parse_from_rfc3339(&saved.timestamp).unwrap().with_timezone(&Local);
+    assert_eq!(saved_at.date_naive(), chrono::NaiveDate::from_ymd_opt(2026, 3, 19).unwrap());
+    h.app.quotas[0].add(&saved);
+    assert_eq!(h.app.quotas[0].done, 1.0);
+}
@@ -0,0 +1,171 @@
+use typa::text::{CharStats, TypedText, KEPT_WORDS};
+
+fn typed(words: &[&str], keys: &str) -> TypedText {
+    let mut text = TypedText::new(words.iter().copied());
+    for c in keys.chars() {
+        match c {
+            ' ' => { text.type_space(); }
+            '<' => { text.backspace(); }
+            c => text.type_char(c),
+        }
+    }
+    text
+}
+
+fn counts(s: CharStats) -> (usize, usize, usize, usize) {
+    (s.correct, s.incorrect, s.extra, s.missed)
+}
+
+#[test]
+fn keystrokes_land_in_the_current_word_only() {
+    let text = typed(&["hello", "world", "again"], "hello wo");
+    assert_eq!(text.current_index(), 1);
+    assert_eq!(text.words()[0].typed(), ['h', 'e', 'l', 'l', 'o']);
+    assert!(text.words()[0].is_closed());
+    assert_eq!(text.words()[1].typed(), ['w', 'o']);
+    assert!(text.words()[2].typed().is_empty());
+    assert_eq!(text.cursor(), 8);
+    assert_eq!(text.next_char(), Some('r'));
+}
+
+#[test]
+fn backspace_reopens_the_previous_word() {
+    let mut text = typed(&["hello", "world"], "helo ");
+    assert_eq!(text.last_typed().map(|(c, _)| c), Some(' '));
+
+    assert_eq!(text.backspace(), Some(' '));
+    assert_eq!(text.current_index(), 0);
+    assert!(!text.words()[0].is_closed());
+    assert_eq!(text.backspace(), Some('o'));
+    assert_eq!(text.cursor(), 3);
+
+    let mut empty = TypedText::new(["a"]);
+    assert_eq!(empty.backspace(), None);
+}
+
+#[test]
+fn the_last_word_cannot_be_closed() {
+    let mut text = typed(&["one", "two"], "one tw");
+    assert!(!text.type_space());
+    assert_eq!(text.current_index(), 1);
+    assert!(text.is_on_last_word());
+    text.type_char('o');
+    assert!(text.is_fully_typed());
+    assert_eq!(text.next_char(), None);
+}
+
+#[test]
+fn extra_letters_widen_the_word() {
+    let text = typed(&["cat", "dog"], "catss");
+    let cat = &text.words()[0];
+    assert_eq!(cat.width(), 5);
+    assert_eq!(cat.extra(), 2);
+    assert_eq!(cat.shown(3), ('s', true));
+    assert_eq!(cat.shown(2), ('t', false));
+}
+
+#[test]
+fn scoring_matches_the_results_screen() {
+    // right, with its space
+    assert_eq!(counts(typed(&["cat", "dog"], "cat ").words()[0].stats(true)), (4, 0, 0, 0));
+    // one wrong letter costs the whole word and its space
+    assert_eq!(counts(typed(&["cat", "dog"], "cut ").words()[0].stats(true)), (0, 1, 0, 0));
+    // closed early: the rest is missed
+    let early = typed(&["cat", "dog"], "c ");
+    assert_eq!(early.words()[0].missed(), 2);
+    assert_eq!(counts(early.words()[0].stats(true)), (0, 0, 0, 2));
+    // typed past the end
+    assert_eq!(counts(typed(&["cat", "dog"], "cats ").words()[0].stats(true)), (0, 0, 1, 0));
+    // dashes and quotes that only differ in shape still count
+    assert_eq!(counts(typed(&["—", "it’s"], "- it's").stats()), (6, 0, 0, 0));
+}
+
+#[test]
+fn whole_text_counts_untyped_words_as_missed() {
+    let text = typed(&["ab", "cd", "ef"], "ab c");
+    // "ab " right, "c" typed, "d" and all of "ef" still missing
+    assert_eq!(counts(text.stats()), (3, 0, 0, 3));
+}
+
+#[test]
+fn live_correct_counts_the_current_word_up_to_the_caret() {
+    assert_eq!(typed(&["ab", "cde"], "ab cd").live_correct(), 5);
+    assert_eq!(typed(&["ab", "cde"], "ab cx").live_correct(), 3);
+    assert_eq!(typed(&["ab", "cde"], "xb cd").live_correct(), 2);
+}
+
+#[test]
+fn truncating_at_the_caret_drops_what_was_never_reached() {
+    let mut text = typed(&["one", "two", "three"], "one tw");
+    text.truncate_at_cursor();
+    assert_eq!(text.len(), 2);
+    assert_eq!(text.words()[1].target(), ['t', 'w']);
+    assert_eq!(counts(text.stats()), (6, 0, 0, 0));
+
+    // right after a space the next word goes entirely, and the space still counts
+    let mut text = typed(&["one", "two"], "one ");
+    text.truncate_at_cursor();
+    assert_eq!(text.len(), 1);
+    assert_eq!(counts(text.stats()), (4, 0, 0, 0));
+}
+
+#[test]
+fn scrolled_words_keep_their_score() {
+    let mut text = typed(&["ab", "cd", "ef", "gh"], "ab cx ef g");
+    let before = text.stats();
+    text.scroll(2);
+    assert_eq!(text.len(), 2);
+    assert_eq!(text.current_index(), 1);
+    assert_eq!(text.words()[0].target(), ['e', 'f']);
+    assert_eq!(text.stats(), before);
+    assert_eq!(text.closed_words(), 3);
+    assert_eq!(text.live_correct(), 7);
+
+    // never past the caret
+    text.scroll(10);
+    assert_eq!(text.scrolled_words(), 3);
+    assert_eq!(text.current_index(), 0);
+    assert_eq!(counts(text.stats()), (6, 1, 0, 1));
+}
+
+#[test]
+fn only_so_many_scrolled_words_are_kept() {
+    let words: Vec<String> = (0..KEPT_WORDS + 20).map(|i| if i % 2 == 0 { "ab".into() } else { "cde".into() }).collect();
+    let mut text = TypedText::new(words.iter().map(String::as_str));
+    for word in &words[..KEPT_WORDS + 10] {
+        for c in word.chars() { text.type_char(c); }
+        text.type_space();
+        text.scroll(1);
+    }
+    // the first ten are let go of, but still counted and scored
+    assert_eq!(text.dropped(), (10, 25));
+    assert_eq!(text.all_words().count(), KEPT_WORDS + 10);
+    assert_eq!(text.scrolled_words(), KEPT_WORDS + 10);
+    assert_eq!(text.closed_words(), KEPT_WORDS + 10);
+    assert_eq!(text.stats().correct, (KEPT_WORDS + 10) / 2 * 7);
+}
+
+#[test]
+fn wrapping_fills_lines_greedily() {
+    let text = TypedText::new(["aaa", "bb", "cccc", "d", "eeeeeeeeeeee", "f"]);
+    let lines = text.wrap(8, usize::MAX);
+    assert_eq!(lines, vec![0..2, 2..4, 4..5, 5..6]);
+    assert_eq!(text.line_width(&lines[0]), 6);
+    // a word wider than the line gets a line to itself
+    assert_eq!(text.line_width(&lines[2]), 12);
+}
+
+#[test]
+fn wrapping_stops_a_line_past_the_caret() {
+    let words: Vec<String> = (0..1000).map(|i| format!("w{}", i % 10)).collect();
+    let text = TypedText::new(words.iter().map(String::as_str));
+    let lines = text.wrap(10, 3);
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0], 0..3);
+}
+
+#[test]
+fn extra_letters_reflow_the_line() {
+    let text = typed(&["aaa", "bb", "cc"], "aaaxx");
+    assert_eq!(text.wrap(7, usize::MAX), vec![0..1, 1..3]);
+}
@@ -0,0 +1,66 @@
+use typa::app::{App, TestOptions};
+use typa::clock::ManualClock;
+use typa::config::{AccentConfig, AppConfig, Theme};
+use typa::models::{Mode, QuoteSelector};
+
+fn low_contrast() -> Theme {
+    Theme {
+        bg: "#2c2e34".to_string(),
+        sub: "#3a3c42".to_string(),
+        ..Theme::default()
+    }
+}
+
+#[test]
+fn warns_without_touching_colors() {
+    let mut theme = low_contrast();
+    let notes = theme.enforce_contrast(4.5, false);
+    assert!(notes.iter().any(|n| n.starts_with("theme.sub")), "{:?}", notes);
+    assert_eq!(theme.sub, "#3a3c42");
+}
+
+#[test]
+fn adjusts_colors_up_to_the_minimum() {
+    let mut theme = low_contrast();
+    theme.enforce_contrast(4.5, true);
+    assert_ne!(theme.sub, "#3a3c42");
+    // a second pass has nothing left to complain about
+    assert!(theme.enforce_contrast(4.5, false).is_empty());
+}
+
+#[test]
+fn readable_colors_are_left_alone() {
+    let mut theme = Theme { bg: "#000000".to_string(), text: "#ffffff".to_string(), ..Theme::default() };
+    let notes = theme.enforce_contrast(4.5, true);
+    assert!(!notes.iter().any(|n| n.starts_with("theme.text")));
+    assert_eq!(theme.text, "#ffffff");
+}
+
+#[test]
+fn each_mode_can_have_its_own_accent() {
+    let accents = AccentConfig { time: Some("#7ec4cf".into()), quote: Some("error".into()), ..AccentConfig::default() };
+    let theme = Theme::default();
+    assert_eq!(accents.main_for(&Mode::Time(30), &theme), "#7ec4cf");
+    assert_eq!(accents.main_for(&Mode::Quote(QuoteSelector::Id(1)), &theme), theme.error);
+    assert_eq!(accents.main_for(&Mode::Words(25), &theme), theme.main);
+
+    let config = AppConfig { accents, ..AppConfig::default() };
+    let options = TestOptions { mode: Mode::Time(30), ..TestOptions::default() };
+    let app = App::detached(options, config, Box::new(ManualClock::new())).unwrap();
+    assert_eq!(app.config.theme.main, "#7ec4cf");
+}
+
+#[test]
+fn accents_are_held_to_the_same_contrast() {
+    let theme = low_contrast();
+    let mut accents = AccentConfig { time: Some("#34363c".into()), quote: Some("sub".into()), ..AccentConfig::default() };
+    let notes = accents.enforce_contrast(&theme, 4.5, false);
+    assert!(notes.iter().any(|n| n.starts_with("accents.time #34363c")), "{:?}", notes);
+    // a theme color's name is checked as that color, with the theme
+    assert!(!notes.iter().any(|n| n.starts_with("accents.quote")));
+    assert_eq!(accents.time.as_deref(), Some("#34363c"));
+
+    accents.enforce_contrast(&theme, 4.5, true);
+    assert_ne!(accents.time.as_deref(), Some("#34363c"));
+    assert!(accents.enforce_contrast(&theme, 4.5, false).is_empty());
+}
@@ -0,0 +1,558 @@
+use std::time::Duration;
+use typa::models::{AppState, FailReason, FailRules, Mode, ResultsTab};
+use typa::app::TestOptions;
+use typa::config::{DictationConfig, LastWord, Reveal, ScoreFormula};
+use typa::testing::{Harness, BACKSPACE};
+use typa::ui::actions::results_footer;
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-6, "expected {expected}, got {actual}");
+}
+
+#[test]
+fn clean_run_counts_every_char() {
+    let mut h = Harness::new(Mode::Words(2), &["hello", "world"]);
+    // 11 keys over 2 seconds
+    h.type_at_wpm("hello world", 60.0);
+
+    assert!(h.is_finished());
+    assert_eq!(h.elapsed(), Duration::from_secs(2));
+    assert_close(h.app.test.final_wpm, 66.0);
+    assert_close(h.app.test.final_raw_wpm, 66.0);
+    assert_close(h.app.test.final_accuracy, 100.0);
+}
+
+#[test]
+fn corrected_typo_costs_accuracy_not_wpm() {
+    let mut h = Harness::new(Mode::Words(2), &["hello", "world"]);
+    h.type_at_wpm(&format!("hellp{BACKSPACE}o world"), 60.0);
+
+    assert!(h.is_finished());
+    // 12 presses over 2.4s, 11 of them land correctly
+    assert_close(h.app.test.final_raw_wpm, 60.0);
+    assert_close(h.app.test.final_wpm, 55.0);
+    assert_close(h.app.test.final_accuracy, 11.0 / 12.0 * 100.0);
+}
+
+#[test]
+fn uncorrected_word_drops_out_of_wpm() {
+    let mut h = Harness::new(Mode::Words(2), &["hello", "world"]);
+    h.type_at_wpm("hellp world", 60.0);
+
+    assert!(h.is_finished());
+    assert!(h.app.test.final_wpm < h.app.test.final_raw_wpm);
+}
+
+#[test]
+fn clock_waits_for_the_first_key() {
+    let mut h = Harness::new(Mode::Time(5), &["a", "b", "c"]);
+    h.advance(Duration::from_secs(30));
+
+    assert_eq!(h.app.test.state, AppState::Waiting);
+    assert_eq!(h.elapsed(), Duration::ZERO);
+}
+
+#[test]
+fn time_mode_ends_on_the_limit() {
+    let mut h = Harness::new(Mode::Time(3), &["a", "b", "c"]);
+    h.type_at_wpm("a b c", 60.0);
+    assert!(!h.is_finished());
+
+    h.advance(Duration::from_secs(10));
+
+    assert!(h.is_finished());
+    assert_close(h.app.test.final_time, 3.0);
+    // one snapshot per whole second
+    assert_eq!(h.app.test.wpm_history.len(), 3);
+}
+
+#[test]
+fn sudden_death_fails_on_first_mistake() {
+    let options = TestOptions {
+        mode: Mode::Words(2),
+        allow_repeats: true,
+        fail_rules: FailRules { sudden_death: true, ..FailRules::default() },
+        ..TestOptions::default()
+    };
+    let mut h = Harness::with_options(options, &["hello", "world"]);
+    h.type_at_wpm("hex", 60.0);
+
+    assert!(h.is_finished());
+    assert_eq!(h.app.test.fail_reason, Some(FailReason::SuddenDeath));
+}
+
+#[test]
+fn score_folds_accuracy_into_wpm() {
+    let mut h = Harness::new(Mode::Words(2), &["hello", "world"]);
+    h.app.config.score_formula = ScoreFormula::Squared;
+    h.type_at_wpm(&format!("hellp{BACKSPACE}o world"), 60.0);
+
+    let acc = 11.0 / 12.0;
+    assert_close(h.app.test.final_score.unwrap(), 55.0 * acc * acc);
+}
+
+#[test]
+fn quick_fixes_are_forgiven_in_settled_accuracy() {
+    let typo = format!("hellp{BACKSPACE}o world");
+
+    // backspace lands 200ms after the typo
+    let mut quick = Harness::new(Mode::Words(2), &["hello", "world"]);
+    quick.app.config.forgive_window = Some(Duration::from_millis(250));
+    quick.type_at_wpm(&typo, 60.0);
+    assert_close(quick.app.test.final_accuracy, 11.0 / 12.0 * 100.0);
+    assert_close(quick.app.test.final_settled_accuracy.unwrap(), 100.0);
+
+    let mut slow = Harness::new(Mode::Words(2), &["hello", "world"]);
+    slow.app.config.forgive_window = Some(Duration::from_millis(150));
+    slow.type_at_wpm(&typo, 60.0);
+    assert_close(slow.app.test.final_settled_accuracy.unwrap(), 11.0 / 12.0 * 100.0);
+}
+
+#[test]
+fn backspaced_mistakes_count_as_corrected() {
+    let mut h = Harness::new(Mode::Words(3), &["hello", "big", "world"]);
+    // one typo fixed, then a wrong word left standing (its bad key and its space)
+    h.type_at_wpm(&format!("hellp{BACKSPACE}o bog world"), 60.0);
+
+    assert!(h.is_finished());
+    let errors = h.app.error_counts();
+    assert_eq!((errors.made, errors.corrected, errors.uncorrected()), (3, 1, 2));
+    // 16 keystrokes, one backspace
+    assert_eq!(h.app.test.backspaces, 1);
+    assert_close(h.app.backspace_ratio(), 100.0 / 16.0);
+}
+
+#[test]
+fn stats_survive_scrolling() {
+    let words = vec!["abc"; 60];
+    let mut h = Harness::new(Mode::Words(60), &words);
+    h.type_at_wpm(&format!("abx {}", vec!["abc"; 59].join(" ")), 60.0);
+
+    assert!(h.is_finished());
+    assert!(h.app.test.text.scrolled_words() > 0);
+    // the wrong first word scrolled away long before the end, and still counts
+    assert_eq!(h.app.resolved_char_stats(), (235, 1, 0, 0));
+    assert_eq!(h.app.test.text.closed_words(), 59);
+}
+
+#[test]
+fn dash_at_the_end_completes_when_typed_as_a_hyphen() {
+    let mut h = Harness::new(Mode::Words(3), &["wait", "for", "—"]);
+    h.type_at_wpm("wait for -", 60.0);
+
+    assert!(h.is_finished());
+    assert_eq!(h.app.resolved_char_stats(), (10, 0, 0, 0));
+}
+
+#[test]
+fn ellipsis_at_the_end_completes_on_its_last_dot() {
+    let mut h = Harness::new(Mode::Words(2), &["it", "was..."]);
+    h.type_at_wpm("it was..", 60.0);
+    assert!(!h.is_finished());
+
+    h.type_at_wpm(".", 60.0);
+    assert!(h.is_finished());
+    assert_close(h.app.test.final_accuracy, 100.0);
+}
+
+#[test]
+fn curly_quote_at_the_end_completes_when_typed_straight() {
+    let mut h = Harness::new(Mode::Words(2), &["he", "said\u{201D}"]);
+    h.type_at_wpm("he said\"", 60.0);
+
+    assert!(h.is_finished());
+}
+
+#[test]
+fn wrong_last_word_waits_to_be_fixed() {
+    let mut h = Harness::new(Mode::Words(2), &["hello", "world"]);
+    h.type_at_wpm("hello wprld", 60.0);
+    let counts = |h: &Harness| (h.app.test.gross_char_count, h.app.test.live_incorrect_keystrokes, h.app.test.keystrokes.len());
+    let before = counts(&h);
+    // the space has no word to close, so it counts for nothing
+    h.type_at_wpm(" ", 60.0);
+    assert!(!h.is_finished());
+    assert_eq!(counts(&h), before);
+    assert!(h.app.test.key_tallies.get(&' ').is_some_and(|t| t.misses == 0));
+
+    h.type_at_wpm(&format!("{BACKSPACE}{BACKSPACE}{BACKSPACE}{BACKSPACE}orld"), 60.0);
+    assert!(h.is_finished());
+}
+
+fn time_up_in(last_word: LastWord, typed: &str) -> Harness {
+    let mut h = Harness::new(Mode::Time(2), &["hello", "world", "again"]);
+    h.app.config.last_word = last_word;
+    // 1.4s of typing, then the clock runs out mid-word
+    h.type_at_wpm(typed, 60.0);
+    h.advance(Duration::from_secs(1));
+    h
+}
+
+#[test]
+fn time_up_mid_word_scores_what_was_typed() {
+    let h = time_up_in(LastWord::Truncate, "hello wo");
+    assert!(h.is_finished());
+    assert_eq!(h.app.resolved_char_stats(), (8, 0, 0, 0));
+}
+
+#[test]
+fn retry_after_time_up_gets_the_words_whole() {
+    let mut h = time_up_in(LastWord::Truncate, "hello wo");
+    h.app.retry_last_test();
+    assert_eq!(h.app.test.words()[..3], ["hello", "world", "again"]);
+}
+
+#[test]
+fn grace_lets_the_last_word_be_finished() {
+    let mut h = time_up_in(LastWord::Grace, "hello wo");
+    assert!(!h.is_finished());
+
+    h.type_at_wpm("rld", 60.0);
+    assert!(h.is_finished());
+    assert_eq!(h.app.resolved_char_stats(), (11, 0, 0, 0));
+    assert_close(h.app.test.final_time, 2.8);
+}
+
+#[test]
+fn grace_runs_out() {
+    let mut h = time_up_in(LastWord::Grace, "hello wo");
+    h.advance(Duration::from_secs(2));
+    assert!(h.is_finished());
+    assert_eq!(h.app.resolved_char_stats(), (8, 0, 0, 0));
+}
+
+#[test]
+fn count_credits_a_correct_partial_word() {
+    let h = time_up_in(LastWord::Count, "hello wo");
+    assert!(h.is_finished());
+    assert_eq!(h.app.resolved_char_stats(), (11, 0, 0, 0));
+
+    let h = time_up_in(LastWord::Count, "hello wx");
+    assert_eq!(h.app.resolved_char_stats(), (6, 1, 0, 0));
+}
+
+#[test]
+fn corrections_are_charted_in_the_second_the_error_was_made() {
+    let mut h = Harness::new(Mode::Time(5), &["hello", "world"]);
+    h.key('x');
+    h.advance(Duration::from_millis(2500));
+    // fixed two seconds later, then a slip fixed within its own second
+    h.key(BACKSPACE);
+    h.key('q');
+    h.advance(Duration::from_millis(300));
+    h.key(BACKSPACE);
+    // the z is left standing
+    h.type_str("hez", Duration::from_millis(300));
+    h.advance(Duration::from_secs(5));
+
+    assert!(h.is_finished());
+    let per_second = |history: &[(f64, f64)]| history.iter().map(|&(_, n)| n as usize).collect::<Vec<_>>();
+    assert_eq!(per_second(&h.app.test.errors_history), [1, 0, 1, 1, 0]);
+    assert_eq!(per_second(&h.app.test.corrected_history), [1, 0, 1, 0, 0]);
+}
+
+#[test]
+fn chart_cursor_stays_on_the_chart() {
+    let mut h = Harness::new(Mode::Time(3), &["a", "b", "c"]);
+    h.app.move_chart_cursor(-1);
+    assert_eq!(h.app.test.chart_cursor, None);
+
+    h.type_at_wpm("a b", 60.0);
+    h.advance(Duration::from_secs(5));
+    h.app.move_chart_cursor(-1);
+    assert_eq!(h.app.test.chart_cursor, Some(2));
+    h.app.move_chart_cursor(1);
+    assert_eq!(h.app.test.chart_cursor, Some(2));
+
+    h.app.test.chart_cursor = None;
+    h.app.move_chart_cursor(1);
+    h.app.move_chart_cursor(-1);
+    assert_eq!(h.app.test.chart_cursor, Some(0));
+}
+
+#[test]
+fn keys_are_tallied_by_what_should_have_been_pressed() {
+    let mut h = Harness::new(Mode::Words(2), &["cat", "dog"]);
+    h.type_at_wpm(&format!("cx{BACKSPACE}at dogg{BACKSPACE}"), 60.0);
+    assert!(h.is_finished());
+
+    let a = h.app.test.key_tallies[&'a'];
+    assert_eq!((a.presses, a.misses), (2, 1));
+    assert_close(a.accuracy(), 50.0);
+    assert_eq!(h.app.test.key_tallies[&' '].presses, 1);
+    // the extra g wasn't meant for anything
+    assert_eq!(h.app.test.key_tallies[&'g'].presses, 1);
+}
+
+#[test]
+fn replay_ends_where_the_test_did() {
+    let words: Vec<String> = (0..60).map(|i| format!("w{}", i % 7)).collect();
+    let words: Vec<&str> = words.iter().map(String::as_str).collect();
+    let mut h = Harness::new(Mode::Words(60), &words);
+    let typed = words.join(" ").replacen("w3", &format!("wx{BACKSPACE}3"), 1).replacen("w5", "w55", 1);
+    h.type_at_wpm(&typed, 60.0);
+    assert!(h.is_finished());
+    assert!(h.app.test.text.scrolled_words() > 0);
+
+    let end = h.app.test.keystrokes.last().unwrap().0;
+    let replayed = h.app.replay_text(end);
+    let typed_of = |words: Vec<&typa::text::Word>| words.iter().map(|w| w.typed().iter().collect::<String>()).collect::<Vec<_>>();
+    assert_eq!(typed_of(replayed.all_words().collect()), typed_of(h.app.test.text.all_words().collect()));
+    assert!(replayed.is_on_last_word());
+
+    // halfway through, only part of it is there
+    assert!(h.app.replay_text(end / 2.0).started_words() < replayed.started_words());
+}
+
+#[test]
+fn results_tabs_wrap_around() {
+    let mut h = Harness::new(Mode::Words(1), &["a"]);
+    h.app.step_results_tab(1);
+    assert_eq!(h.app.test.results_tab, ResultsTab::Summary);
+
+    h.type_at_wpm("a", 60.0);
+    h.app.step_results_tab(-1);
+    assert_eq!(h.app.test.results_tab, ResultsTab::Replay);
+    assert!(h.app.replay_position().is_some());
+    h.app.step_results_tab(1);
+    assert_eq!(h.app.test.results_tab, ResultsTab::Summary);
+    assert!(h.app.replay_position().is_none());
+}
+
+#[test]
+fn paused_time_does_not_count() {
+    let mut h = Harness::new(Mode::Time(5), &["a", "b", "c", "d"]);
+    h.app.pause();
+    assert!(!h.app.is_paused());
+
+    h.type_at_wpm("a b", 60.0);
+    let before = h.elapsed();
+    h.app.pause();
+    h.advance(Duration::from_secs(60));
+    h.app.check_time();
+    assert_eq!(h.app.test.state, AppState::Running);
+    assert_eq!(h.elapsed(), before);
+
+    h.app.resume();
+    h.advance(Duration::from_secs(1));
+    assert_eq!(h.elapsed(), before + Duration::from_secs(1));
+
+    // typing picks it back up too
+    h.app.pause();
+    h.advance(Duration::from_secs(10));
+    h.key(' ');
+    assert!(!h.app.is_paused());
+    assert_eq!(h.elapsed(), before + Duration::from_secs(1));
+}
+
+#[test]
+fn title_shows_time_left_and_live_wpm() {
+    use typa::ui::title::title_text;
+    let mut h = Harness::new(Mode::Time(30), &["abcd"; 40]);
+    assert_eq!(title_text(&h.app), "typa");
+
+    h.type_at_wpm("abcd abcd abcd", 60.0);
+    h.app.check_time();
+    assert_eq!(title_text(&h.app), "typa 00:28 60wpm");
+
+    h.app.pause();
+    assert_eq!(title_text(&h.app), "typa paused");
+}
+
+#[test]
+fn large_text_wraps_at_half_the_width() {
+    let words = vec!["abcd"; 40];
+    let mut h = Harness::new(Mode::Words(40), &words);
+    // 78 columns to wrap in, or 39 letters at two cells each
+    h.app.resize(100, 30);
+    assert_eq!(h.app.test.visual_lines[0].len(), 15);
+
+    h.app.config.accessibility.large_text = true;
+    h.app.resize(100, 30);
+    assert_eq!(h.app.test.visual_lines[0].len(), 8);
+}
+
+#[test]
+fn session_tallies_every_finished_test() {
+    let mut h = Harness::new(Mode::Words(2), &["hello", "world"]);
+    h.type_at_wpm("hello world", 60.0);
+    h.app.retry_last_test();
+    h.type_at_wpm("hello world", 30.0);
+    h.app.retry_last_test();
+    // left unfinished, so it doesn't count
+    h.type_at_wpm("hello", 60.0);
+
+    let session = &h.app.session;
+    assert_eq!(session.results.len(), 2);
+    assert_close(session.best_wpm().unwrap(), 66.0);
+    // 2 seconds at 66 and 4 at 33
+    assert_close(session.average_wpm().unwrap(), 44.0);
+    assert_eq!(session.summary().unwrap(), "2 tests, 44 wpm average, 66 best, 100% accuracy, 6s typing");
+}
+
+#[test]
+fn break_is_suggested_after_enough_typing() {
+    let mut h = Harness::new(Mode::Words(2), &["hello", "world"]);
+    h.app.config.break_after = Some(Duration::from_secs(3));
+    h.type_at_wpm("hello world", 60.0);
+    assert!(!h.app.test.break_due);
+
+    h.app.retry_last_test();
+    h.type_at_wpm("hello world", 60.0);
+    assert!(h.app.test.break_due);
+
+    h.app.dismiss_break();
+    h.app.retry_last_test();
+    h.type_at_wpm("hello world", 60.0);
+    assert!(!h.app.test.break_due);
+
+    // a long enough gap between tests is a break too
+    h.app.retry_last_test();
+    h.advance(Duration::from_secs(600));
+    h.type_at_wpm("hello world", 60.0);
+    assert!(!h.app.test.break_due);
+}
+
+#[test]
+fn strict_space_types_a_mistake_mid_word() {
+    let options = TestOptions { mode: Mode::Words(2), strict_space: true, ..TestOptions::default() };
+    let mut h = Harness::with_options(options, &["hello", "world"]);
+    h.type_at_wpm("hel ", 60.0);
+
+    // still on the first word, with the space in it as a wrong letter
+    assert_eq!(h.app.test.text.current_index(), 0);
+    assert_eq!(h.app.test.text.current().unwrap().typed(), &['h', 'e', 'l', ' ']);
+    assert_eq!(h.app.test.stray_spaces, 1);
+    assert_eq!(h.app.test.live_incorrect_keystrokes, 1);
+
+    h.type_at_wpm(&format!("{BACKSPACE}lo world"), 60.0);
+    assert!(h.is_finished());
+    assert_eq!(h.app.replay_text(f64::MAX).words()[0].typed(), &['h', 'e', 'l', 'l', 'o']);
+}
+
+#[test]
+fn expert_fails_on_a_wrong_word_not_a_stray_space() {
+    let fail_rules = FailRules { expert: true, ..FailRules::default() };
+    let options = TestOptions { mode: Mode::Words(2), strict_space: true, fail_rules, ..TestOptions::default() };
+    let mut h = Harness::with_options(options, &["hello", "world"]);
+    // a space mid-word goes into it, and doesn't submit anything
+    h.type_at_wpm("hel ", 60.0);
+    assert_eq!(h.app.test.fail_reason, None);
+
+    h.type_at_wpm(&format!("{BACKSPACE}lp "), 60.0);
+    assert_eq!(h.app.test.fail_reason, Some(FailReason::Expert));
+}
+
+#[test]
+fn kids_get_a_cheer_for_each_right_word() {
+    let options = TestOptions { mode: Mode::Words(3), kids: true, ..TestOptions::default() };
+    let mut h = Harness::with_options(options, &["cat", "dog", "sun"]);
+    assert!(!h.app.config.use_punctuation && h.app.config.practice);
+
+    h.type_at_wpm("cat ", 30.0);
+    assert!(h.app.cheer().is_some());
+    h.advance(Duration::from_secs(1));
+    assert!(h.app.cheer().is_none());
+
+    h.type_at_wpm("dgo ", 30.0);
+    assert!(h.app.cheer().is_none());
+
+    h.type_at_wpm("sun", 30.0);
+    assert!(h.is_finished());
+    assert_eq!(h.app.stars(), 0);
+}
+
+#[test]
+fn results_footer_only_offers_what_works() {
+    let mut h = Harness::new(Mode::Words(1), &["a"]);
+    h.type_at_wpm("a", 60.0);
+    assert_eq!(
+        results_footer(&h.app, 100),
+        "tab: next test  |  r: retry same words  |  ←/→: tabs  |  s: save card  |  esc: quit"
+    );
+    assert_eq!(results_footer(&h.app, 60), "tab: next test  |  r: retry same words  |  esc: quit");
+    assert_eq!(results_footer(&h.app, 40), "tab: next  |  r: retry  |  esc: quit");
+
+    // no tabs or card after a failed test, but its mistake can be drilled
+    let options = TestOptions { mode: Mode::Words(2), fail_rules: FailRules { sudden_death: true, ..FailRules::default() }, ..TestOptions::default() };
+    let mut h = Harness::with_options(options, &["a", "b"]);
+    h.type_at_wpm("x", 60.0);
+    assert!(h.is_finished());
+    assert_eq!(results_footer(&h.app, 100), "tab: next test  |  r: retry same words  |  d: drill mistakes  |  esc: quit");
+}
+
+#[test]
+fn drill_repeats_missed_words_until_clean() {
+    let mut h = Harness::new(Mode::Time(30), &["the", "quick", "brown", "fox", "jumps"]);
+    h.type_at_wpm(&format!("the quixk{BACKSPACE}{BACKSPACE}ck brwn fox "), 60.0);
+    h.advance(Duration::from_secs(30));
+    assert!(h.is_finished());
+    assert_eq!(h.app.missed_words(), vec!["quick", "brown"]);
+
+    h.app.drill();
+    assert_eq!(h.app.config.mode, Mode::Words(2));
+    assert_eq!(h.app.test_type_label(), "word 2 english drill round 1");
+    h.type_at_wpm("quick brwon", 60.0);
+    h.advance(Duration::from_secs(1));
+    assert!(!h.is_finished());
+    h.type_at_wpm(&format!("{BACKSPACE}{BACKSPACE}{BACKSPACE}own"), 60.0);
+    assert!(h.is_finished());
+    assert_eq!(results_footer(&h.app, 100), "tab: next round  |  r: retry same words  |  ←/→: tabs  |  s: save card  |  esc: quit");
+
+    h.app.drill();
+    assert_eq!(h.app.drill.as_ref().map(|d| d.round), Some(2));
+    h.type_at_wpm("brown", 60.0);
+    assert!(h.is_finished());
+    assert_eq!(results_footer(&h.app, 100), "tab: done  |  r: retry same words  |  ←/→: tabs  |  s: save card  |  esc: quit");
+
+    // a clean round ends it, back to the test it started from
+    h.app.drill();
+    assert!(h.app.drill.is_none());
+    assert_eq!(h.app.config.mode, Mode::Time(30));
+    assert_eq!(h.app.test.state, AppState::Waiting);
+}
+
+#[test]
+fn dictation_flashes_each_word_then_hides_it() {
+    let options = TestOptions { mode: Mode::Words(3), dictation: true, ..TestOptions::default() };
+    let mut h = Harness::with_options(options, &["one", "two", "six"]);
+    h.app.config.dictation = Some(DictationConfig { reveal: Reveal::Flash, flash_ms: 500 });
+
+    // the first word stays up until the test starts
+    h.advance(Duration::from_secs(2));
+    assert_eq!(h.app.hidden_from(), Some(1));
+    h.key('o');
+    assert_eq!(h.app.hidden_from(), Some(0));
+
+    h.type_str("ne ", Duration::from_millis(100));
+    assert_eq!(h.app.hidden_from(), Some(2));
+    // the loop wakes for the word to go, even when nothing else would draw a frame
+    let deadline = h.app.flash_deadline().unwrap();
+    assert_eq!(deadline.duration_since(h.app.now()), Duration::from_millis(500));
+    h.advance(Duration::from_millis(500));
+    assert_eq!(h.app.hidden_from(), Some(1));
+    assert_eq!(h.app.flash_deadline(), None);
+
+    h.app.config.dictation = Some(DictationConfig { reveal: Reveal::Current, ..DictationConfig::default() });
+    assert_eq!(h.app.hidden_from(), Some(2));
+}
+
+#[test]
+fn next_test_is_made_while_the_results_are_up() {
+    let mut h = Harness::new(Mode::Words(2), &["hello", "world"]);
+    assert!(!h.app.is_prewarmed());
+    h.type_at_wpm("hello world", 60.0);
+    assert!(h.app.is_prewarmed());
+
+    h.app.restart_test();
+    assert!(!h.app.is_prewarmed());
+    assert_eq!(h.app.test.state, AppState::Waiting);
+    assert_eq!(h.app.test.words().len(), 2);
+
+    // a quote test has nothing made ahead
+    let options = TestOptions { mode: Mode::Quote(typa::models::QuoteSelector::Category(typa::models::QuoteLength::Short)), ..TestOptions::default() };
+    let mut h = Harness::with_options(options, &["hello", "world"]);
+    h.type_at_wpm("hello world", 60.0);
+    assert!(h.is_finished());
+    assert!(!h.app.is_prewarmed());
+}
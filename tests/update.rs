@@ -0,0 +1,28 @@
+use chrono::{Duration, Utc};
+use typa::update::{is_newer, parse_feed, Cache};
+
+#[test]
+fn only_a_later_release_is_newer() {
+    assert!(is_newer("v0.5.0", "0.4.0"));
+    assert!(is_newer("0.4.1", "0.4.0"));
+    assert!(is_newer("1.0", "0.9.9"));
+    assert!(!is_newer("v0.4.0", "0.4.0"));
+    assert!(!is_newer("0.4.0-rc.1", "0.4.0"));
+    assert!(!is_newer("0.3.9", "0.4.0"));
+    assert!(!is_newer("nightly", "0.4.0"));
+}
+
+#[test]
+fn the_feed_gives_the_release_tag() {
+    assert_eq!(parse_feed(br#"{"tag_name": "v0.5.0", "name": "typa 0.5.0", "draft": false}"#).as_deref(), Some("v0.5.0"));
+    assert_eq!(parse_feed(b"<html>rate limited</html>"), None);
+}
+
+#[test]
+fn a_check_lasts_a_day() {
+    let now = Utc::now();
+    let at = |ago: Duration| Cache { checked_at: Some((now - ago).to_rfc3339()), latest: None };
+    assert!(at(Duration::hours(23)).is_fresh(now));
+    assert!(!at(Duration::hours(25)).is_fresh(now));
+    assert!(!Cache::default().is_fresh(now));
+}
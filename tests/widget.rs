@@ -0,0 +1,41 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{backend::TestBackend, layout::Rect, Terminal};
+use typa::models::Mode;
+use typa::testing::Harness;
+use typa::widget::{TypaState, TypaWidget};
+
+fn screen(state: &mut TypaState, area: Rect) -> Vec<String> {
+    let mut terminal = Terminal::new(TestBackend::new(30, 8)).unwrap();
+    terminal.draw(|f| f.render_stateful_widget(TypaWidget, area, state)).unwrap();
+    let buf = terminal.backend().buffer().clone();
+    (0..buf.area.height)
+        .map(|y| (0..buf.area.width).map(|x| buf[(x, y)].symbol()).collect::<String>().trim_end().to_string())
+        .collect()
+}
+
+fn press(state: &mut TypaState, code: KeyCode) -> bool {
+    state.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+}
+
+#[test]
+fn the_widget_wraps_to_its_pane_and_takes_forwarded_keys() {
+    let h = Harness::new(Mode::Words(4), &["alpha", "beta", "gamma", "delta"]);
+    let mut state = TypaState::from_app(h.app);
+    let pane = Rect::new(2, 1, 14, 5);
+
+    assert_eq!(&screen(&mut state, pane)[1..5], ["  0/4", "  alpha beta", "  gamma delta", ""]);
+
+    for c in "alpha beta gamma delt".chars() {
+        assert!(press(&mut state, KeyCode::Char(c)));
+    }
+    assert!(!press(&mut state, KeyCode::Esc));
+    assert!(!state.is_finished());
+    assert!(press(&mut state, KeyCode::Char('a')));
+    assert!(state.is_finished());
+    let done = screen(&mut state, Rect::new(0, 0, 30, 4));
+    assert!(done[0].ends_with("wpm  100.0% accuracy"), "{:?}", done);
+    assert_eq!(done[3], "tab: again");
+
+    assert!(press(&mut state, KeyCode::Tab));
+    assert!(!state.is_finished());
+}